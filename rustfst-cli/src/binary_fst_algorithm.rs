@@ -2,13 +2,15 @@ use std::fs::File;
 use std::io::Write;
 use std::time::{Duration, Instant};
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::ArgMatches;
 use colored::Colorize;
 use log::{debug, info};
 
 use rustfst::prelude::*;
 
+use crate::weight::{read_fst_data, CliWeight};
+
 fn duration_to_seconds(duration: &Duration) -> f64 {
     duration.as_secs() as f64 + duration.subsec_nanos() as f64 * 1.0e-9
 }
@@ -28,43 +30,60 @@ pub trait BinaryFstAlgorithm {
     fn get_path_out(&self) -> &str;
     fn get_algorithm_name(&self) -> String;
 
-    fn read(&self) -> Result<(VectorFst<TropicalWeight>, VectorFst<TropicalWeight>)> {
+    fn read<W: CliWeight>(&self) -> Result<(VectorFst<W>, VectorFst<W>)> {
         Ok((
-            VectorFst::<TropicalWeight>::read(self.get_path_in_1())?,
-            VectorFst::<TropicalWeight>::read(self.get_path_in_2())?,
+            VectorFst::<W>::read(self.get_path_in_1())?,
+            VectorFst::<W>::read(self.get_path_in_2())?,
         ))
     }
 
-    fn run_algorithm(
+    fn run_algorithm<W: CliWeight>(
         &self,
-        fst_1: VectorFst<TropicalWeight>,
-        fst_2: VectorFst<TropicalWeight>,
-    ) -> Result<VectorFst<TropicalWeight>>;
+        fst_1: VectorFst<W>,
+        fst_2: VectorFst<W>,
+    ) -> Result<VectorFst<W>>;
 
-    fn write(&self, fst: &VectorFst<TropicalWeight>) -> Result<()> {
+    fn write<W: CliWeight>(&self, fst: &VectorFst<W>) -> Result<()> {
         fst.write(self.get_path_out())
     }
 
     fn run_cli_or_bench(&self, m: &ArgMatches) -> Result<()> {
-        if m.contains_id("bench") {
-            // Run bench
-            self.run_bench(
-                m.get_one::<String>("n_warm_ups").unwrap().parse().unwrap(),
-                m.get_one::<String>("n_iters").unwrap().parse().unwrap(),
-                m.get_one::<String>("export-markdown").map(|s| s.as_str()),
-            )
-        } else {
-            // Run cli
-            self.run_cli()
+        // The weight type is the one stored in the headers of the input FSTs.
+        let (hdr_1, data_1) = read_fst_data(self.get_path_in_1())?;
+        let (hdr_2, data_2) = read_fst_data(self.get_path_in_2())?;
+        if hdr_1.tr_type != hdr_2.tr_type {
+            bail!(
+                "The input FSTs must have the same arc type : {} has arc type {} but {} has arc type {}",
+                self.get_path_in_1(),
+                hdr_1.tr_type,
+                self.get_path_in_2(),
+                hdr_2.tr_type
+            );
         }
+        dispatch_weight!(hdr_1.tr_type.as_str(), W => {
+            if m.contains_id("bench") {
+                // Run bench
+                self.run_bench::<W>(
+                    m.get_one::<String>("n_warm_ups").unwrap().parse().unwrap(),
+                    m.get_one::<String>("n_iters").unwrap().parse().unwrap(),
+                    m.get_one::<String>("export-markdown").map(|s| s.as_str()),
+                )
+            } else {
+                // Run cli
+                self.run_cli::<W>(&data_1, &data_2)
+            }
+        })
     }
 
-    fn run_cli(&self) -> Result<()> {
+    /// Runs the algorithm on the FSTs parsed from `data_1` and `data_2`, the content of
+    /// the input files.
+    fn run_cli<W: CliWeight>(&self, data_1: &[u8], data_2: &[u8]) -> Result<()> {
         info!("Running {} algorithm", self.get_algorithm_name().blue());
         // Parsing
         debug!("Parsing...");
         let parsing_start = Instant::now();
-        let (fst_1, fst_2) = self.read()?;
+        let fst_1 = VectorFst::<W>::load(data_1)?;
+        let fst_2 = VectorFst::<W>::load(data_2)?;
         let duration_parsing = parsing_start.elapsed();
         debug!("Duration parsing : {:?}", &duration_parsing);
 
@@ -85,7 +104,7 @@ pub trait BinaryFstAlgorithm {
         Ok(())
     }
 
-    fn run_bench(
+    fn run_bench<W: CliWeight>(
         &self,
         n_warm_ups: usize,
         n_iters: usize,
@@ -107,7 +126,7 @@ pub trait BinaryFstAlgorithm {
         for i in 0..(n_warm_ups + n_iters) {
             // Parsing
             let parsing_start = Instant::now();
-            let (fst_1, fst_2) = self.read()?;
+            let (fst_1, fst_2) = self.read::<W>()?;
             let duration_parsing = parsing_start.elapsed();
 
             // Algorithm
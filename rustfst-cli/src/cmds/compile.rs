@@ -0,0 +1,130 @@
+use std::fs::read_to_string;
+
+use anyhow::{bail, Context, Result};
+use log::info;
+
+use rustfst::prelude::*;
+
+use crate::cmds::symt::{read_symt, symbol_to_label};
+
+/// Checks that the optional weight of a row is fully parsed as a `W`.
+fn check_weight<W: SerializableSemiring>(weight: &[&str], idx: usize) -> Result<()> {
+    if let Some(weight) = weight.first() {
+        match W::parse_text(weight) {
+            Ok(("", _)) => {}
+            _ => bail!("Invalid weight {:?} at line {}", weight, idx + 1),
+        }
+    }
+    Ok(())
+}
+
+/// Equivalent of `fstcompile` : turns a FST in AT&T text format into a binary FST.
+pub struct CompileFst {
+    path_in: String,
+    path_out: String,
+    isymbols: Option<String>,
+    osymbols: Option<String>,
+    acceptor: bool,
+    keep_isymbols: bool,
+    keep_osymbols: bool,
+    tr_type: String,
+}
+
+impl CompileFst {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        path_in: &str,
+        path_out: &str,
+        isymbols: Option<&String>,
+        osymbols: Option<&String>,
+        acceptor: bool,
+        keep_isymbols: bool,
+        keep_osymbols: bool,
+        tr_type: &str,
+    ) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+            isymbols: isymbols.cloned(),
+            osymbols: osymbols.cloned(),
+            acceptor,
+            keep_isymbols,
+            keep_osymbols,
+            tr_type: tr_type.to_string(),
+        }
+    }
+
+    pub fn run(&self) -> Result<()> {
+        dispatch_weight!(self.tr_type.as_str(), W => self.compile::<W>())
+    }
+
+    fn compile<W: SerializableSemiring>(&self) -> Result<()> {
+        let isymt = read_symt(self.isymbols.as_ref())?;
+        // As in OpenFST, acceptors use the input symbol table for both sides.
+        let osymt = if self.acceptor {
+            isymt.clone()
+        } else {
+            read_symt(self.osymbols.as_ref())?
+        };
+
+        let text = read_to_string(&self.path_in)
+            .with_context(|| format!("Can't open text FST {:?}", self.path_in))?;
+
+        // Rewrite each row in the tab-separated numeric format expected by the parser.
+        let mut rows = vec![];
+        for (idx, line) in text.lines().enumerate() {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let n_label_fields = if self.acceptor { 1 } else { 2 };
+            let row = match fields.len() {
+                0 => continue,
+                1 | 2 => {
+                    fields[0]
+                        .parse::<StateId>()
+                        .with_context(|| format!("Invalid state at line {}", idx + 1))?;
+                    check_weight::<W>(&fields[1..], idx)?;
+                    fields.join("\t")
+                }
+                n if n == 2 + n_label_fields || n == 3 + n_label_fields => {
+                    let mut row = vec![];
+                    for state in &fields[..2] {
+                        state
+                            .parse::<StateId>()
+                            .with_context(|| format!("Invalid state at line {}", idx + 1))?;
+                        row.push(state.to_string());
+                    }
+                    let ilabel = symbol_to_label(fields[2], isymt.as_ref())
+                        .with_context(|| format!("Invalid input label at line {}", idx + 1))?;
+                    let olabel = if self.acceptor {
+                        ilabel
+                    } else {
+                        symbol_to_label(fields[3], osymt.as_ref())
+                            .with_context(|| format!("Invalid output label at line {}", idx + 1))?
+                    };
+                    row.push(ilabel.to_string());
+                    row.push(olabel.to_string());
+                    let weight = &fields[2 + n_label_fields..];
+                    check_weight::<W>(weight, idx)?;
+                    row.extend(weight.iter().map(|s| s.to_string()));
+                    row.join("\t")
+                }
+                n => bail!("Unexpected number of fields ({}) at line {}", n, idx + 1),
+            };
+            rows.push(row);
+        }
+
+        let mut fst = VectorFst::<W>::from_text_string(&rows.join("\n"))?;
+        if self.keep_isymbols {
+            if let Some(isymt) = isymt {
+                fst.set_input_symbols(isymt);
+            }
+        }
+        if self.keep_osymbols {
+            if let Some(osymt) = osymt {
+                fst.set_output_symbols(osymt);
+            }
+        }
+        fst.write(&self.path_out)?;
+        info!("FST compiled to {}", self.path_out);
+        Ok(())
+    }
+}
@@ -4,9 +4,9 @@ use rustfst::algorithms::compose::{
     compose, ComposeFst, ComposeFstOpOptions, LabelReachableData, MatcherFst,
 };
 use rustfst::fst_impls::VectorFst;
-use rustfst::semirings::TropicalWeight;
 
 use crate::binary_fst_algorithm::BinaryFstAlgorithm;
+use crate::weight::CliWeight;
 use rustfst::algorithms::compose::compose_filters::{
     AltSequenceComposeFilterBuilder, ComposeFilterBuilder,
 };
@@ -65,14 +65,14 @@ impl BinaryFstAlgorithm for ComposeAlgorithm {
         "compose".to_string()
     }
 
-    fn run_algorithm(
+    fn run_algorithm<W: CliWeight>(
         &self,
-        fst_1: VectorFst<TropicalWeight>,
-        mut fst_2: VectorFst<TropicalWeight>,
-    ) -> Result<VectorFst<TropicalWeight>> {
+        fst_1: VectorFst<W>,
+        mut fst_2: VectorFst<W>,
+    ) -> Result<VectorFst<W>> {
         match self.compose_type {
             ComposeType::Default => {
-                compose::<TropicalWeight, VectorFst<_>, VectorFst<_>, _, _, _>(&fst_1, &fst_2)
+                compose::<W, VectorFst<_>, VectorFst<_>, _, _, _>(&fst_1, &fst_2)
             }
             ComposeType::LookAhead => {
                 type TLaFst<'a, S, F> = MatcherFst<
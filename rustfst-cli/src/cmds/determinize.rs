@@ -4,6 +4,7 @@ use rustfst::algorithms::determinize::{DeterminizeConfig, DeterminizeType};
 use rustfst::prelude::*;
 
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::weight::CliWeight;
 
 pub struct DeterminizeAlgorithm {
     path_in: String,
@@ -24,7 +25,7 @@ impl UnaryFstAlgorithm for DeterminizeAlgorithm {
         "determinize".to_string()
     }
 
-    fn run_algorithm(&self, fst: VectorFst<TropicalWeight>) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm<W: CliWeight>(&self, fst: VectorFst<W>) -> Result<VectorFst<W>> {
         let det_config = DeterminizeConfig::default().with_det_type(self.det_type);
         let fst = determinize::determinize_with_config(&fst, det_config)?;
         Ok(fst)
@@ -0,0 +1,54 @@
+use anyhow::Result;
+use log::info;
+
+use rustfst::prelude::*;
+use rustfst::DrawingConfig;
+
+use crate::cmds::symt::read_symt;
+use crate::weight::read_fst_data;
+
+/// Equivalent of `fstdraw` : writes a binary FST in the GraphViz DOT format.
+pub struct DrawFst {
+    path_in: String,
+    path_out: String,
+    isymbols: Option<String>,
+    osymbols: Option<String>,
+    config: DrawingConfig,
+}
+
+impl DrawFst {
+    pub fn new(
+        path_in: &str,
+        path_out: &str,
+        isymbols: Option<&String>,
+        osymbols: Option<&String>,
+        config: DrawingConfig,
+    ) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+            isymbols: isymbols.cloned(),
+            osymbols: osymbols.cloned(),
+            config,
+        }
+    }
+
+    pub fn run(&self) -> Result<()> {
+        let (hdr, data) = read_fst_data(&self.path_in)?;
+        dispatch_weight!(hdr.tr_type.as_str(), W => {
+            dispatch_fst_type!(hdr.fst_type.as_str(), &data, W, fst => self.draw(fst))
+        })
+    }
+
+    fn draw<W: SerializableSemiring, F: SerializableFst<W>>(&self, mut fst: F) -> Result<()> {
+        if let Some(isymt) = read_symt(self.isymbols.as_ref())? {
+            fst.set_input_symbols(isymt);
+        }
+        if let Some(osymt) = read_symt(self.osymbols.as_ref())? {
+            fst.set_output_symbols(osymt);
+        }
+        fst.draw(&self.path_out, &self.config)?;
+        info!("FST drawn to {}", self.path_out);
+        Ok(())
+    }
+}
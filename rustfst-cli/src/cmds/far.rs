@@ -0,0 +1,184 @@
+use std::path::Path;
+
+use anyhow::{bail, format_err, Context, Result};
+use log::info;
+
+use rustfst::far::{load_fst_header, FarReader, FarType, FarWriter};
+use rustfst::prelude::*;
+use rustfst::BinaryFstHeader;
+
+use crate::weight::read_fst_data;
+
+fn parse_far_type(far_type: &str) -> Result<FarType> {
+    match far_type {
+        "sttable" => Ok(FarType::STTable),
+        "stlist" => Ok(FarType::STList),
+        _ => bail!("Unexpected far_type : {}", far_type),
+    }
+}
+
+/// Reads an archive and the tr type of its FSTs. Like for `FarCreate`, it is the one of the first
+/// FST as they all share the same. Empty archives are read as `standard` ones.
+fn read_far_data(path: &str) -> Result<(Vec<u8>, String)> {
+    let data = std::fs::read(path).with_context(|| format!("Can't open FAR file : {:?}", path))?;
+    let tr_type = match load_fst_header(&data)? {
+        Some(hdr) if hdr.fst_type != "vector" => {
+            bail!("Unsupported fst type in the archive : {}", hdr.fst_type)
+        }
+        Some(hdr) => hdr.tr_type,
+        None => "standard".to_string(),
+    };
+    Ok((data, tr_type))
+}
+
+/// Key of an FST in an archive : the name of its file without the extension.
+fn key_from_path(path: &str) -> Result<String> {
+    Path::new(path)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| format_err!("Can't compute the FAR key of {:?}", path))
+}
+
+pub struct FarCreate {
+    paths_in: Vec<String>,
+    path_out: String,
+    far_type: String,
+}
+
+impl FarCreate {
+    pub fn new(paths_in: Vec<String>, path_out: &str, far_type: &str) -> Self {
+        Self {
+            paths_in,
+            path_out: path_out.to_string(),
+            far_type: far_type.to_string(),
+        }
+    }
+
+    pub fn run(&self) -> Result<()> {
+        let mut entries = self
+            .paths_in
+            .iter()
+            .map(|p| Ok((key_from_path(p)?, p.as_str())))
+            .collect::<Result<Vec<_>>>()?;
+        // Archives require the keys to be sorted.
+        entries.sort();
+        let far_type = parse_far_type(&self.far_type)?;
+
+        // All the FSTs of an archive share the tr type of the first one.
+        let (_, path) = entries
+            .first()
+            .ok_or_else(|| format_err!("No FST to add to the archive"))?;
+        let first = read_fst_data(path)?;
+        let tr_type = first.0.tr_type.clone();
+        dispatch_weight!(tr_type.as_str(), W => self.create::<W>(far_type, &entries, first))
+    }
+
+    /// Writes the archive. `first` is the content of the file of the first entry, already read.
+    fn create<W: SerializableSemiring>(
+        &self,
+        far_type: FarType,
+        entries: &[(String, &str)],
+        first: (BinaryFstHeader, Vec<u8>),
+    ) -> Result<()> {
+        let tr_type = first.0.tr_type.clone();
+        let mut first = Some(first);
+        let mut writer = FarWriter::create(&self.path_out, far_type)?;
+        for (key, path) in entries {
+            let (hdr, data) = match first.take() {
+                Some(first) => first,
+                None => read_fst_data(path)?,
+            };
+            if hdr.tr_type != tr_type {
+                bail!(
+                    "All the FSTs of an archive must have the same arc type : {} has arc type {} instead of {}",
+                    path,
+                    hdr.tr_type,
+                    tr_type
+                );
+            }
+            let fst = VectorFst::<W>::load(&data)?;
+            writer
+                .add(&key, &fst)
+                .with_context(|| format!("Can't add {:?} to the archive", path))?;
+        }
+        writer.finish()?;
+        info!("FAR written to {}", self.path_out);
+        Ok(())
+    }
+}
+
+pub struct FarExtract {
+    path_in: String,
+    dir_out: String,
+    keys: Option<Vec<String>>,
+}
+
+impl FarExtract {
+    pub fn new(path_in: &str, dir_out: &str, keys: Option<Vec<String>>) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            dir_out: dir_out.to_string(),
+            keys,
+        }
+    }
+
+    pub fn run(&self) -> Result<()> {
+        let (data, tr_type) = read_far_data(&self.path_in)?;
+        dispatch_weight!(tr_type.as_str(), W => self.extract::<W>(data))
+    }
+
+    fn extract<W: SerializableSemiring>(&self, data: Vec<u8>) -> Result<()> {
+        let reader = FarReader::<W, VectorFst<_>>::load(data)?;
+        let write_fst = |key: &str, fst: VectorFst<_>| -> Result<()> {
+            let path_out = Path::new(&self.dir_out).join(format!("{}.fst", key));
+            fst.write(&path_out)?;
+            info!("Extracted {} to {:?}", key, path_out);
+            Ok(())
+        };
+        match &self.keys {
+            Some(keys) => {
+                for key in keys {
+                    let fst = reader
+                        .find(key)?
+                        .ok_or_else(|| format_err!("Key {:?} not found in the archive", key))?;
+                    write_fst(key, fst)?;
+                }
+            }
+            None => {
+                for entry in reader.iter() {
+                    let (key, fst) = entry?;
+                    write_fst(key, fst)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct FarList {
+    path_in: String,
+}
+
+impl FarList {
+    pub fn new(path_in: &str) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+        }
+    }
+
+    pub fn run(&self) -> Result<()> {
+        let (data, tr_type) = read_far_data(&self.path_in)?;
+        dispatch_weight!(tr_type.as_str(), W => self.list::<W>(data))
+    }
+
+    fn list<W: SerializableSemiring>(&self, data: Vec<u8>) -> Result<()> {
+        let reader = FarReader::<W, VectorFst<_>>::load(data)?;
+        println!("# {} ({} entries)", reader.far_type().name(), reader.len());
+        for entry in reader.iter() {
+            let (key, fst) = entry?;
+            println!("{}\t{}", key, fst.num_states());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+use anyhow::Result;
+use serde_json::{Map, Value};
+
+use rustfst::fst_properties::{compute_fst_properties, FstProperties};
+use rustfst::prelude::*;
+
+use crate::weight::read_fst_data;
+
+/// Equivalent of `fstinfo` : prints the header, the stats and the properties of a FST.
+pub struct FstInfo {
+    path_in: String,
+    json: bool,
+}
+
+/// Stats computed by iterating over the whole FST.
+struct FstStats {
+    num_states: usize,
+    num_trs: usize,
+    num_final_states: usize,
+    num_input_epsilons: usize,
+    num_output_epsilons: usize,
+    num_sccs: usize,
+    properties: FstProperties,
+}
+
+impl FstStats {
+    fn compute<W: SerializableSemiring, F: ExpandedFst<W>>(fst: &F) -> Result<Self> {
+        let mut num_trs = 0;
+        let mut num_final_states = 0;
+        let mut num_input_epsilons = 0;
+        let mut num_output_epsilons = 0;
+        for s in fst.states_iter() {
+            num_trs += fst.num_trs(s)?;
+            num_input_epsilons += fst.num_input_epsilons(s)?;
+            num_output_epsilons += fst.num_output_epsilons(s)?;
+            if fst.is_final(s)? {
+                num_final_states += 1;
+            }
+        }
+
+        let (sccs, _): (_, VectorFst<W>) = condense(fst)?;
+        let num_sccs = sccs.iter().max().map_or(0, |m| *m as usize + 1);
+
+        let mut known = FstProperties::empty();
+        let properties = compute_fst_properties(fst, FstProperties::all(), &mut known, false)?;
+
+        Ok(Self {
+            num_states: fst.num_states(),
+            num_trs,
+            num_final_states,
+            num_input_epsilons,
+            num_output_epsilons,
+            num_sccs,
+            properties,
+        })
+    }
+}
+
+fn symt_name(symt: &Option<(String, std::sync::Arc<SymbolTable>)>) -> Value {
+    match symt {
+        Some((name, _)) => Value::String(name.clone()),
+        None => Value::Null,
+    }
+}
+
+impl FstInfo {
+    pub fn new(path_in: &str, json: bool) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            json,
+        }
+    }
+
+    pub fn run(&self) -> Result<()> {
+        let (hdr, data) = read_fst_data(&self.path_in)?;
+        let stats = dispatch_weight!(hdr.tr_type.as_str(), W => {
+            dispatch_fst_type!(hdr.fst_type.as_str(), &data, W, fst => FstStats::compute(&fst))
+        })?;
+
+        let fields = vec![
+            ("fst type", Value::from(hdr.fst_type.clone())),
+            ("arc type", Value::from(hdr.tr_type.clone())),
+            ("input symbol table", symt_name(&hdr.isymt)),
+            ("output symbol table", symt_name(&hdr.osymt)),
+            ("# of states", Value::from(stats.num_states)),
+            ("# of arcs", Value::from(stats.num_trs)),
+            ("initial state", hdr.start.map_or(Value::Null, Value::from)),
+            ("# of final states", Value::from(stats.num_final_states)),
+            ("# of input epsilons", Value::from(stats.num_input_epsilons)),
+            (
+                "# of output epsilons",
+                Value::from(stats.num_output_epsilons),
+            ),
+            ("# of strongly conn components", Value::from(stats.num_sccs)),
+        ];
+        let properties = FstProperties::all()
+            .iter_names()
+            .map(|(name, prop)| (name.to_lowercase(), stats.properties.contains(prop)))
+            .collect::<Vec<_>>();
+
+        if self.json {
+            let mut out = fields
+                .into_iter()
+                .map(|(k, v)| (k.replace("# of ", "num_").replace(' ', "_"), v))
+                .collect::<Map<_, _>>();
+            out.insert(
+                "properties".to_string(),
+                Value::Object(
+                    properties
+                        .into_iter()
+                        .map(|(k, v)| (k, Value::from(v)))
+                        .collect(),
+                ),
+            );
+            println!("{}", serde_json::to_string_pretty(&Value::Object(out))?);
+        } else {
+            for (k, v) in fields {
+                let v = match v {
+                    Value::Null => "none".to_string(),
+                    Value::String(s) => s,
+                    v => v.to_string(),
+                };
+                println!("{:<50}{}", k, v);
+            }
+            for (k, v) in properties {
+                println!("{:<50}{}", k, if v { "y" } else { "n" });
+            }
+        }
+        Ok(())
+    }
+}
@@ -4,11 +4,12 @@ use unsafe_unwrap::UnsafeUnwrap;
 use rustfst::prelude::*;
 
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::weight::{parse_weight, CliWeight};
 
 pub struct MapAlgorithm {
     path_in: String,
     map_type: String,
-    weight: Option<f32>,
+    weight: Option<String>,
     path_out: String,
 }
 
@@ -25,10 +26,7 @@ impl UnaryFstAlgorithm for MapAlgorithm {
         format!("map {}", self.map_type)
     }
 
-    fn run_algorithm(
-        &self,
-        mut fst: VectorFst<TropicalWeight>,
-    ) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm<W: CliWeight>(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
         match self.map_type.as_str() {
             "tr_sum" | "arc_sum" => {
                 tr_sum(&mut fst);
@@ -60,7 +58,8 @@ impl UnaryFstAlgorithm for MapAlgorithm {
             }
             "plus" => {
                 // Safe because there is a check at parsing time.
-                let mapper = tr_mappers::PlusMapper::new(unsafe { self.weight.unsafe_unwrap() });
+                let weight = parse_weight::<W>(unsafe { self.weight.as_ref().unsafe_unwrap() })?;
+                let mapper = tr_mappers::PlusMapper::new(weight.take_value());
                 tr_map(&mut fst, &mapper)?;
                 Ok(fst)
             }
@@ -77,7 +76,8 @@ impl UnaryFstAlgorithm for MapAlgorithm {
             }
             "times" => {
                 // Safe because there is a check at parsing time.
-                let mapper = tr_mappers::TimesMapper::new(unsafe { self.weight.unsafe_unwrap() });
+                let weight = parse_weight::<W>(unsafe { self.weight.as_ref().unsafe_unwrap() })?;
+                let mapper = tr_mappers::TimesMapper::new(weight.take_value());
                 tr_map(&mut fst, &mapper)?;
                 Ok(fst)
             }
@@ -94,7 +94,7 @@ impl MapAlgorithm {
         Self {
             path_in: path_in.to_string(),
             map_type: map_type.to_string(),
-            weight: weight.map(|f| f.parse().unwrap()),
+            weight: weight.map(|w| w.to_string()),
             path_out: path_out.to_string(),
         }
     }
@@ -1,6 +1,7 @@
 use rustfst::prelude::*;
 
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::weight::CliWeight;
 use anyhow::Result;
 
 pub struct MinimizeAlgorithm {
@@ -22,10 +23,7 @@ impl UnaryFstAlgorithm for MinimizeAlgorithm {
         "minimize".to_string()
     }
 
-    fn run_algorithm(
-        &self,
-        mut fst: VectorFst<TropicalWeight>,
-    ) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm<W: CliWeight>(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
         let config = MinimizeConfig::default().with_allow_nondet(self.allow_nondet);
         minimize_with_config(&mut fst, config)?;
         Ok(fst)
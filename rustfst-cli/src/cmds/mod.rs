@@ -1,14 +1,20 @@
+pub mod compile;
 pub mod compose;
 pub mod connect;
 pub mod determinize;
+pub mod draw;
+pub mod far;
+pub mod info;
 pub mod invert;
 pub mod map;
 pub mod minimize;
 pub mod optimize;
+pub mod print;
 pub mod project;
 pub mod push;
 pub mod reverse;
 pub mod rm_final_epsilon;
 pub mod shortest_path;
+pub mod symt;
 pub mod topsort;
 pub mod tr_sort;
@@ -0,0 +1,69 @@
+use anyhow::{Context, Result};
+
+use rustfst::prelude::*;
+
+use crate::cmds::symt::{label_to_symbol, read_symt};
+use crate::weight::read_fst_data;
+
+/// Equivalent of `fstprint` : prints a binary FST in AT&T text format.
+pub struct PrintFst {
+    path_in: String,
+    path_out: Option<String>,
+    isymbols: Option<String>,
+    osymbols: Option<String>,
+    acceptor: bool,
+}
+
+impl PrintFst {
+    pub fn new(
+        path_in: &str,
+        path_out: Option<&String>,
+        isymbols: Option<&String>,
+        osymbols: Option<&String>,
+        acceptor: bool,
+    ) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.cloned(),
+            isymbols: isymbols.cloned(),
+            osymbols: osymbols.cloned(),
+            acceptor,
+        }
+    }
+
+    pub fn run(&self) -> Result<()> {
+        let (hdr, data) = read_fst_data(&self.path_in)?;
+        dispatch_weight!(hdr.tr_type.as_str(), W => {
+            dispatch_fst_type!(hdr.fst_type.as_str(), &data, W, fst => self.print(&fst))
+        })
+    }
+
+    fn print<W: SerializableSemiring, F: SerializableFst<W>>(&self, fst: &F) -> Result<()> {
+        // The symbol tables given on the command line take precedence over the ones of the FST.
+        let isymt = read_symt(self.isymbols.as_ref())?.or_else(|| fst.input_symbols().cloned());
+        let osymt = read_symt(self.osymbols.as_ref())?.or_else(|| fst.output_symbols().cloned());
+
+        let mut out = String::new();
+        for line in fst.text()?.lines() {
+            let mut fields = line.split('\t').map(|s| s.to_string()).collect::<Vec<_>>();
+            // Rows of trs are `state nextstate ilabel olabel weight`.
+            if fields.len() >= 4 {
+                fields[2] = label_to_symbol(&fields[2], isymt.as_ref())?;
+                if self.acceptor {
+                    fields.remove(3);
+                } else {
+                    fields[3] = label_to_symbol(&fields[3], osymt.as_ref())?;
+                }
+            }
+            out.push_str(&fields.join("\t"));
+            out.push('\n');
+        }
+
+        match &self.path_out {
+            Some(path_out) => std::fs::write(path_out, out)
+                .with_context(|| format!("Can't write text FST {:?}", path_out))?,
+            None => print!("{}", out),
+        }
+        Ok(())
+    }
+}
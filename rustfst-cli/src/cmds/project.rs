@@ -3,6 +3,7 @@ use anyhow::Result;
 use rustfst::prelude::*;
 
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::weight::CliWeight;
 
 #[derive(Debug)]
 pub struct ProjectFstAlgorithm {
@@ -24,10 +25,7 @@ impl UnaryFstAlgorithm for ProjectFstAlgorithm {
         "project".into()
     }
 
-    fn run_algorithm(
-        &self,
-        mut fst: VectorFst<TropicalWeight>,
-    ) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm<W: CliWeight>(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
         dbg!(&self);
         project(&mut fst, self.project_type);
         Ok(fst)
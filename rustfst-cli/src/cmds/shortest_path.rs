@@ -1,6 +1,7 @@
 use rustfst::prelude::*;
 
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::weight::CliWeight;
 use anyhow::Result;
 
 pub struct ShortestPathAlgorithm {
@@ -23,7 +24,7 @@ impl UnaryFstAlgorithm for ShortestPathAlgorithm {
         "shortest path".to_string()
     }
 
-    fn run_algorithm(&self, fst: VectorFst<TropicalWeight>) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm<W: CliWeight>(&self, fst: VectorFst<W>) -> Result<VectorFst<W>> {
         let config = ShortestPathConfig::default()
             .with_nshortest(self.nshortest)
             .with_unique(self.unique);
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use anyhow::{format_err, Context, Result};
+
+use rustfst::prelude::*;
+
+/// Loads a symbol table in text format if a path is given.
+pub fn read_symt(path: Option<&String>) -> Result<Option<Arc<SymbolTable>>> {
+    path.map(|p| {
+        SymbolTable::read_text(p)
+            .map(Arc::new)
+            .with_context(|| format!("Can't read symbol table {:?}", p))
+    })
+    .transpose()
+}
+
+/// Turns a label of a text FST into its integer form, using the symbol table if any.
+pub fn symbol_to_label(sym: &str, symt: Option<&Arc<SymbolTable>>) -> Result<Label> {
+    match symt {
+        Some(symt) => symt
+            .get_label(sym)
+            .ok_or_else(|| format_err!("Symbol {:?} is not in the symbol table", sym)),
+        None => sym
+            .parse()
+            .map_err(|_| format_err!("Invalid label {:?}, a symbol table is needed", sym)),
+    }
+}
+
+/// Turns an integer label of a text FST into its symbol if a symbol table is given.
+pub fn label_to_symbol(label: &str, symt: Option<&Arc<SymbolTable>>) -> Result<String> {
+    match symt {
+        Some(symt) => {
+            let label: Label = label.parse()?;
+            symt.get_symbol(label)
+                .map(|s| s.to_string())
+                .ok_or_else(|| format_err!("Label {} is not in the symbol table", label))
+        }
+        None => Ok(label.to_string()),
+    }
+}
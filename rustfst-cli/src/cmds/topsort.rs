@@ -3,6 +3,7 @@ use rustfst::prelude::*;
 use anyhow::Result;
 
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::weight::CliWeight;
 
 pub struct TopsortAlgorithm {
     path_in: String,
@@ -22,10 +23,7 @@ impl UnaryFstAlgorithm for TopsortAlgorithm {
         "topsort".to_string()
     }
 
-    fn run_algorithm(
-        &self,
-        mut fst: VectorFst<TropicalWeight>,
-    ) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm<W: CliWeight>(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
         top_sort(&mut fst)?;
         Ok(fst)
     }
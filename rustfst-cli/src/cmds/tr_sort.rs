@@ -3,6 +3,7 @@ use anyhow::{bail, Result};
 use rustfst::prelude::*;
 
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::weight::CliWeight;
 
 pub struct TrsortAlgorithm {
     path_in: String,
@@ -23,10 +24,7 @@ impl UnaryFstAlgorithm for TrsortAlgorithm {
         "tr_sort".to_string()
     }
 
-    fn run_algorithm(
-        &self,
-        mut fst: VectorFst<TropicalWeight>,
-    ) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm<W: CliWeight>(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
         match self.sort_type.as_str() {
             "ilabel" => tr_sort(&mut fst, ILabelCompare {}),
             "olabel" => tr_sort(&mut fst, OLabelCompare {}),
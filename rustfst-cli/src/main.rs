@@ -2,17 +2,23 @@ use std::process;
 
 use anyhow::{format_err, Result};
 use clap::parser::ValueSource;
-use clap::{Arg, ArgAction, Command};
+use clap::{value_parser, Arg, ArgAction, Command};
 use log::error;
+use rustfst::DrawingConfig;
 
 use crate::binary_fst_algorithm::BinaryFstAlgorithm;
+use crate::cmds::compile::CompileFst;
 use crate::cmds::compose::ComposeAlgorithm;
 use crate::cmds::connect::ConnectAlgorithm;
 use crate::cmds::determinize::DeterminizeAlgorithm;
+use crate::cmds::draw::DrawFst;
+use crate::cmds::far::{FarCreate, FarExtract, FarList};
+use crate::cmds::info::FstInfo;
 use crate::cmds::invert::InvertAlgorithm;
 use crate::cmds::map::MapAlgorithm;
 use crate::cmds::minimize::MinimizeAlgorithm;
 use crate::cmds::optimize::OptimizeAlgorithm;
+use crate::cmds::print::PrintFst;
 use crate::cmds::project::ProjectFstAlgorithm;
 use crate::cmds::push::PushAlgorithm;
 use crate::cmds::reverse::ReverseAlgorithm;
@@ -22,6 +28,9 @@ use crate::cmds::topsort::TopsortAlgorithm;
 use crate::cmds::tr_sort::TrsortAlgorithm;
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
 
+#[macro_use]
+pub mod weight;
+
 pub mod binary_fst_algorithm;
 pub mod cmds;
 pub mod unary_fst_algorithm;
@@ -192,6 +201,212 @@ fn main() {
     );
     app = app.subcommand(two_in_one_out_options(compose_cmd));
 
+    // FST archives
+    let far_cmd = Command::new("far")
+        .about("Create, extract and list FST archives.")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("create")
+                .about("Creates an archive from FST files, keyed by their file name.")
+                .arg(
+                    Arg::new("far_type")
+                        .long("far_type")
+                        .value_parser(["sttable", "stlist"])
+                        .default_value("sttable")
+                        .action(ArgAction::Set),
+                )
+                .arg(
+                    Arg::new("in.fst")
+                        .help("Paths to input fst files.")
+                        .required(true)
+                        .num_args(1..)
+                        .action(ArgAction::Append),
+                )
+                .arg(
+                    Arg::new("out.far")
+                        .help("Path to output far file.")
+                        .required(true)
+                        .action(ArgAction::Set),
+                ),
+        )
+        .subcommand(
+            Command::new("extract")
+                .about("Extracts the FSTs of an archive to <key>.fst files.")
+                .arg(
+                    Arg::new("in.far")
+                        .help("Path to input far file.")
+                        .required(true)
+                        .action(ArgAction::Set),
+                )
+                .arg(
+                    Arg::new("out_dir")
+                        .help("Directory where the fst files are written.")
+                        .default_value(".")
+                        .action(ArgAction::Set),
+                )
+                .arg(
+                    Arg::new("keys")
+                        .long("keys")
+                        .help("Comma-separated list of the keys to extract. All by default.")
+                        .value_delimiter(',')
+                        .action(ArgAction::Append),
+                ),
+        )
+        .subcommand(
+            Command::new("list")
+                .about("Lists the keys of an archive.")
+                .arg(
+                    Arg::new("in.far")
+                        .help("Path to input far file.")
+                        .required(true)
+                        .action(ArgAction::Set),
+                ),
+        );
+    app = app.subcommand(far_cmd);
+
+    // Info
+    let info_cmd = Command::new("info")
+        .about("Prints out information about an FST.")
+        .arg(
+            Arg::new("in.fst")
+                .help("Path to input fst file.")
+                .required(true)
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .help("Print the information as JSON.")
+                .action(ArgAction::SetTrue),
+        );
+    app = app.subcommand(info_cmd);
+
+    // Compile
+    let compile_cmd = Command::new("compile")
+        .about("Creates a binary FST from a text FST in AT&T format.")
+        .arg(
+            Arg::new("in.txt")
+                .help("Path to input text fst file.")
+                .required(true)
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("out.fst")
+                .help("Path to output fst file.")
+                .required(true)
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("keep_isymbols")
+                .long("keep_isymbols")
+                .help("Store the input symbol table in the FST.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("keep_osymbols")
+                .long("keep_osymbols")
+                .help("Store the output symbol table in the FST.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(arc_type_arg());
+    app = app.subcommand(symbols_options(compile_cmd));
+
+    // Print
+    let print_cmd = Command::new("print")
+        .about("Prints out a binary FST in AT&T text format.")
+        .arg(
+            Arg::new("in.fst")
+                .help("Path to input fst file.")
+                .required(true)
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("out.txt")
+                .help("Path to output text fst file. Defaults to stdout.")
+                .action(ArgAction::Set),
+        );
+    app = app.subcommand(symbols_options(print_cmd));
+
+    // Draw
+    let draw_cmd = Command::new("draw")
+        .about("Draws a binary FST in the DOT format.")
+        .arg(
+            Arg::new("in.fst")
+                .help("Path to input fst file.")
+                .required(true)
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("out.dot")
+                .help("Path to output dot file.")
+                .required(true)
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("title")
+                .long("title")
+                .help("Set figure title.")
+                .default_value("")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("portrait")
+                .long("portrait")
+                .help("Portrait mode (def: landscape).")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("vertical")
+                .long("vertical")
+                .help("Draw bottom-to-top instead of left-to-right.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("width")
+                .long("width")
+                .help("Set width.")
+                .requires("height")
+                .value_parser(value_parser!(f32))
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("height")
+                .long("height")
+                .help("Set height.")
+                .requires("width")
+                .value_parser(value_parser!(f32))
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("ranksep")
+                .long("ranksep")
+                .help("Set minimum separation between ranks (see dot documentation).")
+                .value_parser(value_parser!(f32))
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("nodesep")
+                .long("nodesep")
+                .help("Set minimum separation between nodes (see dot documentation).")
+                .value_parser(value_parser!(f32))
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("fontsize")
+                .long("fontsize")
+                .help("Set fontsize.")
+                .default_value("14")
+                .value_parser(value_parser!(u32))
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("show_weight_one")
+                .long("show_weight_one")
+                .help("Draw weights equal to Weight::ONE.")
+                .action(ArgAction::SetTrue),
+        );
+    app = app.subcommand(symbols_options(draw_cmd));
+
     let matches = app.get_matches();
 
     let env = env_logger::Env::default().filter_or(env_logger::DEFAULT_FILTER_ENV, "debug");
@@ -294,11 +509,102 @@ fn handle(matches: clap::ArgMatches) -> Result<()> {
             m.get_one::<String>("compose_type").unwrap(),
         )
         .run_cli_or_bench(m),
+        Some(("far", m)) => match m.subcommand() {
+            Some(("create", m)) => FarCreate::new(
+                m.get_many::<String>("in.fst").unwrap().cloned().collect(),
+                m.get_one::<String>("out.far").unwrap(),
+                m.get_one::<String>("far_type").unwrap(),
+            )
+            .run(),
+            Some(("extract", m)) => FarExtract::new(
+                m.get_one::<String>("in.far").unwrap(),
+                m.get_one::<String>("out_dir").unwrap(),
+                m.get_many::<String>("keys").map(|k| k.cloned().collect()),
+            )
+            .run(),
+            Some(("list", m)) => FarList::new(m.get_one::<String>("in.far").unwrap()).run(),
+            Some((s, _)) => Err(format_err!("Unknown far subcommand {}.", s)),
+            None => Err(format_err!("Unknown None")),
+        },
+        Some(("info", m)) => {
+            FstInfo::new(m.get_one::<String>("in.fst").unwrap(), m.get_flag("json")).run()
+        }
+        Some(("compile", m)) => CompileFst::new(
+            m.get_one::<String>("in.txt").unwrap(),
+            m.get_one::<String>("out.fst").unwrap(),
+            m.get_one::<String>("isymbols"),
+            m.get_one::<String>("osymbols"),
+            m.get_flag("acceptor"),
+            m.get_flag("keep_isymbols"),
+            m.get_flag("keep_osymbols"),
+            m.get_one::<String>("arc_type").unwrap(),
+        )
+        .run(),
+        Some(("print", m)) => PrintFst::new(
+            m.get_one::<String>("in.fst").unwrap(),
+            m.get_one::<String>("out.txt"),
+            m.get_one::<String>("isymbols"),
+            m.get_one::<String>("osymbols"),
+            m.get_flag("acceptor"),
+        )
+        .run(),
+        Some(("draw", m)) => DrawFst::new(
+            m.get_one::<String>("in.fst").unwrap(),
+            m.get_one::<String>("out.dot").unwrap(),
+            m.get_one::<String>("isymbols"),
+            m.get_one::<String>("osymbols"),
+            DrawingConfig {
+                vertical: m.get_flag("vertical"),
+                size: m
+                    .get_one::<f32>("width")
+                    .zip(m.get_one::<f32>("height"))
+                    .map(|(w, h)| (*w, *h)),
+                title: m.get_one::<String>("title").unwrap().clone(),
+                portrait: m.get_flag("portrait"),
+                ranksep: m.get_one::<f32>("ranksep").copied(),
+                nodesep: m.get_one::<f32>("nodesep").copied(),
+                fontsize: *m.get_one::<u32>("fontsize").unwrap(),
+                acceptor: m.get_flag("acceptor"),
+                show_weight_one: m.get_flag("show_weight_one"),
+                print_weight: true,
+            },
+        )
+        .run(),
         Some((s, _)) => Err(format_err!("Unknown subcommand {}.", s)),
         None => Err(format_err!("Unknown None")),
     }
 }
 
+fn arc_type_arg() -> Arg {
+    Arg::new("arc_type")
+        .long("arc_type")
+        .help("Type of the trs (standard, log, tropical64, log64, boolean or a product like tropical_X_log).")
+        .default_value("standard")
+        .action(ArgAction::Set)
+}
+
+fn symbols_options(command: Command) -> Command {
+    command
+        .arg(
+            Arg::new("isymbols")
+                .long("isymbols")
+                .help("Path to the input symbol table in text format.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("osymbols")
+                .long("osymbols")
+                .help("Path to the output symbol table in text format.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("acceptor")
+                .long("acceptor")
+                .help("Text FST in acceptor format.")
+                .action(ArgAction::SetTrue),
+        )
+}
+
 fn one_in_one_out_options(command: Command) -> Command {
     command
         .version("1.0")
@@ -9,6 +9,8 @@ use log::{debug, info};
 
 use rustfst::prelude::*;
 
+use crate::weight::{read_fst_data, CliWeight};
+
 fn duration_to_seconds(duration: &Duration) -> f64 {
     duration.as_secs() as f64 + duration.subsec_nanos() as f64 * 1.0e-9
 }
@@ -27,36 +29,41 @@ pub trait UnaryFstAlgorithm {
     fn get_path_out(&self) -> &str;
     fn get_algorithm_name(&self) -> String;
 
-    fn read(&self) -> Result<VectorFst<TropicalWeight>> {
-        VectorFst::<TropicalWeight>::read(self.get_path_in())
+    fn read<W: CliWeight>(&self) -> Result<VectorFst<W>> {
+        VectorFst::<W>::read(self.get_path_in())
     }
 
-    fn run_algorithm(&self, fst: VectorFst<TropicalWeight>) -> Result<VectorFst<TropicalWeight>>;
+    fn run_algorithm<W: CliWeight>(&self, fst: VectorFst<W>) -> Result<VectorFst<W>>;
 
-    fn write(&self, fst: &VectorFst<TropicalWeight>) -> Result<()> {
+    fn write<W: CliWeight>(&self, fst: &VectorFst<W>) -> Result<()> {
         fst.write(self.get_path_out())
     }
 
     fn run_cli_or_bench(&self, m: &ArgMatches) -> Result<()> {
-        if m.contains_id("bench") {
-            // Run bench
-            self.run_bench(
-                m.get_one::<String>("n_warm_ups").unwrap().parse().unwrap(),
-                m.get_one::<String>("n_iters").unwrap().parse().unwrap(),
-                m.get_one::<String>("export-markdown").map(|s| s.as_str()),
-            )
-        } else {
-            // Run cli
-            self.run_cli()
-        }
+        // The weight type is the one stored in the header of the input FST.
+        let (hdr, data) = read_fst_data(self.get_path_in())?;
+        dispatch_weight!(hdr.tr_type.as_str(), W => {
+            if m.contains_id("bench") {
+                // Run bench
+                self.run_bench::<W>(
+                    m.get_one::<String>("n_warm_ups").unwrap().parse().unwrap(),
+                    m.get_one::<String>("n_iters").unwrap().parse().unwrap(),
+                    m.get_one::<String>("export-markdown").map(|s| s.as_str()),
+                )
+            } else {
+                // Run cli
+                self.run_cli::<W>(&data)
+            }
+        })
     }
 
-    fn run_cli(&self) -> Result<()> {
+    /// Runs the algorithm on the FST parsed from `data`, the content of the input file.
+    fn run_cli<W: CliWeight>(&self, data: &[u8]) -> Result<()> {
         info!("Running {} algorithm", self.get_algorithm_name().blue());
         // Parsing
         debug!("Parsing...");
         let parsing_start = Instant::now();
-        let mut fst = self.read()?;
+        let mut fst = VectorFst::<W>::load(data)?;
         let duration_parsing = parsing_start.elapsed();
         debug!("Duration parsing : {:?}", &duration_parsing);
 
@@ -77,7 +84,7 @@ pub trait UnaryFstAlgorithm {
         Ok(())
     }
 
-    fn run_bench(
+    fn run_bench<W: CliWeight>(
         &self,
         n_warm_ups: usize,
         n_iters: usize,
@@ -99,7 +106,7 @@ pub trait UnaryFstAlgorithm {
         for i in 0..(n_warm_ups + n_iters) {
             // Parsing
             let parsing_start = Instant::now();
-            let mut fst = self.read()?;
+            let mut fst = self.read::<W>()?;
             let duration_parsing = parsing_start.elapsed();
 
             // Algorithm
@@ -0,0 +1,124 @@
+use anyhow::{format_err, Context, Result};
+
+use rustfst::prelude::*;
+use rustfst::BinaryFstHeader;
+
+/// Bounds shared by all the weights the CLI can operate on. Those are the ones required
+/// by the most demanding algorithms (determinize, minimize, push and shortest_path).
+pub trait CliWeight:
+    SerializableSemiring<ReverseWeight = Self> + WeaklyDivisibleSemiring + WeightQuantize + 'static
+{
+}
+
+impl<W> CliWeight for W where
+    W: SerializableSemiring<ReverseWeight = W> + WeaklyDivisibleSemiring + WeightQuantize + 'static
+{
+}
+
+/// Reads a binary FST file and parses its header. The returned bytes can then be given to
+/// `dispatch_fst_type!` to parse the FST itself without reading the file again.
+pub fn read_fst_data(path: &str) -> Result<(BinaryFstHeader, Vec<u8>)> {
+    let data =
+        std::fs::read(path).with_context(|| format!("Can't open Fst binary file : {:?}", path))?;
+    let hdr = BinaryFstHeader::load(&data)?;
+    Ok((hdr, data))
+}
+
+/// Parses a weight given on the command line.
+pub fn parse_weight<W: SerializableSemiring>(weight: &str) -> Result<W> {
+    match W::parse_text(weight) {
+        Ok(("", w)) => Ok(w),
+        _ => Err(format_err!(
+            "Can't parse {:?} as a {} weight",
+            weight,
+            W::weight_type()
+        )),
+    }
+}
+
+/// Runs `$body` with `$w` aliased to the weight matching the tr type of a binary FST.
+macro_rules! dispatch_weight {
+    ($tr_type:expr, $w:ident => $body:expr) => {{
+        use rustfst::semirings::{
+            BooleanWeight, LogWeight, LogWeight64, ProductWeight, TropicalWeight, TropicalWeight64,
+        };
+        match $tr_type {
+            "standard" => {
+                type $w = TropicalWeight;
+                $body
+            }
+            "log" => {
+                type $w = LogWeight;
+                $body
+            }
+            "tropical64" => {
+                type $w = TropicalWeight64;
+                $body
+            }
+            "log64" => {
+                type $w = LogWeight64;
+                $body
+            }
+            "boolean" => {
+                type $w = BooleanWeight;
+                $body
+            }
+            "tropical_X_tropical" => {
+                type $w = ProductWeight<TropicalWeight, TropicalWeight>;
+                $body
+            }
+            "tropical_X_log" => {
+                type $w = ProductWeight<TropicalWeight, LogWeight>;
+                $body
+            }
+            "log_X_tropical" => {
+                type $w = ProductWeight<LogWeight, TropicalWeight>;
+                $body
+            }
+            "log_X_log" => {
+                type $w = ProductWeight<LogWeight, LogWeight>;
+                $body
+            }
+            tr_type => Err(anyhow::format_err!("Unsupported arc type : {}", tr_type)),
+        }
+    }};
+}
+
+/// Runs `$body` with `$fst` bound to the FST parsed from `$data`, using the implementation
+/// matching the fst type of its header.
+macro_rules! dispatch_fst_type {
+    ($fst_type:expr, $data:expr, $w:ty, $fst:ident => $body:expr) => {{
+        use rustfst::prelude::*;
+        match $fst_type {
+            "vector" => {
+                let $fst = VectorFst::<$w>::load($data)?;
+                $body
+            }
+            "const" => {
+                let $fst = ConstFst::<$w>::load($data)?;
+                $body
+            }
+            "compact_acceptor" => {
+                let $fst = CompactAcceptorFst::<$w>::load($data)?;
+                $body
+            }
+            "compact_string" => {
+                let $fst = CompactStringFst::<$w>::load($data)?;
+                $body
+            }
+            "compact_weighted_string" => {
+                let $fst = CompactWeightedStringFst::<$w>::load($data)?;
+                $body
+            }
+            "compact_unweighted_acceptor" => {
+                let $fst = CompactUnweightedAcceptorFst::<$w>::load($data)?;
+                $body
+            }
+            "compact_unweighted" => {
+                let $fst = CompactUnweightedFst::<$w>::load($data)?;
+                $body
+            }
+            fst_type => Err(anyhow::format_err!("Unsupported fst type : {}", fst_type)),
+        }
+    }};
+}
@@ -0,0 +1,33 @@
+use anyhow::{anyhow, bail};
+
+use crate::fst::CFst;
+use crate::weight::dispatch_weight;
+use crate::{get_mut, wrap, RUSTFST_FFI_RESULT};
+
+use rustfst::algorithms::closure::{closure, ClosureType};
+use rustfst::fst_impls::VectorFst;
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_closure(
+    ptr: *mut CFst,
+    closure_type: libc::size_t,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get_mut!(CFst, ptr);
+        let closure_type = match closure_type {
+            0 => ClosureType::ClosureStar,
+            1 => ClosureType::ClosurePlus,
+            _ => bail!("Unexpected closure type : {}", closure_type),
+        };
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst: &mut VectorFst<W> = fst
+                .downcast_mut()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+            closure(vec_fst, closure_type);
+        });
+        Ok(())
+    })
+}
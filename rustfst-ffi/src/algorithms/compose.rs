@@ -2,6 +2,7 @@ use anyhow::{anyhow, Result};
 
 use super::EnumConversionError;
 use crate::fst::CFst;
+use crate::weight::dispatch_weight;
 use crate::{get, wrap, CLabel, RUSTFST_FFI_RESULT};
 
 use ffi_convert::*;
@@ -11,7 +12,6 @@ use rustfst::algorithms::compose::{
     SigmaMatcherConfig,
 };
 use rustfst::fst_impls::VectorFst;
-use rustfst::semirings::TropicalWeight;
 use rustfst::Label;
 
 #[derive(RawPointerConverter, Debug)]
@@ -127,10 +127,14 @@ impl AsRust<MatcherConfig> for CMatcherConfig {
         if let Some(v) = &self.sigma_matcher_config {
             Ok(MatcherConfig {
                 sigma_matcher_config: Some(v.as_rust()?),
+                rho_matcher_config: None,
+                phi_matcher_config: None,
             })
         } else {
             Ok(MatcherConfig {
                 sigma_matcher_config: None,
+                rho_matcher_config: None,
+                phi_matcher_config: None,
             })
         }
     }
@@ -312,23 +316,25 @@ pub unsafe extern "C" fn fst_compose(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst_1 = get!(CFst, fst_1);
-        let vec_fst1: &VectorFst<TropicalWeight> = fst_1
-            .downcast_ref()
-            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
         let fst_2 = get!(CFst, fst_2);
-        let vec_fst2: &VectorFst<TropicalWeight> = fst_2
-            .downcast_ref()
-            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
-        let fst: VectorFst<TropicalWeight> = compose::<
-            TropicalWeight,
-            VectorFst<TropicalWeight>,
-            VectorFst<TropicalWeight>,
-            _,
-            _,
-            _,
-        >(vec_fst1, vec_fst2)?;
-        let fst_ptr = CFst(Box::new(fst)).into_raw_pointer();
-        unsafe { *composition_ptr = fst_ptr };
+        dispatch_weight!(fst_1.fst_weight_type(), W => {
+            let vec_fst1: &VectorFst<W> = fst_1
+                .downcast_ref()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+            let vec_fst2: &VectorFst<W> = fst_2
+                .downcast_ref()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+            let fst: VectorFst<W> = compose::<
+                W,
+                VectorFst<W>,
+                VectorFst<W>,
+                _,
+                _,
+                _,
+            >(vec_fst1, vec_fst2)?;
+            let fst_ptr = CFst(Box::new(fst)).into_raw_pointer();
+            unsafe { *composition_ptr = fst_ptr };
+        });
         Ok(())
     })
 }
@@ -345,28 +351,30 @@ pub unsafe extern "C" fn fst_compose_with_config(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst_1 = get!(CFst, fst_1);
-        let vec_fst1: &VectorFst<TropicalWeight> = fst_1
-            .downcast_ref()
-            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
         let fst_2 = get!(CFst, fst_2);
-        let vec_fst2: &VectorFst<TropicalWeight> = fst_2
-            .downcast_ref()
-            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
-
         let compose_config = unsafe {
             <CComposeConfig as ffi_convert::RawBorrow<CComposeConfig>>::raw_borrow(config)?
         };
-        let fst: VectorFst<TropicalWeight> =
-            compose_with_config::<
-                TropicalWeight,
-                VectorFst<TropicalWeight>,
-                VectorFst<TropicalWeight>,
-                _,
-                _,
-                _,
-            >(vec_fst1, vec_fst2, compose_config.as_rust()?)?;
-        let fst_ptr = CFst(Box::new(fst)).into_raw_pointer();
-        unsafe { *composition_ptr = fst_ptr };
+        dispatch_weight!(fst_1.fst_weight_type(), W => {
+            let vec_fst1: &VectorFst<W> = fst_1
+                .downcast_ref()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+            let vec_fst2: &VectorFst<W> = fst_2
+                .downcast_ref()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+
+            let fst: VectorFst<W> =
+                compose_with_config::<
+                    W,
+                    VectorFst<W>,
+                    VectorFst<W>,
+                    _,
+                    _,
+                    _,
+                >(vec_fst1, vec_fst2, compose_config.as_rust()?)?;
+            let fst_ptr = CFst(Box::new(fst)).into_raw_pointer();
+            unsafe { *composition_ptr = fst_ptr };
+        });
         Ok(())
     })
 }
@@ -2,6 +2,7 @@ use anyhow::{anyhow, Result};
 
 use super::EnumConversionError;
 use crate::fst::CFst;
+use crate::weight::dispatch_weight;
 use crate::{get, wrap, RUSTFST_FFI_RESULT};
 
 use ffi_convert::*;
@@ -9,7 +10,6 @@ use rustfst::algorithms::determinize::{
     determinize, determinize_with_config, DeterminizeConfig, DeterminizeType,
 };
 use rustfst::fst_impls::VectorFst;
-use rustfst::semirings::TropicalWeight;
 
 #[derive(RawPointerConverter)]
 pub struct CDeterminizeType(usize);
@@ -78,12 +78,14 @@ pub unsafe extern "C" fn fst_determinize(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get!(CFst, ptr);
-        let vec_fst: &VectorFst<TropicalWeight> = fst
-            .downcast_ref()
-            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
-        let fst: VectorFst<TropicalWeight> = determinize(vec_fst)?;
-        let fst_ptr = CFst(Box::new(fst)).into_raw_pointer();
-        unsafe { *det_fst = fst_ptr };
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst: &VectorFst<W> = fst
+                .downcast_ref()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+            let fst: VectorFst<W> = determinize(vec_fst)?;
+            let fst_ptr = CFst(Box::new(fst)).into_raw_pointer();
+            unsafe { *det_fst = fst_ptr };
+        });
         Ok(())
     })
 }
@@ -99,17 +101,19 @@ pub unsafe extern "C" fn fst_determinize_with_config(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get!(CFst, ptr);
-        let vec_fst: &VectorFst<TropicalWeight> = fst
-            .downcast_ref()
-            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
-
         let det_config = unsafe {
             <CDeterminizeConfig as ffi_convert::RawBorrow<CDeterminizeConfig>>::raw_borrow(config)?
         };
-        let fst: VectorFst<TropicalWeight> =
-            determinize_with_config(vec_fst, det_config.as_rust()?)?;
-        let fst_ptr = CFst(Box::new(fst)).into_raw_pointer();
-        unsafe { *det_fst = fst_ptr };
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst: &VectorFst<W> = fst
+                .downcast_ref()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+
+            let fst: VectorFst<W> =
+                determinize_with_config(vec_fst, det_config.as_rust()?)?;
+            let fst_ptr = CFst(Box::new(fst)).into_raw_pointer();
+            unsafe { *det_fst = fst_ptr };
+        });
         Ok(())
     })
 }
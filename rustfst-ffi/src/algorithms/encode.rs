@@ -0,0 +1,78 @@
+use std::any::Any;
+
+use anyhow::anyhow;
+
+use crate::fst::CFst;
+use crate::weight::dispatch_weight;
+use crate::{get, get_mut, wrap, RUSTFST_FFI_RESULT};
+
+use ffi_convert::RawPointerConverter;
+use rustfst::algorithms::encode::{decode, encode, EncodeTable, EncodeType};
+use rustfst::fst_impls::VectorFst;
+
+/// Opaque `EncodeTable` produced by `fst_encode`, typed after the weight of the encoded FST.
+#[derive(RawPointerConverter)]
+pub struct CEncodeTable(pub(crate) Box<dyn Any>);
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_encode(
+    ptr: *mut CFst,
+    encode_labels: bool,
+    encode_weights: bool,
+    table: *mut *const CEncodeTable,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get_mut!(CFst, ptr);
+        let encode_type = EncodeType::from_bools(encode_weights, encode_labels)?;
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst: &mut VectorFst<W> = fst
+                .downcast_mut()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+            let encode_table = encode(vec_fst, encode_type)?;
+            unsafe { *table = CEncodeTable(Box::new(encode_table)).into_raw_pointer() };
+        });
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_decode(
+    ptr: *mut CFst,
+    table: *const CEncodeTable,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get_mut!(CFst, ptr);
+        let table = get!(CEncodeTable, table);
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let encode_table: &EncodeTable<W> = table
+                .downcast_ref()
+                .ok_or_else(|| anyhow!("Encode table does not match the weight type of the FST"))?;
+            let vec_fst: &mut VectorFst<W> = fst
+                .downcast_mut()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+            decode(vec_fst, encode_table.clone())?;
+        });
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn encode_table_destroy(ptr: *mut CEncodeTable) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        if ptr.is_null() {
+            return Ok(());
+        }
+
+        drop(unsafe { Box::from_raw(ptr) });
+        Ok(())
+    })
+}
@@ -0,0 +1,25 @@
+use anyhow::anyhow;
+
+use crate::fst::CFst;
+use crate::weight::dispatch_weight;
+use crate::{get_mut, wrap, RUSTFST_FFI_RESULT};
+
+use rustfst::algorithms::invert;
+use rustfst::fst_impls::VectorFst;
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_invert(ptr: *mut CFst) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get_mut!(CFst, ptr);
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst: &mut VectorFst<W> = fst
+                .downcast_mut()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+            invert(vec_fst);
+        });
+        Ok(())
+    })
+}
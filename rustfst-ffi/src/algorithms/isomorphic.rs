@@ -1,11 +1,11 @@
 use anyhow::anyhow;
 
 use crate::fst::CFst;
+use crate::weight::dispatch_weight;
 use crate::{get, wrap, RUSTFST_FFI_RESULT};
 
 use rustfst::algorithms::isomorphic;
 use rustfst::fst_impls::VectorFst;
-use rustfst::semirings::TropicalWeight;
 
 /// # Safety
 ///
@@ -19,14 +19,16 @@ pub unsafe fn fst_isomorphic(
     wrap(|| {
         let fst = get!(CFst, fst);
         let other_fst = get!(CFst, other_fst);
-        let vec_fst: &VectorFst<TropicalWeight> = fst
-            .downcast_ref()
-            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
-        let other_vec_fst: &VectorFst<TropicalWeight> = other_fst
-            .downcast_ref()
-            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
-        let res = isomorphic(vec_fst, other_vec_fst)?;
-        unsafe { *is_isomorphic = res as usize }
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst: &VectorFst<W> = fst
+                .downcast_ref()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+            let other_vec_fst: &VectorFst<W> = other_fst
+                .downcast_ref()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+            let res = isomorphic(vec_fst, other_vec_fst)?;
+            unsafe { *is_isomorphic = res as usize }
+        });
         Ok(())
     })
 }
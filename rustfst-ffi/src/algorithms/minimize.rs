@@ -1,13 +1,13 @@
 use anyhow::anyhow;
 
 use crate::fst::CFst;
+use crate::weight::dispatch_weight;
 use crate::{get_mut, wrap, RUSTFST_FFI_RESULT};
 
 use ffi_convert::*;
 use rustfst::algorithms::{minimize_with_config, MinimizeConfig};
 use rustfst::fst_impls::VectorFst;
 use rustfst::prelude::minimize;
-use rustfst::semirings::TropicalWeight;
 
 #[derive(AsRust, CReprOf, CDrop, RawPointerConverter)]
 #[target_type(MinimizeConfig)]
@@ -42,10 +42,12 @@ pub unsafe extern "C" fn fst_minimize_config_new(
 pub unsafe extern "C" fn fst_minimize(ptr: *mut CFst) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get_mut!(CFst, ptr);
-        let vec_fst: &mut VectorFst<TropicalWeight> = fst
-            .downcast_mut()
-            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
-        minimize(vec_fst)?;
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst: &mut VectorFst<W> = fst
+                .downcast_mut()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+            minimize(vec_fst)?;
+        });
         Ok(())
     })
 }
@@ -60,14 +62,16 @@ pub unsafe extern "C" fn fst_minimize_with_config(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get_mut!(CFst, ptr);
-        let vec_fst: &mut VectorFst<TropicalWeight> = fst
-            .downcast_mut()
-            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
-
         let config = unsafe {
             <CMinimizeConfig as ffi_convert::RawBorrow<CMinimizeConfig>>::raw_borrow(config)?
         };
-        minimize_with_config(vec_fst, config.as_rust()?)?;
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst: &mut VectorFst<W> = fst
+                .downcast_mut()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+
+            minimize_with_config(vec_fst, config.as_rust()?)?;
+        });
         Ok(())
     })
 }
@@ -1,17 +1,25 @@
+pub mod closure;
 pub mod compose;
 pub mod concat;
 pub mod connect;
 pub mod determinize;
+pub mod encode;
+pub mod invert;
 pub mod isomorphic;
 mod minimize;
 pub mod optimize;
 pub mod project;
+pub mod push;
 pub mod randgen;
+pub mod relabel_pairs;
 pub mod replace;
 pub mod reverse;
 pub mod rm_epsilon;
+pub mod shortest_distance;
 pub mod shortest_path;
+pub mod state_sort;
 pub mod top_sort;
+pub mod tr_map;
 pub mod tr_sort;
 pub mod tr_unique;
 pub mod union;
@@ -2,12 +2,12 @@ use anyhow::{anyhow, Result};
 
 use super::EnumConversionError;
 use crate::fst::CFst;
+use crate::weight::dispatch_weight;
 use crate::{get_mut, wrap, RUSTFST_FFI_RESULT};
 
 use ffi_convert::*;
 use rustfst::algorithms::{project, ProjectType};
 use rustfst::fst_impls::VectorFst;
-use rustfst::semirings::TropicalWeight;
 
 #[derive(RawPointerConverter)]
 pub struct CProjectType(usize);
@@ -63,14 +63,14 @@ pub unsafe extern "C" fn fst_project(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get_mut!(CFst, ptr);
-        let vec_fst: &mut VectorFst<TropicalWeight> = fst
-            .downcast_mut()
-            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
-
         let project_type =
             unsafe { <CProjectType as ffi_convert::RawBorrow<CProjectType>>::raw_borrow(config)? };
-
-        project(vec_fst, project_type.as_rust()?);
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst: &mut VectorFst<W> = fst
+                .downcast_mut()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+            project(vec_fst, project_type.as_rust()?);
+        });
         Ok(())
     })
 }
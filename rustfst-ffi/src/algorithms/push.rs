@@ -0,0 +1,72 @@
+use anyhow::{anyhow, bail, format_err, Result};
+
+use crate::fst::CFst;
+use crate::weight::dispatch_weight;
+use crate::{get, get_mut, wrap, RUSTFST_FFI_RESULT};
+
+use ffi_convert::RawPointerConverter;
+use rustfst::algorithms::{
+    push_weights_with_config, push_with_config, PushConfig, PushType, PushWeightsConfig,
+    ReweightType,
+};
+use rustfst::fst_impls::VectorFst;
+
+fn reweight_type_from_c(reweight_type: libc::size_t) -> Result<ReweightType> {
+    match reweight_type {
+        0 => Ok(ReweightType::ReweightToInitial),
+        1 => Ok(ReweightType::ReweightToFinal),
+        _ => bail!("Unexpected reweight type : {}", reweight_type),
+    }
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_push_weights(
+    ptr: *mut CFst,
+    reweight_type: libc::size_t,
+    delta: libc::c_float,
+    remove_total_weight: bool,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get_mut!(CFst, ptr);
+        let reweight_type = reweight_type_from_c(reweight_type)?;
+        let config = PushWeightsConfig::new(delta, remove_total_weight);
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst: &mut VectorFst<W> = fst
+                .downcast_mut()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+            push_weights_with_config(vec_fst, reweight_type, config)?;
+        });
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_push(
+    ptr: *const CFst,
+    reweight_type: libc::size_t,
+    push_type: libc::c_uint,
+    delta: libc::c_float,
+    res_fst: *mut *const CFst,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get!(CFst, ptr);
+        let reweight_type = reweight_type_from_c(reweight_type)?;
+        let push_type = PushType::from_bits(push_type)
+            .ok_or_else(|| format_err!("Unexpected push type : {}", push_type))?;
+        let config = PushConfig::new(delta);
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst: &VectorFst<W> = fst
+                .downcast_ref()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+            let res: VectorFst<W> = push_with_config(vec_fst, reweight_type, push_type, config)?;
+            unsafe { *res_fst = CFst(Box::new(res)).into_raw_pointer() };
+        });
+        Ok(())
+    })
+}
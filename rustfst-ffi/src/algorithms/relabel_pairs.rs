@@ -0,0 +1,56 @@
+use anyhow::anyhow;
+
+use crate::fst::CFst;
+use crate::weight::dispatch_weight;
+use crate::CLabel;
+use crate::{get_mut, wrap, RUSTFST_FFI_RESULT};
+
+use rustfst::algorithms::relabel_pairs;
+use rustfst::fst_impls::VectorFst;
+use rustfst::Label;
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct CLabelPair {
+    pub old_label: CLabel,
+    pub new_label: CLabel,
+}
+
+unsafe fn label_pairs<'a>(
+    pairs_ptr: *const CLabelPair,
+    pairs_len: libc::size_t,
+) -> impl Iterator<Item = (Label, Label)> + 'a {
+    let pairs: &'a [CLabelPair] = if pairs_len == 0 {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(pairs_ptr, pairs_len) }
+    };
+    pairs
+        .iter()
+        .map(|pair| (pair.old_label as Label, pair.new_label as Label))
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_relabel_pairs(
+    ptr: *mut CFst,
+    ipairs_ptr: *const CLabelPair,
+    ipairs_len: libc::size_t,
+    opairs_ptr: *const CLabelPair,
+    opairs_len: libc::size_t,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get_mut!(CFst, ptr);
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst: &mut VectorFst<W> = fst
+                .downcast_mut()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+            let ipairs = unsafe { label_pairs(ipairs_ptr, ipairs_len) };
+            let opairs = unsafe { label_pairs(opairs_ptr, opairs_len) };
+            relabel_pairs(vec_fst, ipairs, opairs)?;
+        });
+        Ok(())
+    })
+}
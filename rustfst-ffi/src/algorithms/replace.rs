@@ -1,12 +1,13 @@
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Result};
 
 use crate::fst::CFst;
+use crate::weight::dispatch_weight;
 use crate::CLabel;
 use crate::{get, wrap, RUSTFST_FFI_RESULT};
 
 use ffi_convert::RawPointerConverter;
 use rustfst::algorithms::replace::replace;
-use rustfst::prelude::{Label, TropicalWeight, VectorFst};
+use rustfst::prelude::{Label, VectorFst};
 
 #[repr(C)]
 #[derive(Debug)]
@@ -29,24 +30,29 @@ pub unsafe extern "C" fn fst_replace(
     wrap(|| {
         let label_fst_pairs =
             unsafe { std::slice::from_raw_parts_mut(fst_list_ptr, fst_list_ptr_len) };
-        let fst_list = label_fst_pairs
-            .iter_mut()
-            .map(|pair| -> Result<(CLabel, &VectorFst<TropicalWeight>)> {
+        let weight_type = match label_fst_pairs.first() {
+            Some(pair) => {
                 let fst_ptr = pair.fst;
-                let fst = get!(CFst, fst_ptr);
-                let vec_fst: &VectorFst<TropicalWeight> = fst
-                    .downcast_ref()
-                    .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
-                Ok((pair.label as Label, vec_fst))
-            })
-            .collect::<Result<Vec<(CLabel, &VectorFst<TropicalWeight>)>>>()?;
-        let res_fst: VectorFst<TropicalWeight> = replace::<
-            TropicalWeight,
-            VectorFst<TropicalWeight>,
-            _,
-            _,
-        >(fst_list, root, epsilon_on_replace)?;
-        unsafe { *replaced_fst = CFst(Box::new(res_fst)).into_raw_pointer() };
+                get!(CFst, fst_ptr).fst_weight_type()
+            }
+            None => bail!("No FST to replace"),
+        };
+        dispatch_weight!(weight_type, W => {
+            let fst_list = label_fst_pairs
+                .iter_mut()
+                .map(|pair| -> Result<(CLabel, &VectorFst<W>)> {
+                    let fst_ptr = pair.fst;
+                    let fst = get!(CFst, fst_ptr);
+                    let vec_fst: &VectorFst<W> = fst
+                        .downcast_ref()
+                        .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+                    Ok((pair.label as Label, vec_fst))
+                })
+                .collect::<Result<Vec<(CLabel, &VectorFst<W>)>>>()?;
+            let res_fst: VectorFst<W> =
+                replace::<W, VectorFst<W>, _, _>(fst_list, root, epsilon_on_replace)?;
+            unsafe { *replaced_fst = CFst(Box::new(res_fst)).into_raw_pointer() };
+        });
         Ok(())
     })
 }
@@ -1,11 +1,11 @@
 use anyhow::anyhow;
 
 use crate::fst::CFst;
+use crate::weight::dispatch_weight;
 use crate::{get_mut, wrap, RUSTFST_FFI_RESULT};
 
 use rustfst::algorithms::rm_epsilon::rm_epsilon;
 use rustfst::fst_impls::VectorFst;
-use rustfst::semirings::TropicalWeight;
 
 /// # Safety
 ///
@@ -14,10 +14,12 @@ use rustfst::semirings::TropicalWeight;
 pub unsafe extern "C" fn fst_rm_epsilon(ptr: *mut CFst) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get_mut!(CFst, ptr);
-        let vec_fst: &mut VectorFst<TropicalWeight> = fst
-            .downcast_mut()
-            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
-        rm_epsilon(vec_fst)?;
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst: &mut VectorFst<W> = fst
+                .downcast_mut()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+            rm_epsilon(vec_fst)?;
+        });
         Ok(())
     })
 }
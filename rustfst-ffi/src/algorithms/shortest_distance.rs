@@ -0,0 +1,37 @@
+use anyhow::anyhow;
+
+use crate::fst::CFst;
+use crate::weight::{dispatch_weight, BindableWeight};
+use crate::{get, wrap, RUSTFST_FFI_RESULT};
+
+use ffi_convert::{CArray, CReprOf, RawPointerConverter};
+use rustfst::algorithms::{shortest_distance_with_config, ShortestDistanceConfig};
+use rustfst::fst_impls::VectorFst;
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_shortest_distance(
+    ptr: *const CFst,
+    reverse: bool,
+    delta: libc::c_float,
+    distances: *mut *const CArray<libc::c_float>,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get!(CFst, ptr);
+        let config = ShortestDistanceConfig::new(delta);
+        let dists = dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst: &VectorFst<W> = fst
+                .downcast_ref()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+            shortest_distance_with_config(vec_fst, reverse, config)?
+                .iter()
+                .map(|w| w.to_c_float())
+                .collect::<Vec<_>>()
+        });
+        let c_dists = CArray::<libc::c_float>::c_repr_of(dists)?;
+        unsafe { *distances = c_dists.into_raw_pointer() };
+        Ok(())
+    })
+}
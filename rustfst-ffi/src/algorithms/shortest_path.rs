@@ -1,12 +1,12 @@
 use anyhow::anyhow;
 
 use crate::fst::CFst;
+use crate::weight::dispatch_weight;
 use crate::{get, wrap, RUSTFST_FFI_RESULT};
 
 use ffi_convert::*;
 use rustfst::algorithms::{shortest_path, shortest_path_with_config, ShortestPathConfig};
 use rustfst::fst_impls::VectorFst;
-use rustfst::semirings::TropicalWeight;
 
 #[derive(AsRust, CReprOf, CDrop, RawPointerConverter)]
 #[target_type(ShortestPathConfig)]
@@ -47,11 +47,13 @@ pub unsafe extern "C" fn fst_shortest_path(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get!(CFst, ptr);
-        let vec_fst: &VectorFst<TropicalWeight> = fst
-            .downcast_ref()
-            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
-        let res: VectorFst<TropicalWeight> = shortest_path(vec_fst)?;
-        unsafe { *res_fst = CFst(Box::new(res)).into_raw_pointer() };
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst: &VectorFst<W> = fst
+                .downcast_ref()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+            let res: VectorFst<W> = shortest_path(vec_fst)?;
+            unsafe { *res_fst = CFst(Box::new(res)).into_raw_pointer() };
+        });
         Ok(())
     })
 }
@@ -67,17 +69,19 @@ pub unsafe extern "C" fn fst_shortest_path_with_config(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get!(CFst, ptr);
-        let vec_fst: &VectorFst<TropicalWeight> = fst
-            .downcast_ref()
-            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
-
         let config = unsafe {
             <CShortestPathConfig as ffi_convert::RawBorrow<CShortestPathConfig>>::raw_borrow(
                 config,
             )?
         };
-        let res: VectorFst<TropicalWeight> = shortest_path_with_config(vec_fst, config.as_rust()?)?;
-        unsafe { *res_fst = CFst(Box::new(res)).into_raw_pointer() };
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst: &VectorFst<W> = fst
+                .downcast_ref()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+
+            let res: VectorFst<W> = shortest_path_with_config(vec_fst, config.as_rust()?)?;
+            unsafe { *res_fst = CFst(Box::new(res)).into_raw_pointer() };
+        });
         Ok(())
     })
 }
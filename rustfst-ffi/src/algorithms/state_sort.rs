@@ -0,0 +1,51 @@
+use anyhow::{anyhow, bail};
+
+use crate::fst::CFst;
+use crate::weight::dispatch_weight;
+use crate::CStateId;
+use crate::{get_mut, wrap, RUSTFST_FFI_RESULT};
+
+use rustfst::algorithms::state_sort;
+use rustfst::fst_impls::VectorFst;
+use rustfst::StateId;
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_state_sort(
+    ptr: *mut CFst,
+    order_ptr: *const CStateId,
+    order_len: libc::size_t,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get_mut!(CFst, ptr);
+        let order: &[CStateId] = if order_len == 0 {
+            &[]
+        } else if order_ptr.is_null() {
+            bail!("fst_state_sort : null order pointer");
+        } else {
+            unsafe { std::slice::from_raw_parts(order_ptr, order_len) }
+        };
+        // `order` must be a permutation of the states.
+        let mut seen = vec![false; order.len()];
+        for s in order {
+            match seen.get_mut(*s as usize) {
+                Some(seen_s) if !*seen_s => *seen_s = true,
+                _ => bail!(
+                    "fst_state_sort : order is not a permutation of the {} states, {} is out of range or repeated",
+                    order.len(),
+                    s
+                ),
+            }
+        }
+        let order = order.iter().map(|s| *s as StateId).collect::<Vec<_>>();
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst: &mut VectorFst<W> = fst
+                .downcast_mut()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+            state_sort(vec_fst, &order)?;
+        });
+        Ok(())
+    })
+}
@@ -2,11 +2,11 @@ use anyhow::anyhow;
 
 use crate::fst::as_mut_fst;
 use crate::fst::CFst;
+use crate::weight::dispatch_weight;
 use crate::{get_mut, wrap, RUSTFST_FFI_RESULT};
 
 use rustfst::algorithms::top_sort;
 use rustfst::fst_impls::VectorFst;
-use rustfst::semirings::TropicalWeight;
 
 /// # Safety
 ///
@@ -15,8 +15,10 @@ use rustfst::semirings::TropicalWeight;
 pub unsafe extern "C" fn fst_top_sort(ptr: *mut CFst) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get_mut!(CFst, ptr);
-        let vec_fst = as_mut_fst!(VectorFst<TropicalWeight>, fst);
-        top_sort(vec_fst)?;
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst = as_mut_fst!(VectorFst<W>, fst);
+            top_sort(vec_fst)?;
+        });
         Ok(())
     })
 }
@@ -0,0 +1,108 @@
+use anyhow::{anyhow, bail, Result};
+
+use crate::fst::CFst;
+use crate::weight::{dispatch_weight, BindableWeight};
+use crate::{get, get_mut, wrap, RUSTFST_FFI_RESULT};
+
+use ffi_convert::RawPointerConverter;
+use rustfst::algorithms::tr_map;
+use rustfst::algorithms::tr_mappers::{
+    IdentityTrMapper, InputEpsilonMapper, InvertWeightMapper, OutputEpsilonMapper, PlusMapper,
+    QuantizeMapper, RmWeightMapper, TimesMapper,
+};
+use rustfst::fst_impls::VectorFst;
+use rustfst::semirings::{WeaklyDivisibleSemiring, WeightQuantize};
+
+/// Mappers from `rustfst::algorithms::tr_mappers` usable with `fst_tr_map`.
+#[derive(Debug, Clone, Copy)]
+pub enum CTrMapper {
+    Identity,
+    InputEpsilon,
+    OutputEpsilon,
+    RmWeight,
+    InvertWeight,
+    Quantize(f32),
+    Plus(f32),
+    Times(f32),
+}
+
+#[derive(RawPointerConverter)]
+pub struct CTrMapperConfig(pub(crate) CTrMapper);
+
+fn tr_map_float<W>(fst: &mut VectorFst<W>, mapper: CTrMapper) -> Result<()>
+where
+    W: BindableWeight + WeaklyDivisibleSemiring + WeightQuantize,
+{
+    match mapper {
+        CTrMapper::Identity => tr_map(fst, &IdentityTrMapper {}),
+        CTrMapper::InputEpsilon => tr_map(fst, &InputEpsilonMapper {}),
+        CTrMapper::OutputEpsilon => tr_map(fst, &OutputEpsilonMapper {}),
+        CTrMapper::RmWeight => tr_map(fst, &RmWeightMapper {}),
+        CTrMapper::InvertWeight => tr_map(fst, &InvertWeightMapper {}),
+        CTrMapper::Quantize(delta) => tr_map(fst, &QuantizeMapper::new(delta)),
+        CTrMapper::Plus(value) => tr_map(fst, &PlusMapper::from_weight(W::from_c_float(value))),
+        CTrMapper::Times(value) => tr_map(fst, &TimesMapper::from_weight(W::from_c_float(value))),
+    }
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_tr_mapper_new(
+    mapper_type: libc::size_t,
+    value: libc::c_float,
+    ptr: *mut *const CTrMapperConfig,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let mapper = match mapper_type {
+            0 => CTrMapper::Identity,
+            1 => CTrMapper::InputEpsilon,
+            2 => CTrMapper::OutputEpsilon,
+            3 => CTrMapper::RmWeight,
+            4 => CTrMapper::InvertWeight,
+            5 => CTrMapper::Quantize(value),
+            6 => CTrMapper::Plus(value),
+            7 => CTrMapper::Times(value),
+            _ => bail!("Unexpected mapper type : {}", mapper_type),
+        };
+        unsafe { *ptr = CTrMapperConfig(mapper).into_raw_pointer() };
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_tr_mapper_destroy(ptr: *mut CTrMapperConfig) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        if ptr.is_null() {
+            return Ok(());
+        }
+
+        drop(unsafe { Box::from_raw(ptr) });
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_tr_map(
+    ptr: *mut CFst,
+    mapper: *const CTrMapperConfig,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get_mut!(CFst, ptr);
+        let mapper = *get!(CTrMapperConfig, mapper);
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst: &mut VectorFst<W> = fst
+                .downcast_mut()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+            tr_map_float(vec_fst, mapper)?;
+        });
+        Ok(())
+    })
+}
@@ -1,7 +1,7 @@
 use super::*;
 use anyhow::anyhow;
 use rustfst::algorithms::concat::ConcatFst;
-use rustfst::prelude::{TropicalWeight, VectorFst};
+use rustfst::prelude::VectorFst;
 
 /// # Safety
 ///
@@ -14,18 +14,19 @@ pub unsafe extern "C" fn concat_fst_new(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst1 = get!(CFst, fst1);
-        let vec_fst1: &VectorFst<TropicalWeight> = fst1
-            .downcast_ref()
-            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
-
         let fst2 = get!(CFst, fst2);
-        let vec_fst2: &VectorFst<TropicalWeight> = fst2
-            .downcast_ref()
-            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
-        let fst = Box::new(ConcatFst::<TropicalWeight, VectorFst<TropicalWeight>>::new(
-            vec_fst1.clone(),
-            vec_fst2.clone(),
-        )?);
+        let fst: Box<dyn BindableFst> = dispatch_weight!(fst1.fst_weight_type(), W => {
+            let vec_fst1: &VectorFst<W> = fst1
+                .downcast_ref()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+            let vec_fst2: &VectorFst<W> = fst2
+                .downcast_ref()
+                .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+            Box::new(ConcatFst::<W, VectorFst<W>>::new(
+                vec_fst1.clone(),
+                vec_fst2.clone(),
+            )?)
+        });
         let raw_pointer = CFst(fst).into_raw_pointer();
         unsafe { *ptr = raw_pointer };
         Ok(())
@@ -40,11 +41,12 @@ pub unsafe extern "C" fn concat_fst_compute(fst: *mut *const CFst) -> RUSTFST_FF
     wrap(|| {
         let c_fst = unsafe { *fst };
         let c_fst = get!(CFst, c_fst);
-        let vec_fst: &ConcatFst<TropicalWeight, VectorFst<TropicalWeight>> =
-            c_fst
+        let new_fst: Box<dyn BindableFst> = dispatch_weight!(c_fst.fst_weight_type(), W => {
+            let concat_fst: &ConcatFst<W, VectorFst<W>> = c_fst
                 .downcast_ref()
                 .ok_or_else(|| anyhow!("Could not downcast to concat FST"))?;
-        let new_fst = Box::new(vec_fst.compute::<VectorFst<TropicalWeight>>()?);
+            Box::new(concat_fst.compute::<VectorFst<W>>()?)
+        });
         unsafe { *fst = CFst(new_fst).into_raw_pointer() }
         Ok(())
     })
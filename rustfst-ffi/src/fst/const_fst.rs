@@ -3,6 +3,8 @@ use anyhow::anyhow;
 use rustfst::DrawingConfig;
 use std::ffi::CString;
 
+use crate::weight::dispatch_weight;
+
 /// # Safety
 ///
 /// The pointers should be valid.
@@ -10,10 +12,14 @@ use std::ffi::CString;
 pub unsafe fn const_fst_from_path(
     ptr: *mut *const CFst,
     path: *const libc::c_char,
+    weight_type: libc::size_t,
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let path = unsafe { CStr::from_ptr(path) }.as_rust()?;
-        let fst = Box::new(ConstFst::<TropicalWeight>::read(path)?);
+        let weight_type = CWeightType::try_from(weight_type)?;
+        let fst: Box<dyn BindableFst> = dispatch_weight!(weight_type, W => {
+            Box::new(ConstFst::<W>::read(path)?)
+        });
         let raw_pointer = CFst(fst).into_raw_pointer();
         unsafe { *ptr = raw_pointer };
         Ok(())
@@ -31,8 +37,10 @@ pub unsafe fn const_fst_write_file(
     wrap(|| {
         let fst = get!(CFst, fst);
         let path = unsafe { CStr::from_ptr(path) }.as_rust()?;
-        let const_fst = as_fst!(ConstFst<TropicalWeight>, fst);
-        const_fst.write(path)?;
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let const_fst = as_fst!(ConstFst<W>, fst);
+            const_fst.write(path)?;
+        });
         Ok(())
     })
 }
@@ -49,10 +57,12 @@ pub unsafe fn const_fst_equals(
     wrap(|| {
         let fst = get!(CFst, fst);
         let other_fst = get!(CFst, other_fst);
-        let const_fst = as_fst!(ConstFst<TropicalWeight>, fst);
-        let other_const_fst = as_fst!(ConstFst<TropicalWeight>, other_fst);
-        let res = const_fst.eq(other_const_fst);
-        unsafe { *is_equal = res as usize }
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let const_fst = as_fst!(ConstFst<W>, fst);
+            let other_const_fst = as_fst!(ConstFst<W>, other_fst);
+            let res = const_fst.eq(other_const_fst);
+            unsafe { *is_equal = res as usize }
+        });
         Ok(())
     })
 }
@@ -67,9 +77,11 @@ pub unsafe extern "C" fn const_fst_copy(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get!(CFst, fst_ptr);
-        let const_fst = as_fst!(ConstFst<TropicalWeight>, fst);
-        let clone = const_fst.clone();
-        unsafe { *clone_ptr = CFst(Box::new(clone)).into_raw_pointer() };
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let const_fst = as_fst!(ConstFst<W>, fst);
+            let clone = const_fst.clone();
+            unsafe { *clone_ptr = CFst(Box::new(clone)).into_raw_pointer() };
+        });
         Ok(())
     })
 }
@@ -98,16 +110,15 @@ pub unsafe fn const_fst_draw(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get_mut!(CFst, fst_ptr);
-        let const_fst = as_mut_fst!(ConstFst<TropicalWeight>, fst);
 
         if !isyms.is_null() {
             let isymt = get!(CSymbolTable, isyms);
-            const_fst.fst_set_input_symbols(isymt.clone());
+            fst.fst_set_input_symbols(isymt.clone());
         }
 
         if !osyms.is_null() {
             let osymt = get!(CSymbolTable, osyms);
-            const_fst.fst_set_output_symbols(osymt.clone());
+            fst.fst_set_output_symbols(osymt.clone());
         }
 
         let drawing_config = DrawingConfig {
@@ -127,7 +138,10 @@ pub unsafe fn const_fst_draw(
             print_weight: print_weight > 0,
         };
 
-        const_fst.draw(unsafe { CStr::from_ptr(fname).as_rust()? }, &drawing_config)?;
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let const_fst = as_fst!(ConstFst<W>, fst);
+            const_fst.draw(unsafe { CStr::from_ptr(fname).as_rust()? }, &drawing_config)?;
+        });
 
         Ok(())
     })
@@ -143,9 +157,11 @@ pub unsafe extern "C" fn const_fst_display(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get!(CFst, fst_ptr);
-        let vec_fst = as_fst!(ConstFst<TropicalWeight>, fst);
-        let res = format!("{}", vec_fst);
-        unsafe { *s = CString::c_repr_of(res)?.into_raw_pointer() as *const libc::c_char };
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let const_fst = as_fst!(ConstFst<W>, fst);
+            let res = format!("{}", const_fst);
+            unsafe { *s = CString::c_repr_of(res)?.into_raw_pointer() as *const libc::c_char };
+        });
         Ok(())
     })
 }
@@ -6,6 +6,7 @@ pub mod vector_fst;
 use crate::symbol_table::CSymbolTable;
 use crate::tr::CTr;
 use crate::trs::CTrs;
+use crate::weight::{dispatch_weight, BindableWeight, CWeightType};
 use crate::{get, get_mut, wrap, CStateId, RUSTFST_FFI_RESULT};
 
 use anyhow::Result;
@@ -13,10 +14,10 @@ use downcast_rs::Downcast;
 use ffi_convert::*;
 use rustfst::algorithms::concat::ConcatFst;
 use rustfst::fst_impls::{ConstFst, VectorFst};
-use rustfst::fst_traits::{Fst, MutableFst, SerializableFst};
-use rustfst::semirings::TropicalWeight;
+use rustfst::fst_traits::{CoreFst, Fst, MutableFst, SerializableFst};
 use rustfst::Semiring;
-use rustfst::{StateId, SymbolTable, Trs, TrsVec};
+use rustfst::{StateId, SymbolTable, Trs};
+use std::convert::TryFrom;
 use std::ffi::CStr;
 use std::sync::Arc;
 
@@ -24,9 +25,11 @@ use std::sync::Arc;
 /// It makes the FST trait Boxable and downcastable to one of the supported C Fst structs.
 /// This trait allows to share Fst trait methods accross FST types by sharing a common input type in the binded methods.
 /// This generic Fst type can then be downcast to the appropriate Fst type (VectorFst, ConstFst, ..) in order to get access to specific methods (add_tr, ..).
+/// The weight type of the FST is exposed so that callers know which semiring to downcast to.
 pub trait BindableFst: Downcast {
+    fn fst_weight_type(&self) -> CWeightType;
     fn fst_start(&self) -> Option<StateId>;
-    fn fst_final_weight(&self, state: StateId) -> Result<Option<TropicalWeight>>;
+    fn fst_final_weight(&self, state: StateId) -> Result<Option<libc::c_float>>;
     fn fst_num_trs(&self, s: StateId) -> Result<usize>;
 
     #[inline]
@@ -40,7 +43,7 @@ pub trait BindableFst: Downcast {
         Some(state_id) == self.fst_start()
     }
 
-    fn fst_get_trs(&self, state_id: StateId) -> Result<TrsVec<TropicalWeight>>;
+    fn fst_get_trs(&self, state_id: StateId) -> Result<CTrs>;
     fn fst_input_symbols(&self) -> Option<Arc<SymbolTable>>;
     fn fst_output_symbols(&self) -> Option<Arc<SymbolTable>>;
     fn fst_set_input_symbols(&mut self, symt: Arc<SymbolTable>);
@@ -51,50 +54,53 @@ pub trait BindableFst: Downcast {
 
 downcast_rs::impl_downcast!(BindableFst);
 
-impl<F: Fst<TropicalWeight> + 'static> BindableFst for F {
-    fn fst_start(&self) -> Option<StateId> {
-        self.start()
-    }
-    fn fst_final_weight(&self, state: StateId) -> Result<Option<TropicalWeight>> {
-        self.final_weight(state)
-    }
-    fn fst_num_trs(&self, s: StateId) -> Result<usize> {
-        self.num_trs(s)
-    }
-    fn fst_get_trs(&self, state_id: StateId) -> Result<TrsVec<TropicalWeight>> {
-        self.get_trs(state_id).map(|it| it.to_trs_vec())
-    }
-    fn fst_input_symbols(&self) -> Option<Arc<SymbolTable>> {
-        self.input_symbols().cloned()
-    }
-    fn fst_output_symbols(&self) -> Option<Arc<SymbolTable>> {
-        self.output_symbols().cloned()
-    }
-    fn fst_set_input_symbols(&mut self, symt: Arc<SymbolTable>) {
-        self.set_input_symbols(symt)
-    }
-    fn fst_set_output_symbols(&mut self, symt: Arc<SymbolTable>) {
-        self.set_output_symbols(symt)
-    }
-    fn fst_take_input_symbols(&mut self) -> Option<Arc<SymbolTable>> {
-        self.take_input_symbols()
-    }
-    fn fst_take_output_symbols(&mut self) -> Option<Arc<SymbolTable>> {
-        self.take_output_symbols()
-    }
+macro_rules! impl_bindable_fst {
+    ($($fst:ty),*) => {
+        $(
+            impl<W: BindableWeight> BindableFst for $fst {
+                fn fst_weight_type(&self) -> CWeightType {
+                    W::WEIGHT_TYPE
+                }
+                fn fst_start(&self) -> Option<StateId> {
+                    self.start()
+                }
+                fn fst_final_weight(&self, state: StateId) -> Result<Option<libc::c_float>> {
+                    Ok(self.final_weight(state)?.map(|w| w.to_c_float()))
+                }
+                fn fst_num_trs(&self, s: StateId) -> Result<usize> {
+                    self.num_trs(s)
+                }
+                fn fst_get_trs(&self, state_id: StateId) -> Result<CTrs> {
+                    self.get_trs(state_id)
+                        .map(|it| CTrs(Box::new(it.to_trs_vec())))
+                }
+                fn fst_input_symbols(&self) -> Option<Arc<SymbolTable>> {
+                    self.input_symbols().cloned()
+                }
+                fn fst_output_symbols(&self) -> Option<Arc<SymbolTable>> {
+                    self.output_symbols().cloned()
+                }
+                fn fst_set_input_symbols(&mut self, symt: Arc<SymbolTable>) {
+                    self.set_input_symbols(symt)
+                }
+                fn fst_set_output_symbols(&mut self, symt: Arc<SymbolTable>) {
+                    self.set_output_symbols(symt)
+                }
+                fn fst_take_input_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+                    self.take_input_symbols()
+                }
+                fn fst_take_output_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+                    self.take_output_symbols()
+                }
+            }
+        )*
+    };
 }
 
-#[derive(RawPointerConverter)]
-pub struct CFst(pub Box<dyn BindableFst>);
-
-#[derive(RawPointerConverter)]
-pub struct CVecFst(pub Box<VectorFst<TropicalWeight>>);
-
-#[derive(RawPointerConverter)]
-pub struct CConstFst(pub Box<ConstFst<TropicalWeight>>);
+impl_bindable_fst!(VectorFst<W>, ConstFst<W>, ConcatFst<W, VectorFst<W>>);
 
 #[derive(RawPointerConverter)]
-pub struct CConcatFst(pub Box<ConcatFst<TropicalWeight, VectorFst<TropicalWeight>>>);
+pub struct CFst(pub Box<dyn BindableFst>);
 
 macro_rules! as_fst {
     ($typ:ty,$fst:ident) => {{
@@ -136,6 +142,22 @@ pub unsafe fn fst_start(fst: *const CFst, mut state: *mut CStateId) -> RUSTFST_F
     })
 }
 
+/// Returns the weight type of the wFST (0: Tropical, 1: Log, 2: Boolean).
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe fn fst_weight_type(
+    fst: *const CFst,
+    weight_type: *mut libc::size_t,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get!(CFst, fst);
+        unsafe { *weight_type = fst.fst_weight_type() as libc::size_t };
+        Ok(())
+    })
+}
+
 /// Retrieves the final weight of a state (if the state is a final one).
 /// # Safety
 ///
@@ -149,7 +171,7 @@ pub unsafe fn fst_final_weight(
     wrap(|| {
         let fst = get!(CFst, fst);
         fst.fst_final_weight(state_id)?
-            .map(|it| unsafe { *final_weight = *it.value() })
+            .map(|it| unsafe { *final_weight = it })
             .unwrap_or_else(|| final_weight = std::ptr::null_mut());
         Ok(())
     })
@@ -185,7 +207,7 @@ pub unsafe fn fst_get_trs(
     wrap(|| {
         let fst = get!(CFst, fst);
         let res = fst.fst_get_trs(state)?;
-        let trs_vec = CTrs(res).into_raw_pointer();
+        let trs_vec = res.into_raw_pointer();
         unsafe { *trs = trs_vec }
         Ok(())
     })
@@ -350,10 +372,14 @@ pub unsafe fn fst_unset_output_symbols(fst: *mut CFst) -> RUSTFST_FFI_RESULT {
 ///
 /// The pointers should be valid.
 #[no_mangle]
-pub unsafe extern "C" fn fst_weight_one(weight_one: *mut libc::c_float) -> RUSTFST_FFI_RESULT {
+pub unsafe extern "C" fn fst_weight_one(
+    weight_one: *mut libc::c_float,
+    weight_type: libc::size_t,
+) -> RUSTFST_FFI_RESULT {
     wrap(|| {
-        let weight = TropicalWeight::one();
-        unsafe { *weight_one = *weight.value() };
+        let weight_type = CWeightType::try_from(weight_type)?;
+        let weight = dispatch_weight!(weight_type, W => W::one().to_c_float());
+        unsafe { *weight_one = weight };
         Ok(())
     })
 }
@@ -362,10 +388,14 @@ pub unsafe extern "C" fn fst_weight_one(weight_one: *mut libc::c_float) -> RUSTF
 ///
 /// The pointers should be valid.
 #[no_mangle]
-pub unsafe extern "C" fn fst_weight_zero(weight_zero: *mut libc::c_float) -> RUSTFST_FFI_RESULT {
+pub unsafe extern "C" fn fst_weight_zero(
+    weight_zero: *mut libc::c_float,
+    weight_type: libc::size_t,
+) -> RUSTFST_FFI_RESULT {
     wrap(|| {
-        let weight = TropicalWeight::zero();
-        unsafe { *weight_zero = *weight.value() };
+        let weight_type = CWeightType::try_from(weight_type)?;
+        let weight = dispatch_weight!(weight_type, W => W::zero().to_c_float());
+        unsafe { *weight_zero = weight };
         Ok(())
     })
 }
@@ -1,10 +1,13 @@
+use crate::fst::BindableFst;
 use crate::fst::CFst;
 use crate::symbol_table::CSymbolTable;
+use crate::weight::{dispatch_weight, BindableWeight, CWeightType};
 use crate::{get_mut, wrap, RUSTFST_FFI_RESULT};
 use anyhow::{anyhow, Context, Result};
 use ffi_convert::*;
-use rustfst::prelude::{Label, Semiring, TropicalWeight, VectorFst};
+use rustfst::prelude::{Label, VectorFst};
 use rustfst::utils::{acceptor, transducer};
+use std::convert::TryFrom;
 use std::ffi::CStr;
 
 /// # Safety
@@ -16,6 +19,7 @@ pub unsafe extern "C" fn utils_string_to_acceptor(
     symbol_table: *mut CSymbolTable,
     weight: libc::c_float,
     fst_ptr: *mut *const CFst,
+    weight_type: libc::size_t,
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let astring: String = unsafe { CStr::from_ptr(astring) }.as_rust()?;
@@ -27,9 +31,11 @@ pub unsafe extern "C" fn utils_string_to_acceptor(
                     .with_context(|| anyhow!("Could not retrieve symbol {:?} in symbol table", sym))
             })
             .collect::<Result<Vec<Label>>>()?;
-        let acceptor_fst: VectorFst<TropicalWeight> =
-            acceptor(labels.as_slice(), TropicalWeight::new(weight));
-        unsafe { *fst_ptr = CFst(Box::new(acceptor_fst)).into_raw_pointer() }
+        let weight_type = CWeightType::try_from(weight_type)?;
+        let acceptor_fst: Box<dyn BindableFst> = dispatch_weight!(weight_type, W => {
+            Box::new(acceptor::<W, VectorFst<W>>(labels.as_slice(), W::from_c_float(weight)))
+        });
+        unsafe { *fst_ptr = CFst(acceptor_fst).into_raw_pointer() }
         Ok(())
     })
 }
@@ -45,6 +51,7 @@ pub unsafe extern "C" fn utils_string_to_transducer(
     osymt: *mut CSymbolTable,
     weight: libc::c_float,
     fst_ptr: *mut *const CFst,
+    weight_type: libc::size_t,
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let istring: String = unsafe { CStr::from_ptr(istring) }.as_rust()?;
@@ -67,12 +74,15 @@ pub unsafe extern "C" fn utils_string_to_transducer(
                     .with_context(|| anyhow!("Could not retrieve symbol {:?} in symbol table", sym))
             })
             .collect::<Result<Vec<Label>>>()?;
-        let transducer_fst: VectorFst<TropicalWeight> = transducer(
-            ilabels.as_slice(),
-            olabels.as_slice(),
-            TropicalWeight::new(weight),
-        );
-        unsafe { *fst_ptr = CFst(Box::new(transducer_fst)).into_raw_pointer() }
+        let weight_type = CWeightType::try_from(weight_type)?;
+        let transducer_fst: Box<dyn BindableFst> = dispatch_weight!(weight_type, W => {
+            Box::new(transducer::<W, VectorFst<W>>(
+                ilabels.as_slice(),
+                olabels.as_slice(),
+                W::from_c_float(weight),
+            ))
+        });
+        unsafe { *fst_ptr = CFst(transducer_fst).into_raw_pointer() }
         Ok(())
     })
 }
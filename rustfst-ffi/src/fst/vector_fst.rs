@@ -6,13 +6,17 @@ use rustfst::fst_traits::ExpandedFst;
 use rustfst::DrawingConfig;
 use std::ffi::CString;
 
+use crate::weight::dispatch_weight;
+
 /// # Safety
 ///
 /// The pointers should be valid.
 #[no_mangle]
-pub unsafe fn vec_fst_new(ptr: *mut *const CFst) -> RUSTFST_FFI_RESULT {
+pub unsafe fn vec_fst_new(ptr: *mut *const CFst, weight_type: libc::size_t) -> RUSTFST_FFI_RESULT {
     wrap(|| {
-        let fst = Box::new(VectorFst::new());
+        let weight_type = CWeightType::try_from(weight_type)?;
+        let fst: Box<dyn BindableFst> =
+            dispatch_weight!(weight_type, W => Box::new(VectorFst::<W>::new()));
         let raw_pointer = CFst(fst).into_raw_pointer();
         unsafe { *ptr = raw_pointer };
         Ok(())
@@ -26,8 +30,10 @@ pub unsafe fn vec_fst_new(ptr: *mut *const CFst) -> RUSTFST_FFI_RESULT {
 pub unsafe fn vec_fst_set_start(fst: *mut CFst, state: CStateId) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let c_fst = get_mut!(CFst, fst);
-        let vec_fst = as_mut_fst!(VectorFst<TropicalWeight>, c_fst);
-        vec_fst.set_start(state)?;
+        dispatch_weight!(c_fst.fst_weight_type(), W => {
+            let vec_fst = as_mut_fst!(VectorFst<W>, c_fst);
+            vec_fst.set_start(state)?;
+        });
         Ok(())
     })
 }
@@ -43,8 +49,10 @@ pub unsafe fn vec_fst_set_final(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get_mut!(CFst, fst);
-        let vec_fst = as_mut_fst!(VectorFst<TropicalWeight>, fst);
-        vec_fst.set_final(state, TropicalWeight::new(weight))?;
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst = as_mut_fst!(VectorFst<W>, fst);
+            vec_fst.set_final(state, W::from_c_float(weight))?;
+        });
         Ok(())
     })
 }
@@ -56,9 +64,11 @@ pub unsafe fn vec_fst_set_final(
 pub unsafe fn vec_fst_add_state(fst: *mut CFst, state: *mut CStateId) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get_mut!(CFst, fst);
-        let vec_fst = as_mut_fst!(VectorFst<TropicalWeight>, fst);
-        let res = vec_fst.add_state();
-        unsafe { *state = res }
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst = as_mut_fst!(VectorFst<W>, fst);
+            let res = vec_fst.add_state();
+            unsafe { *state = res }
+        });
         Ok(())
     })
 }
@@ -70,8 +80,10 @@ pub unsafe fn vec_fst_add_state(fst: *mut CFst, state: *mut CStateId) -> RUSTFST
 pub unsafe fn vec_fst_delete_states(fst: *mut CFst) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get_mut!(CFst, fst);
-        let vec_fst = as_mut_fst!(VectorFst<TropicalWeight>, fst);
-        vec_fst.del_all_states();
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst = as_mut_fst!(VectorFst<W>, fst);
+            vec_fst.del_all_states();
+        });
         Ok(())
     })
 }
@@ -87,9 +99,11 @@ pub unsafe fn vec_fst_add_tr(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get_mut!(CFst, fst);
-        let tr = unsafe { <CTr as ffi_convert::RawBorrow<CTr>>::raw_borrow(tr)? }.as_rust()?;
-        let vec_fst = as_mut_fst!(VectorFst<TropicalWeight>, fst);
-        vec_fst.add_tr(state, tr)?;
+        let tr = unsafe { <CTr as ffi_convert::RawBorrow<CTr>>::raw_borrow(tr)? };
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst = as_mut_fst!(VectorFst<W>, fst);
+            vec_fst.add_tr(state, tr.as_rust()?)?;
+        });
         Ok(())
     })
 }
@@ -101,9 +115,10 @@ pub unsafe fn vec_fst_add_tr(
 pub unsafe fn vec_fst_del_final_weight(fst: *mut CFst, state: CStateId) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get_mut!(CFst, fst);
-        let vec_fst = as_mut_fst!(VectorFst<TropicalWeight>, fst);
-        vec_fst.delete_final_weight(state)?;
-
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst = as_mut_fst!(VectorFst<W>, fst);
+            vec_fst.delete_final_weight(state)?;
+        });
         Ok(())
     })
 }
@@ -115,10 +130,14 @@ pub unsafe fn vec_fst_del_final_weight(fst: *mut CFst, state: CStateId) -> RUSTF
 pub unsafe fn vec_fst_from_path(
     ptr: *mut *const CFst,
     path: *const libc::c_char,
+    weight_type: libc::size_t,
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let path = unsafe { CStr::from_ptr(path) }.as_rust()?;
-        let fst = Box::new(VectorFst::<TropicalWeight>::read(path)?);
+        let weight_type = CWeightType::try_from(weight_type)?;
+        let fst: Box<dyn BindableFst> = dispatch_weight!(weight_type, W => {
+            Box::new(VectorFst::<W>::read(path)?)
+        });
         let raw_pointer = CFst(fst).into_raw_pointer();
         unsafe { *ptr = raw_pointer };
         Ok(())
@@ -136,8 +155,10 @@ pub unsafe fn vec_fst_write_file(
     wrap(|| {
         let fst = get!(CFst, fst);
         let path = unsafe { CStr::from_ptr(path) }.as_rust()?;
-        let vec_fst = as_fst!(VectorFst<TropicalWeight>, fst);
-        vec_fst.write(path)?;
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst = as_fst!(VectorFst<W>, fst);
+            vec_fst.write(path)?;
+        });
         Ok(())
     })
 }
@@ -157,8 +178,6 @@ pub unsafe fn vec_fst_relabel_tables(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get_mut!(CFst, fst);
-        let vec_fst: &mut VectorFst<_> = as_mut_fst!(VectorFst<TropicalWeight>, fst);
-
         let old_isymbols = get_symt(old_isymbols)?;
         let new_isymbols =
             get_symt(new_isymbols)?.ok_or_else(|| format_err!("New isymbols ptr is null"))?;
@@ -169,14 +188,17 @@ pub unsafe fn vec_fst_relabel_tables(
         let attach_new_isymbols = attach_new_isymbols > 0;
         let attach_new_osymbols = attach_new_osymbols > 0;
 
-        vec_fst.relabel_tables(
-            old_isymbols,
-            new_isymbols,
-            attach_new_isymbols,
-            old_osymbols,
-            new_osymbols,
-            attach_new_osymbols,
-        )?;
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst: &mut VectorFst<_> = as_mut_fst!(VectorFst<W>, fst);
+            vec_fst.relabel_tables(
+                old_isymbols,
+                new_isymbols,
+                attach_new_isymbols,
+                old_osymbols,
+                new_osymbols,
+                attach_new_osymbols,
+            )?;
+        });
 
         Ok(())
     })
@@ -206,16 +228,15 @@ pub unsafe fn vec_fst_draw(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get_mut!(CFst, fst_ptr);
-        let vec_fst = as_mut_fst!(VectorFst<TropicalWeight>, fst);
 
         if !isyms.is_null() {
             let isymt = get!(CSymbolTable, isyms);
-            vec_fst.fst_set_input_symbols(isymt.clone());
+            fst.fst_set_input_symbols(isymt.clone());
         }
 
         if !osyms.is_null() {
             let osymt = get!(CSymbolTable, osyms);
-            vec_fst.fst_set_output_symbols(osymt.clone());
+            fst.fst_set_output_symbols(osymt.clone());
         }
 
         let drawing_config = DrawingConfig {
@@ -235,7 +256,10 @@ pub unsafe fn vec_fst_draw(
             print_weight: print_weight > 0,
         };
 
-        vec_fst.draw(unsafe { CStr::from_ptr(fname).as_rust()? }, &drawing_config)?;
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst = as_fst!(VectorFst<W>, fst);
+            vec_fst.draw(unsafe { CStr::from_ptr(fname).as_rust()? }, &drawing_config)?;
+        });
 
         Ok(())
     })
@@ -251,9 +275,11 @@ pub unsafe fn vec_fst_num_states(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get!(CFst, fst);
-        let vec_fst = as_fst!(VectorFst<TropicalWeight>, fst);
-        let res = vec_fst.num_states();
-        unsafe { *num_states = res };
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst = as_fst!(VectorFst<W>, fst);
+            let res = vec_fst.num_states();
+            unsafe { *num_states = res };
+        });
         Ok(())
     })
 }
@@ -270,10 +296,12 @@ pub unsafe fn vec_fst_equals(
     wrap(|| {
         let fst = get!(CFst, fst);
         let other_fst = get!(CFst, other_fst);
-        let vec_fst = as_fst!(VectorFst<TropicalWeight>, fst);
-        let other_vec_fst = as_fst!(VectorFst<TropicalWeight>, other_fst);
-        let res = vec_fst.eq(other_vec_fst);
-        unsafe { *is_equal = res as usize }
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst = as_fst!(VectorFst<W>, fst);
+            let other_vec_fst = as_fst!(VectorFst<W>, other_fst);
+            let res = vec_fst.eq(other_vec_fst);
+            unsafe { *is_equal = res as usize }
+        });
         Ok(())
     })
 }
@@ -288,9 +316,11 @@ pub unsafe extern "C" fn vec_fst_copy(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get!(CFst, fst_ptr);
-        let vec_fst = as_fst!(VectorFst<TropicalWeight>, fst);
-        let clone = vec_fst.clone();
-        unsafe { *clone_ptr = CFst(Box::new(clone)).into_raw_pointer() };
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst = as_fst!(VectorFst<W>, fst);
+            let clone = vec_fst.clone();
+            unsafe { *clone_ptr = CFst(Box::new(clone)).into_raw_pointer() };
+        });
         Ok(())
     })
 }
@@ -305,9 +335,11 @@ pub unsafe extern "C" fn vec_fst_display(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get!(CFst, fst_ptr);
-        let vec_fst = as_fst!(VectorFst<TropicalWeight>, fst);
-        let res = format!("{}", vec_fst);
-        unsafe { *s = CString::c_repr_of(res)?.into_raw_pointer() as *const libc::c_char };
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst = as_fst!(VectorFst<W>, fst);
+            let res = format!("{}", vec_fst);
+            unsafe { *s = CString::c_repr_of(res)?.into_raw_pointer() as *const libc::c_char };
+        });
         Ok(())
     })
 }
@@ -322,15 +354,16 @@ pub unsafe extern "C" fn vec_fst_to_bytes(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get!(CFst, fst_ptr);
-        let vec_fst: &VectorFst<_> = as_fst!(VectorFst<TropicalWeight>, fst);
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst: &VectorFst<_> = as_fst!(VectorFst<W>, fst);
 
-        let mut bytes = vec![];
-        vec_fst.store(&mut bytes)?;
-
-        let c_bytes = CArray::<u8>::c_repr_of(bytes)?;
-        let raw_pointer = c_bytes.into_raw_pointer();
-        unsafe { *output_bytes = raw_pointer };
+            let mut bytes = vec![];
+            vec_fst.store(&mut bytes)?;
 
+            let c_bytes = CArray::<u8>::c_repr_of(bytes)?;
+            let raw_pointer = c_bytes.into_raw_pointer();
+            unsafe { *output_bytes = raw_pointer };
+        });
         Ok(())
     })
 }
@@ -342,12 +375,16 @@ pub unsafe extern "C" fn vec_fst_to_bytes(
 pub unsafe extern "C" fn vec_fst_from_bytes(
     bytes: *const CArray<u8>,
     ptr: *mut *const CFst,
+    weight_type: libc::size_t,
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let bytes = unsafe { CArray::raw_borrow(bytes)? };
         let bytes = bytes.as_rust()?;
-        let fst = VectorFst::load(bytes.as_slice())?;
-        let raw_pointer = CFst(Box::new(fst)).into_raw_pointer();
+        let weight_type = CWeightType::try_from(weight_type)?;
+        let fst: Box<dyn BindableFst> = dispatch_weight!(weight_type, W => {
+            Box::new(VectorFst::<W>::load(bytes.as_slice())?)
+        });
+        let raw_pointer = CFst(fst).into_raw_pointer();
         unsafe { *ptr = raw_pointer };
         Ok(())
     })
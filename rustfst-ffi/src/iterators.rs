@@ -1,23 +1,24 @@
-use crate::fst::{CFst, CVecFst};
+use crate::fst::{as_fst, as_mut_fst, CFst};
 use crate::tr::CTr;
+use crate::trs::BindableTrs;
+use crate::weight::{dispatch_weight, BindableWeight};
 use crate::{get, get_mut, wrap, CStateId, RUSTFST_FFI_RESULT};
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use ffi_convert::*;
 use rustfst::fst_traits::MutableFst;
-use rustfst::prelude::{StateIterator, Tr, TropicalWeight, TrsVec};
+use rustfst::prelude::{StateIterator, VectorFst};
 use rustfst::trs_iter_mut::TrsIterMut;
 use std::iter::Peekable;
 use std::ops::Range;
 
-#[derive(Debug)]
 pub struct TrsIterator {
-    trs: TrsVec<TropicalWeight>,
+    trs: Box<dyn BindableTrs>,
     index: usize,
 }
 
 impl TrsIterator {
     fn done(&self) -> bool {
-        self.trs.len() == self.index
+        self.trs.trs_len() == self.index
     }
 
     fn reset(&mut self) {
@@ -26,9 +27,9 @@ impl TrsIterator {
 }
 
 impl Iterator for TrsIterator {
-    type Item = Tr<TropicalWeight>;
+    type Item = Result<CTr>;
     fn next(&mut self) -> Option<Self::Item> {
-        let item = self.trs.get(self.index).cloned();
+        let item = self.trs.trs_get(self.index).transpose();
         self.index += 1;
         item
     }
@@ -51,7 +52,10 @@ pub unsafe extern "C" fn trs_iterator_new(
         fst.fst_get_trs(state_id)
             .map(|trs| {
                 let raw_ptr = {
-                    let trs_iterator = TrsIterator { trs, index: 0 };
+                    let trs_iterator = TrsIterator {
+                        trs: trs.0,
+                        index: 0,
+                    };
                     CTrsIterator(trs_iterator).into_raw_pointer()
                 };
 
@@ -76,7 +80,7 @@ pub unsafe extern "C" fn trs_iterator_next(
         trs_iter
             .next()
             .map(|tr| {
-                let ctr = Box::into_raw(Box::new(CTr::c_repr_of(tr)?));
+                let ctr = Box::into_raw(Box::new(tr?));
                 unsafe { *tr_ptr = ctr };
                 Ok(())
             })
@@ -132,26 +136,45 @@ pub unsafe extern "C" fn trs_iterator_destroy(iter_ptr: *mut CTrsIterator) -> RU
     })
 }
 
+/// Mutable view over the transitions of a state, whatever the semiring.
+pub trait BindableMutTrs {
+    fn trs_len(&self) -> usize;
+    fn trs_get(&self, index: usize) -> Result<Option<CTr>>;
+    fn trs_set(&mut self, index: usize, tr: &CTr) -> Result<()>;
+}
+
+impl<'a, W: BindableWeight> BindableMutTrs for TrsIterMut<'a, W> {
+    fn trs_len(&self) -> usize {
+        self.len()
+    }
+    fn trs_get(&self, index: usize) -> Result<Option<CTr>> {
+        Ok(self.get(index).cloned().map(CTr::c_repr_of).transpose()?)
+    }
+    fn trs_set(&mut self, index: usize, tr: &CTr) -> Result<()> {
+        self.set_tr(index, tr.as_rust()?)
+    }
+}
+
 pub struct MutTrsIterator<'a> {
-    trs: TrsIterMut<'a, TropicalWeight>,
+    trs: Box<dyn BindableMutTrs + 'a>,
     index: usize,
 }
 
 impl<'a> MutTrsIterator<'a> {
     pub fn done(&self) -> bool {
-        self.trs.len() == self.index
+        self.trs.trs_len() == self.index
     }
 
     pub fn next(&mut self) {
         self.index += 1
     }
 
-    pub fn value(&self) -> Option<Tr<TropicalWeight>> {
-        self.trs.get(self.index).cloned()
+    pub fn value(&self) -> Result<Option<CTr>> {
+        self.trs.trs_get(self.index)
     }
 
-    pub fn set_value(&mut self, tr: Tr<TropicalWeight>) -> Result<()> {
-        self.trs.set_tr(self.index, tr)
+    pub fn set_value(&mut self, tr: &CTr) -> Result<()> {
+        self.trs.trs_set(self.index, tr)
     }
 
     pub fn reset(&mut self) {
@@ -195,22 +218,29 @@ impl<'a> RawPointerConverter<CMutTrsIterator<'a>> for CMutTrsIterator<'a> {
 /// The pointers should be valid.
 #[no_mangle]
 pub unsafe extern "C" fn mut_trs_iterator_new(
-    fst_ptr: *mut CVecFst,
+    fst_ptr: *mut CFst,
     state_id: CStateId,
     mut iter_ptr: *mut *const CMutTrsIterator,
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
-        let fst = get_mut!(CVecFst, fst_ptr);
-        fst.tr_iter_mut(state_id)
-            .map(|trs| {
-                let raw_ptr = {
-                    let trs_iterator = MutTrsIterator { trs, index: 0 };
-                    CMutTrsIterator(trs_iterator).into_raw_pointer()
-                };
-
-                unsafe { *iter_ptr = raw_ptr };
-            })
-            .unwrap_or_else(|_| iter_ptr = std::ptr::null_mut());
+        let fst = get_mut!(CFst, fst_ptr);
+        dispatch_weight!(fst.fst_weight_type(), W => {
+            let vec_fst = as_mut_fst!(VectorFst<W>, fst);
+            vec_fst
+                .tr_iter_mut(state_id)
+                .map(|trs| {
+                    let raw_ptr = {
+                        let trs_iterator = MutTrsIterator {
+                            trs: Box::new(trs),
+                            index: 0,
+                        };
+                        CMutTrsIterator(trs_iterator).into_raw_pointer()
+                    };
+
+                    unsafe { *iter_ptr = raw_ptr };
+                })
+                .unwrap_or_else(|_| iter_ptr = std::ptr::null_mut());
+        });
 
         Ok(())
     })
@@ -241,9 +271,9 @@ pub unsafe extern "C" fn mut_trs_iterator_value(
     wrap(|| {
         let trs_iter = get_mut!(CMutTrsIterator, iter_ptr);
         trs_iter
-            .value()
+            .value()?
             .map(|tr| {
-                let ctr = Box::into_raw(Box::new(CTr::c_repr_of(tr)?));
+                let ctr = Box::into_raw(Box::new(tr));
                 unsafe { *tr_ptr = ctr };
                 Ok(())
             })
@@ -265,7 +295,7 @@ pub unsafe extern "C" fn mut_trs_iterator_set_value(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let trs_iter = get_mut!(CMutTrsIterator, iter_ptr);
-        let tr = unsafe { <CTr as ffi_convert::RawBorrow<CTr>>::raw_borrow(tr_ptr)? }.as_rust()?;
+        let tr = unsafe { <CTr as ffi_convert::RawBorrow<CTr>>::raw_borrow(tr_ptr)? };
         trs_iter.set_value(tr)?;
         Ok(())
     })
@@ -326,12 +356,14 @@ pub struct CStateIterator(pub(crate) Peekable<Range<CStateId>>);
 /// The pointers should be valid.
 #[no_mangle]
 pub unsafe extern "C" fn state_iterator_new(
-    fst_ptr: *mut CVecFst,
+    fst_ptr: *mut CFst,
     iter_ptr: *mut *const CStateIterator,
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
-        let fst = get!(CVecFst, fst_ptr);
-        let state_iter = fst.states_iter().peekable();
+        let fst = get!(CFst, fst_ptr);
+        let state_iter = dispatch_weight!(fst.fst_weight_type(), W => {
+            as_fst!(VectorFst<W>, fst).states_iter().peekable()
+        });
         let raw_ptr = CStateIterator(state_iter).into_raw_pointer();
         unsafe { *iter_ptr = raw_ptr };
         Ok(())
@@ -8,6 +8,7 @@ pub mod string_paths_iterator;
 pub mod symbol_table;
 pub mod tr;
 pub mod trs;
+pub mod weight;
 
 use std::cell::RefCell;
 use std::ffi::CString;
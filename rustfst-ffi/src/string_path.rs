@@ -1,13 +1,33 @@
+use anyhow::Result;
 use ffi_convert::{CReprOf, RawPointerConverter};
 use std::ffi::CString;
 
-use rustfst::semirings::TropicalWeight;
-use rustfst::{Semiring, StringPath};
+use rustfst::StringPath;
 
+use crate::weight::BindableWeight;
 use crate::{get, wrap, RUSTFST_FFI_RESULT};
 
+/// String path of any of the supported semirings.
+pub trait BindableStringPath {
+    fn path_weight(&self) -> libc::c_float;
+    fn path_istring(&self) -> Result<String>;
+    fn path_ostring(&self) -> Result<String>;
+}
+
+impl<W: BindableWeight> BindableStringPath for StringPath<W> {
+    fn path_weight(&self) -> libc::c_float {
+        self.weight().to_c_float()
+    }
+    fn path_istring(&self) -> Result<String> {
+        self.istring()
+    }
+    fn path_ostring(&self) -> Result<String> {
+        self.ostring()
+    }
+}
+
 #[derive(RawPointerConverter)]
-pub struct CStringPath(pub(crate) StringPath<TropicalWeight>);
+pub struct CStringPath(pub(crate) Box<dyn BindableStringPath>);
 
 /// # Safety
 ///
@@ -34,7 +54,7 @@ pub unsafe extern "C" fn string_path_weight(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let string_path = get!(CStringPath, c_string_path);
-        let weight_val = *string_path.weight().value();
+        let weight_val = string_path.path_weight();
         unsafe { *weight = weight_val }
         Ok(())
     })
@@ -50,7 +70,7 @@ pub unsafe extern "C" fn string_path_istring(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let string_path = get!(CStringPath, c_string_path);
-        let istring = string_path.istring()?;
+        let istring = string_path.path_istring()?;
         unsafe {
             *c_istring = CString::c_repr_of(istring)?.into_raw_pointer() as *const libc::c_char
         }
@@ -69,7 +89,7 @@ pub unsafe extern "C" fn string_path_ostring(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let string_path = get!(CStringPath, c_string_path);
-        let ostring = string_path.ostring()?;
+        let ostring = string_path.path_ostring()?;
         unsafe {
             *c_ostring = CString::c_repr_of(ostring)?.into_raw_pointer() as *const libc::c_char
         }
@@ -4,16 +4,16 @@ use anyhow::{anyhow, Result};
 use ffi_convert::{RawPointerConverter, UnexpectedNullPointerError};
 
 use rustfst::fst_impls::VectorFst;
-use rustfst::prelude::{Fst, StringPathsIterator};
-use rustfst::semirings::TropicalWeight;
+use rustfst::prelude::Fst;
 
 use crate::fst::as_fst;
 use crate::fst::CFst;
 use crate::string_path::CStringPath;
+use crate::weight::dispatch_weight;
 use crate::{get, get_mut, wrap, RUSTFST_FFI_RESULT};
 
 pub struct CStringPathsIterator<'a>(
-    pub(crate) Peekable<StringPathsIterator<'a, TropicalWeight, VectorFst<TropicalWeight>>>,
+    pub(crate) Peekable<Box<dyn Iterator<Item = CStringPath> + 'a>>,
 );
 
 impl<'a> RawPointerConverter<CStringPathsIterator<'a>> for CStringPathsIterator<'a> {
@@ -55,9 +55,14 @@ pub unsafe extern "C" fn string_paths_iterator_new(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let fst = get!(CFst, fst);
-        let fst: &VectorFst<_> = as_fst!(VectorFst<TropicalWeight>, fst);
-        let it = fst.string_paths_iter()?.peekable();
-        let raw_pointer = CStringPathsIterator(it).into_raw_pointer();
+        let it: Box<dyn Iterator<Item = CStringPath>> = dispatch_weight!(fst.fst_weight_type(), W => {
+            let fst: &VectorFst<_> = as_fst!(VectorFst<W>, fst);
+            Box::new(
+                fst.string_paths_iter()?
+                    .map(|string_path| CStringPath(Box::new(string_path))),
+            )
+        });
+        let raw_pointer = CStringPathsIterator(it.peekable()).into_raw_pointer();
         unsafe { *res_iterator = raw_pointer };
         Ok(())
     })
@@ -76,7 +81,7 @@ pub unsafe extern "C" fn string_paths_iterator_next(
         string_paths_iter
             .next()
             .map(|string_path| {
-                let ctr = CStringPath(string_path).into_raw_pointer();
+                let ctr = string_path.into_raw_pointer();
                 unsafe { *string_path_ptr = ctr };
                 Ok(())
             })
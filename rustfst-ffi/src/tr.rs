@@ -1,43 +1,44 @@
+use crate::weight::BindableWeight;
 use crate::{wrap, CLabel, CStateId, RUSTFST_FFI_RESULT};
 
 use ffi_convert::*;
 use rustfst::prelude::{StateId, Tr};
-use rustfst::semirings::TropicalWeight;
-use rustfst::Semiring;
 
+/// C representation of a transition. The weight is stored as a float so that the same
+/// struct can be used whatever the semiring of the FST it comes from or goes to.
 #[derive(Debug)]
 #[repr(C)]
-#[derive(CReprOf, AsRust, CDrop, RawPointerConverter)]
-#[target_type(Tr::<TropicalWeight>)]
+#[derive(CDrop, RawPointerConverter)]
 pub struct CTr {
     /// Input label.
     pub ilabel: CLabel,
     /// Output label.
     pub olabel: CLabel,
     /// Weight.
-    pub weight: CTropicalWeight,
+    pub weight: libc::c_float,
     /// ID of the next state.
     pub nextstate: CStateId,
 }
 
-#[derive(Debug)]
-#[repr(C)]
-#[derive(CDrop, RawPointerConverter)]
-pub struct CTropicalWeight {
-    value: libc::c_float,
-}
-
-impl CReprOf<TropicalWeight> for CTropicalWeight {
-    fn c_repr_of(input: TropicalWeight) -> Result<Self, CReprOfError> {
+impl<W: BindableWeight> CReprOf<Tr<W>> for CTr {
+    fn c_repr_of(input: Tr<W>) -> Result<Self, CReprOfError> {
         Ok(Self {
-            value: input.take_value(),
+            ilabel: input.ilabel,
+            olabel: input.olabel,
+            weight: input.weight.to_c_float(),
+            nextstate: input.nextstate,
         })
     }
 }
 
-impl AsRust<TropicalWeight> for CTropicalWeight {
-    fn as_rust(&self) -> Result<TropicalWeight, AsRustError> {
-        Ok(self.value.into())
+impl<W: BindableWeight> AsRust<Tr<W>> for CTr {
+    fn as_rust(&self) -> Result<Tr<W>, AsRustError> {
+        Ok(Tr::new(
+            self.ilabel,
+            self.olabel,
+            W::from_c_float(self.weight),
+            self.nextstate,
+        ))
     }
 }
 
@@ -56,7 +57,7 @@ pub unsafe extern "C" fn tr_new(
         let tr = CTr {
             ilabel,
             olabel,
-            weight: CTropicalWeight { value: weight },
+            weight,
             nextstate,
         };
         let raw_pointer: *mut CTr = Box::into_raw(Box::new(tr));
@@ -125,7 +126,7 @@ pub unsafe extern "C" fn tr_weight(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let tr = unsafe { <CTr as ffi_convert::RawBorrow<CTr>>::raw_borrow(tr)? };
-        let weight_val = *tr.weight.as_rust()?.value();
+        let weight_val = tr.weight;
         unsafe { *weight = weight_val }
         Ok(())
     })
@@ -138,8 +139,7 @@ pub unsafe extern "C" fn tr_weight(
 pub unsafe extern "C" fn tr_set_weight(tr: *mut CTr, weight: libc::c_float) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let tr = &mut unsafe { <CTr as ffi_convert::RawBorrowMut<CTr>>::raw_borrow_mut(tr)? };
-        let tropical_weight = TropicalWeight::new(weight);
-        tr.weight = CTropicalWeight::c_repr_of(tropical_weight)?;
+        tr.weight = weight;
         Ok(())
     })
 }
@@ -1,23 +1,61 @@
 use crate::tr::CTr;
+use crate::weight::{dispatch_weight, BindableWeight, CWeightType};
 use crate::{get, get_mut, wrap, RUSTFST_FFI_RESULT};
+use std::convert::TryFrom;
 use std::ffi::CString;
 
 use anyhow::Result;
 use ffi_convert::*;
 use rustfst::prelude::TrsVec;
-use rustfst::semirings::TropicalWeight;
 use rustfst::Trs;
 
+/// List of transitions of any of the supported semirings.
+pub trait BindableTrs {
+    fn trs_len(&self) -> usize;
+    fn trs_get(&self, index: usize) -> Result<Option<CTr>>;
+    fn trs_remove(&mut self, index: usize) -> Result<CTr>;
+    fn trs_push(&mut self, tr: &CTr) -> Result<()>;
+    fn trs_shallow_clone(&self) -> Box<dyn BindableTrs>;
+    fn trs_display(&self) -> String;
+}
+
+impl<W: BindableWeight> BindableTrs for TrsVec<W> {
+    fn trs_len(&self) -> usize {
+        self.len()
+    }
+    fn trs_get(&self, index: usize) -> Result<Option<CTr>> {
+        Ok(self.get(index).cloned().map(CTr::c_repr_of).transpose()?)
+    }
+    fn trs_remove(&mut self, index: usize) -> Result<CTr> {
+        Ok(CTr::c_repr_of(self.remove(index))?)
+    }
+    fn trs_push(&mut self, tr: &CTr) -> Result<()> {
+        self.push(tr.as_rust()?);
+        Ok(())
+    }
+    fn trs_shallow_clone(&self) -> Box<dyn BindableTrs> {
+        Box::new(self.shallow_clone())
+    }
+    fn trs_display(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
 #[derive(RawPointerConverter)]
-pub struct CTrs(pub(crate) TrsVec<TropicalWeight>);
+pub struct CTrs(pub(crate) Box<dyn BindableTrs>);
 
 /// # Safety
 ///
 /// The pointers should be valid.
 #[no_mangle]
-pub unsafe extern "C" fn trs_vec_new(new_struct: *mut *const CTrs) -> RUSTFST_FFI_RESULT {
+pub unsafe extern "C" fn trs_vec_new(
+    new_struct: *mut *const CTrs,
+    weight_type: libc::size_t,
+) -> RUSTFST_FFI_RESULT {
     wrap(|| {
-        let trs: TrsVec<TropicalWeight> = TrsVec::default();
+        let weight_type = CWeightType::try_from(weight_type)?;
+        let trs: Box<dyn BindableTrs> =
+            dispatch_weight!(weight_type, W => Box::new(TrsVec::<W>::default()));
         let raw_pointer = CTrs(trs).into_raw_pointer();
         unsafe { *new_struct = raw_pointer };
         Ok(())
@@ -35,8 +73,8 @@ pub unsafe extern "C" fn trs_vec_remove(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let trs = get_mut!(CTrs, trs);
-        let removed_tr = trs.remove(index);
-        let ctr = Box::into_raw(Box::new(CTr::c_repr_of(removed_tr)?));
+        let removed_tr = trs.trs_remove(index)?;
+        let ctr = Box::into_raw(Box::new(removed_tr));
         unsafe { *removed_tr_ptr = ctr };
         Ok(())
     })
@@ -50,7 +88,7 @@ pub unsafe extern "C" fn trs_vec_push(trs: *mut CTrs, new_tr: *const CTr) -> RUS
     wrap(|| {
         let trs = get_mut!(CTrs, trs);
         let tr = unsafe { <CTr as ffi_convert::RawBorrow<CTr>>::raw_borrow(new_tr)? };
-        trs.push(tr.as_rust()?);
+        trs.trs_push(tr)?;
         Ok(())
     })
 }
@@ -65,7 +103,7 @@ pub unsafe extern "C" fn trs_vec_shallow_clone(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let trs = get!(CTrs, trs);
-        let cloned_trs = trs.shallow_clone();
+        let cloned_trs = trs.trs_shallow_clone();
         let raw_pointer = CTrs(cloned_trs).into_raw_pointer();
         unsafe { *cloned_trs_ptr = raw_pointer };
         Ok(())
@@ -82,7 +120,7 @@ pub unsafe extern "C" fn trs_vec_len(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let trs = get!(CTrs, trs);
-        unsafe { *num_trs = trs.len() as libc::size_t };
+        unsafe { *num_trs = trs.trs_len() as libc::size_t };
         Ok(())
     })
 }
@@ -97,7 +135,7 @@ pub unsafe extern "C" fn trs_vec_display(
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let trs = get!(CTrs, trs);
-        let trs_display = format!("{:?}", trs);
+        let trs_display = trs.trs_display();
         unsafe {
             *string = CString::c_repr_of(trs_display)?.into_raw_pointer() as *const libc::c_char
         };
@@ -0,0 +1,114 @@
+use std::convert::TryFrom;
+
+use rustfst::semirings::{BooleanWeight, LogWeight, TropicalWeight};
+use rustfst::Semiring;
+
+use crate::algorithms::EnumConversionError;
+
+/// Semiring of the weights stored in a FST handle.
+/// The discriminants are the values expected on the C side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CWeightType {
+    Tropical = 0,
+    Log = 1,
+    Boolean = 2,
+}
+
+impl TryFrom<libc::size_t> for CWeightType {
+    type Error = EnumConversionError;
+
+    fn try_from(value: libc::size_t) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(CWeightType::Tropical),
+            1 => Ok(CWeightType::Log),
+            2 => Ok(CWeightType::Boolean),
+            _ => Err(EnumConversionError {}),
+        }
+    }
+}
+
+/// Semirings that can be exposed through the bindings.
+/// Weights cross the FFI boundary as floats whatever the semiring.
+pub trait BindableWeight: Semiring + 'static {
+    const WEIGHT_TYPE: CWeightType;
+    fn from_c_float(value: libc::c_float) -> Self;
+    fn to_c_float(&self) -> libc::c_float;
+}
+
+impl BindableWeight for TropicalWeight {
+    const WEIGHT_TYPE: CWeightType = CWeightType::Tropical;
+    fn from_c_float(value: libc::c_float) -> Self {
+        TropicalWeight::new(value)
+    }
+    fn to_c_float(&self) -> libc::c_float {
+        *self.value()
+    }
+}
+
+impl BindableWeight for LogWeight {
+    const WEIGHT_TYPE: CWeightType = CWeightType::Log;
+    fn from_c_float(value: libc::c_float) -> Self {
+        LogWeight::new(value)
+    }
+    fn to_c_float(&self) -> libc::c_float {
+        *self.value()
+    }
+}
+
+/// `true` is mapped to `1.0` and `false` to `0.0`. Any non-zero float is `true`.
+impl BindableWeight for BooleanWeight {
+    const WEIGHT_TYPE: CWeightType = CWeightType::Boolean;
+    fn from_c_float(value: libc::c_float) -> Self {
+        BooleanWeight::new(value != 0.0)
+    }
+    fn to_c_float(&self) -> libc::c_float {
+        if *self.value() {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Evaluates `$body` with `$w` aliased to the semiring matching `$weight_type`.
+macro_rules! dispatch_weight {
+    ($weight_type:expr, $w:ident => $body:expr) => {{
+        match $weight_type {
+            $crate::weight::CWeightType::Tropical => {
+                type $w = rustfst::semirings::TropicalWeight;
+                $body
+            }
+            $crate::weight::CWeightType::Log => {
+                type $w = rustfst::semirings::LogWeight;
+                $body
+            }
+            $crate::weight::CWeightType::Boolean => {
+                type $w = rustfst::semirings::BooleanWeight;
+                $body
+            }
+        }
+    }};
+}
+
+/// Same as `dispatch_weight!` but restricted to the float semirings.
+/// Needed by the operations sampling with float probabilities or converting to the log semiring.
+macro_rules! dispatch_float_weight {
+    ($weight_type:expr, $w:ident => $body:expr) => {{
+        match $weight_type {
+            $crate::weight::CWeightType::Tropical => {
+                type $w = rustfst::semirings::TropicalWeight;
+                $body
+            }
+            $crate::weight::CWeightType::Log => {
+                type $w = rustfst::semirings::LogWeight;
+                $body
+            }
+            weight_type => {
+                anyhow::bail!("Operation not supported for {:?} weights", weight_type)
+            }
+        }
+    }};
+}
+
+pub(crate) use dispatch_float_weight;
+pub(crate) use dispatch_weight;
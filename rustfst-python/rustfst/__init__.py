@@ -2,7 +2,8 @@ from .tr import Tr
 from .trs import Trs
 from .symbol_table import SymbolTable
 from .fst import Fst
-from .fst.vector_fst import VectorFst
-from .fst.const_fst import ConstFst
+from .fst.vector_fst import VectorFst, LogVectorFst, BooleanVectorFst
+from .fst.const_fst import ConstFst, LogConstFst, BooleanConstFst
+from .weight import WeightType
 from .iterators import TrsIterator, MutableTrsIterator, StateIterator
 from .drawing_config import DrawingConfig
@@ -1,6 +1,6 @@
 from __future__ import annotations
 
-from typing import Optional
+from typing import Optional, Type
 
 from rustfst.weight import weight_one
 from rustfst.fst.vector_fst import VectorFst
@@ -14,7 +14,10 @@ from rustfst.ffi_utils import (
 
 
 def acceptor(
-    astring: str, symbol_table: SymbolTable, weight: Optional[float] = None
+    astring: str,
+    symbol_table: SymbolTable,
+    weight: Optional[float] = None,
+    fst_type: Type[VectorFst] = VectorFst,
 ) -> VectorFst:
     """
     Creates an acceptor from a string.
@@ -25,11 +28,13 @@ def acceptor(
       weight: A Weight or weight string indicating the desired path weight. If
         omitted or null, the path weight is set to semiring One.
       symbol_table: SymbolTable to be used to encode the string.
+      fst_type: Class of the returned FST (e.g `LogVectorFst`), defines the semiring.
     Returns:
       An FST acceptor.
     """
+    weight_type = fst_type._weight_type
     if weight is None:
-        weight = weight_one()
+        weight = weight_one(weight_type)
 
     acceptor_fst_ptr = ctypes.pointer(ctypes.c_void_p())
     ret_code = lib.utils_string_to_acceptor(
@@ -37,10 +42,11 @@ def acceptor(
         symbol_table.ptr,
         ctypes.c_float(weight),
         ctypes.byref(acceptor_fst_ptr),
+        ctypes.c_size_t(weight_type.value),
     )
     err_msg = "Error creating acceptor FST"
     check_ffi_error(ret_code, err_msg)
-    return VectorFst(ptr=acceptor_fst_ptr)
+    return fst_type(ptr=acceptor_fst_ptr)
 
 
 def transducer(
@@ -49,6 +55,7 @@ def transducer(
     isymt: SymbolTable,
     osymt: SymbolTable,
     weight: Optional[float] = None,
+    fst_type: Type[VectorFst] = VectorFst,
 ) -> VectorFst:
     """
     Creates a transducer from a pair of strings or acceptor FSTs.
@@ -60,12 +67,13 @@ def transducer(
       weight: A Weight as float.
       isymt: SymbolTable to be used to encode the string.
       osymt: SymbolTable to be used to encode the string.
+      fst_type: Class of the returned FST (e.g `LogVectorFst`), defines the semiring.
     Returns:
       An FST transducer.
     """
-
+    weight_type = fst_type._weight_type
     if weight is None:
-        weight = weight_one()
+        weight = weight_one(weight_type)
 
     transducer_fst_ptr = ctypes.c_void_p()
     ret_code = lib.utils_string_to_transducer(
@@ -75,26 +83,30 @@ def transducer(
         osymt.ptr,
         ctypes.c_float(weight),
         ctypes.byref(transducer_fst_ptr),
+        ctypes.c_size_t(weight_type.value),
     )
     err_msg = "Error creating tranducer FST"
     check_ffi_error(ret_code, err_msg)
 
-    return VectorFst(ptr=transducer_fst_ptr)
+    return fst_type(ptr=transducer_fst_ptr)
 
 
-def epsilon_machine(weight: Optional[float] = None) -> VectorFst:
+def epsilon_machine(
+    weight: Optional[float] = None, fst_type: Type[VectorFst] = VectorFst
+) -> VectorFst:
     """
     Constructs a single-state, no-arc FST accepting epsilon.
     This function creates an unweighted FST with a single state which is both
     initial and final.
     Args:
       weight: A Weight. Default semiring One.
+      fst_type: Class of the returned FST (e.g `LogVectorFst`), defines the semiring.
     Returns:
       An FST.
     """
     if weight is None:
-        weight = weight_one()
-    fst = VectorFst()
+        weight = weight_one(fst_type._weight_type)
+    fst = fst_type()
     state = fst.add_state()
     fst.set_start(state)
     fst.set_final(state, weight)
@@ -0,0 +1,49 @@
+from __future__ import annotations
+import ctypes
+from enum import Enum
+
+from rustfst.ffi_utils import (
+    lib,
+    check_ffi_error,
+)
+
+from rustfst.fst.vector_fst import VectorFst
+
+
+class ClosureType(Enum):
+    """
+    Different types of closure.
+    """
+
+    CLOSURE_STAR = 0
+    """
+    Kleene star : the empty string is accepted.
+    """
+    CLOSURE_PLUS = 1
+    """
+    Kleene plus : at least one repetition is required.
+    """
+
+
+def closure(
+    fst: VectorFst, closure_type: ClosureType = ClosureType.CLOSURE_STAR
+) -> VectorFst:
+    """
+    Compute the concatenative closure of the Fst in-place.
+    If the Fst transduces string x to y with weight a, then the closure transduces
+    x to y with weight a, xx to yy with weight a ⊗ a, xxx to yyy with weight
+    a ⊗ a ⊗ a, etc. With `CLOSURE_STAR`, the empty string is also accepted
+    with weight one.
+
+    Args:
+        fst: Fst on which to apply the algorithm.
+        closure_type: Star or Plus closure.
+    Returns:
+        self
+    """
+
+    ret_code = lib.fst_closure(fst.ptr, ctypes.c_size_t(closure_type.value))
+    err_msg = "Error during closure"
+    check_ffi_error(ret_code, err_msg)
+
+    return fst
@@ -111,7 +111,7 @@ def compose(fst: VectorFst, other_fst: VectorFst) -> VectorFst:
     err_msg = "Error Composing FSTs"
     check_ffi_error(ret_code, err_msg)
 
-    return VectorFst(ptr=composition)
+    return type(fst)(ptr=composition)
 
 
 def compose_with_config(
@@ -134,4 +134,4 @@ def compose_with_config(
     err_msg = "Error Composing FSTs"
     check_ffi_error(ret_code, err_msg)
 
-    return VectorFst(ptr=composition)
+    return type(fst)(ptr=composition)
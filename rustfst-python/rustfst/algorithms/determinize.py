@@ -72,7 +72,7 @@ def determinize(fst: VectorFst) -> VectorFst:
     err_msg = "Error during determinization"
     check_ffi_error(ret_code, err_msg)
 
-    return VectorFst(ptr=det_fst)
+    return type(fst)(ptr=det_fst)
 
 
 def determinize_with_config(fst: VectorFst, config: DeterminizeConfig) -> VectorFst:
@@ -91,4 +91,4 @@ def determinize_with_config(fst: VectorFst, config: DeterminizeConfig) -> Vector
     err_msg = "Error during determinization"
     check_ffi_error(ret_code, err_msg)
 
-    return VectorFst(ptr=det_fst)
+    return type(fst)(ptr=det_fst)
@@ -0,0 +1,69 @@
+from __future__ import annotations
+import ctypes
+
+from rustfst.ffi_utils import (
+    lib,
+    check_ffi_error,
+)
+
+from rustfst.fst.vector_fst import VectorFst
+
+
+class EncodeTable:
+    """
+    Mapping between the encoded labels and the original (ilabel, olabel, weight) tuples.
+    Returned by `encode` and needed by `decode` to revert the encoding.
+    """
+
+    def __init__(self, ptr):
+        self.ptr = ptr
+
+    def __del__(self):
+        lib.encode_table_destroy(self.ptr)
+
+
+def encode(
+    fst: VectorFst, encode_labels: bool = True, encode_weights: bool = False
+) -> EncodeTable:
+    """
+    Encode the Fst in-place as an acceptor (when `encode_labels` is set) and/or as an
+    unweighted Fst (when `encode_weights` is set). Each pair or triple of labels and
+    weights is replaced by a single label.
+
+    Args:
+        fst: Fst to encode.
+        encode_labels: Whether to encode the (ilabel, olabel) pairs.
+        encode_weights: Whether to encode the weights.
+    Returns:
+        The table to use to decode the Fst.
+    """
+
+    table = ctypes.pointer(ctypes.c_void_p())
+    ret_code = lib.fst_encode(
+        fst.ptr,
+        ctypes.c_bool(encode_labels),
+        ctypes.c_bool(encode_weights),
+        ctypes.byref(table),
+    )
+    err_msg = "Error during encode"
+    check_ffi_error(ret_code, err_msg)
+
+    return EncodeTable(table)
+
+
+def decode(fst: VectorFst, encode_table: EncodeTable) -> VectorFst:
+    """
+    Revert in-place the encoding performed by `encode`.
+
+    Args:
+        fst: Encoded Fst.
+        encode_table: Table returned by `encode`.
+    Returns:
+        self
+    """
+
+    ret_code = lib.fst_decode(fst.ptr, encode_table.ptr)
+    err_msg = "Error during decode"
+    check_ffi_error(ret_code, err_msg)
+
+    return fst
@@ -0,0 +1,22 @@
+from __future__ import annotations
+from rustfst.ffi_utils import (
+    lib,
+    check_ffi_error,
+)
+
+from rustfst.fst.vector_fst import VectorFst
+
+
+def invert(fst: VectorFst) -> VectorFst:
+    """
+    Swap the input and output labels of every transition of the Fst.
+
+    Returns :
+        self
+    """
+
+    ret_code = lib.fst_invert(fst.ptr)
+    err_msg = "Error during invert"
+    check_ffi_error(ret_code, err_msg)
+
+    return fst
@@ -0,0 +1,105 @@
+from __future__ import annotations
+import ctypes
+from enum import Enum
+
+from rustfst.ffi_utils import (
+    lib,
+    check_ffi_error,
+)
+
+from rustfst.fst.vector_fst import VectorFst
+
+KDELTA = 1.0 / 1024.0
+
+
+class ReweightType(Enum):
+    """
+    Direction in which the weights are pushed.
+    """
+
+    REWEIGHT_TO_INITIAL = 0
+    """
+    Push the weights towards the initial state.
+    """
+    REWEIGHT_TO_FINAL = 1
+    """
+    Push the weights towards the final states.
+    """
+
+
+class PushType:
+    """
+    Flags controlling what is pushed by `push`. They can be combined with `|`.
+    """
+
+    PUSH_WEIGHTS = 0b1
+    PUSH_LABELS = 0b10
+    REMOVE_TOTAL_WEIGHT = 0b100
+    REMOVE_COMMON_AFFIX = 0b1000
+
+
+def push_weights(
+    fst: VectorFst,
+    reweight_type: ReweightType,
+    remove_total_weight: bool = False,
+    delta: float = KDELTA,
+) -> VectorFst:
+    """
+    Push the weights of the Fst in-place.
+    If pushing towards the initial state, the sum of the weight of the
+    outgoing transitions and final weight at a non-initial state is
+    equal to one in the resulting machine. If pushing towards the
+    final state, the same property holds on the reverse machine.
+
+    Args:
+        fst: Fst on which to apply the algorithm.
+        reweight_type: Whether to push towards the initial or the final states.
+        remove_total_weight: Whether to remove the total weight of the Fst.
+        delta: Comparison and quantization delta.
+    Returns:
+        self
+    """
+
+    ret_code = lib.fst_push_weights(
+        fst.ptr,
+        ctypes.c_size_t(reweight_type.value),
+        ctypes.c_float(delta),
+        ctypes.c_bool(remove_total_weight),
+    )
+    err_msg = "Error during push_weights"
+    check_ffi_error(ret_code, err_msg)
+
+    return fst
+
+
+def push(
+    fst: VectorFst,
+    reweight_type: ReweightType,
+    push_type: int = PushType.PUSH_WEIGHTS,
+    delta: float = KDELTA,
+) -> VectorFst:
+    """
+    Push the weights and/or labels of the Fst towards the initial state or the final
+    states.
+
+    Args:
+        fst: Fst on which to apply the algorithm.
+        reweight_type: Whether to push towards the initial or the final states.
+        push_type: Combination of `PushType` flags.
+        delta: Comparison and quantization delta.
+    Returns:
+        The resulting Fst.
+    """
+
+    pushed_fst = ctypes.c_void_p()
+    ret_code = lib.fst_push(
+        fst.ptr,
+        ctypes.c_size_t(reweight_type.value),
+        ctypes.c_uint(push_type),
+        ctypes.c_float(delta),
+        ctypes.byref(pushed_fst),
+    )
+    err_msg = "Error during push"
+    check_ffi_error(ret_code, err_msg)
+
+    return type(fst)(ptr=pushed_fst)
@@ -69,4 +69,4 @@ def randgen(
     err_msg = "Error during randgen"
     check_ffi_error(ret_code, err_msg)
 
-    return VectorFst(ptr=randgen_fst)
+    return type(ifst)(ptr=randgen_fst)
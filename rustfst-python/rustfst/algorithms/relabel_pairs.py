@@ -0,0 +1,54 @@
+from __future__ import annotations
+import ctypes
+from typing import List, Optional, Tuple
+
+from rustfst.ffi_utils import (
+    lib,
+    check_ffi_error,
+)
+
+from rustfst.fst.vector_fst import VectorFst
+
+
+class LabelPair(ctypes.Structure):
+    _fields_ = [
+        ("old_label", ctypes.c_uint32),
+        ("new_label", ctypes.c_uint32),
+    ]
+
+
+def _label_pairs_array(pairs: Optional[List[Tuple[int, int]]]):
+    pairs = pairs or []
+    return (LabelPair * len(pairs))(*[LabelPair(old, new) for (old, new) in pairs])
+
+
+def relabel_pairs(
+    fst: VectorFst,
+    ipairs: Optional[List[Tuple[int, int]]] = None,
+    opairs: Optional[List[Tuple[int, int]]] = None,
+) -> VectorFst:
+    """
+    Replace in-place the input and/or output labels using pairs of labels.
+    Labels missing from the pairs are left unchanged.
+
+    Args:
+        fst: Fst on which to apply the algorithm.
+        ipairs: List of (old_label, new_label) for the input labels.
+        opairs: List of (old_label, new_label) for the output labels.
+    Returns:
+        self
+    """
+
+    ipairs_array = _label_pairs_array(ipairs)
+    opairs_array = _label_pairs_array(opairs)
+    ret_code = lib.fst_relabel_pairs(
+        fst.ptr,
+        ipairs_array,
+        ctypes.c_size_t(len(ipairs_array)),
+        opairs_array,
+        ctypes.c_size_t(len(opairs_array)),
+    )
+    err_msg = "Error during relabel_pairs"
+    check_ffi_error(ret_code, err_msg)
+
+    return fst
@@ -77,4 +77,4 @@ def replace(
     err_msg = "Error performing replace"
     check_ffi_error(ret_code, err_msg)
 
-    return VectorFst(ptr=res_fst)
+    return type(fst_list[0][1])(ptr=res_fst)
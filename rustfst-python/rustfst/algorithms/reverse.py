@@ -21,4 +21,4 @@ def reverse(fst: VectorFst):
     err_msg = "Error during reverse"
     check_ffi_error(ret_code, err_msg)
 
-    return VectorFst(ptr=reversed_fst)
+    return type(fst)(ptr=reversed_fst)
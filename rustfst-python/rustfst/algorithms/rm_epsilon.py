@@ -21,4 +21,4 @@ def rm_epsilon(fst: VectorFst):
     err_msg = "Error during rm_epsilon"
     check_ffi_error(ret_code, err_msg)
 
-    return VectorFst(ptr=rm_epsilon_fst)
+    return type(fst)(ptr=rm_epsilon_fst)
@@ -0,0 +1,46 @@
+from __future__ import annotations
+import ctypes
+from typing import List
+
+from rustfst.ffi_utils import (
+    lib,
+    check_ffi_error,
+)
+
+from rustfst.fst.vector_fst import VectorFst
+
+KSHORTESTDELTA = 1e-6
+
+
+class FloatArray(ctypes.Structure):
+    _fields_ = [("data_ptr", ctypes.POINTER(ctypes.c_float)), ("size", ctypes.c_size_t)]
+
+
+def shortest_distance(
+    fst: VectorFst, reverse: bool = False, delta: float = KSHORTESTDELTA
+) -> List[float]:
+    """
+    Compute the shortest distance from the initial state to every state.
+    The shortest distance from `p` to `q` is the ⊕-sum of the weights
+    of all the paths between `p` and `q`.
+
+    Args:
+        fst: Fst on which to compute the distances.
+        reverse: If true, compute the shortest distance from every state to the final
+            states.
+        delta: Convergence delta.
+    Returns:
+        The distances, indexed by state id.
+    """
+
+    distances = ctypes.pointer(FloatArray())
+    ret_code = lib.fst_shortest_distance(
+        fst.ptr,
+        ctypes.c_bool(reverse),
+        ctypes.c_float(delta),
+        ctypes.byref(distances),
+    )
+    err_msg = "Error computing shortest distance"
+    check_ffi_error(ret_code, err_msg)
+
+    return [distances.contents.data_ptr[i] for i in range(distances.contents.size)]
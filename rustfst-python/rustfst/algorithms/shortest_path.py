@@ -39,7 +39,7 @@ def shortestpath(fst: VectorFst) -> VectorFst:
     err_msg = "Error computing shortest path"
     check_ffi_error(ret_code, err_msg)
 
-    return VectorFst(ptr=shortest_path)
+    return type(fst)(ptr=shortest_path)
 
 
 def shortestpath_with_config(fst: VectorFst, config: ShortestPathConfig) -> VectorFst:
@@ -59,4 +59,4 @@ def shortestpath_with_config(fst: VectorFst, config: ShortestPathConfig) -> Vect
     err_msg = "Error computing shortest path"
     check_ffi_error(ret_code, err_msg)
 
-    return VectorFst(ptr=shortest_path)
+    return type(fst)(ptr=shortest_path)
@@ -0,0 +1,30 @@
+from __future__ import annotations
+import ctypes
+from typing import List
+
+from rustfst.ffi_utils import (
+    lib,
+    check_ffi_error,
+)
+
+from rustfst.fst.vector_fst import VectorFst
+
+
+def state_sort(fst: VectorFst, order: List[int]) -> VectorFst:
+    """
+    Sort the states of the Fst in-place.
+
+    Args:
+        fst: Fst on which to apply the algorithm.
+        order: `order[i]` is the state id after sorting of the state `i`. It must be a
+            permutation of the state ids of the Fst.
+    Returns:
+        self
+    """
+
+    order_array = (ctypes.c_uint32 * len(order))(*order)
+    ret_code = lib.fst_state_sort(fst.ptr, order_array, ctypes.c_size_t(len(order)))
+    err_msg = "Error during state_sort"
+    check_ffi_error(ret_code, err_msg)
+
+    return fst
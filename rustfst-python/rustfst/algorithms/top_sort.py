@@ -33,4 +33,4 @@ def top_sort(fst: VectorFst) -> VectorFst:
     err_msg = "Error during top_sort"
     check_ffi_error(ret_code, err_msg)
 
-    return VectorFst(ptr=top_sorted_fst)
+    return type(fst)(ptr=top_sorted_fst)
@@ -0,0 +1,92 @@
+from __future__ import annotations
+import ctypes
+from enum import Enum
+from typing import Optional
+
+from rustfst.ffi_utils import (
+    lib,
+    check_ffi_error,
+)
+
+from rustfst.fst.vector_fst import VectorFst
+
+KDELTA = 1.0 / 1024.0
+
+
+class TrMapperType(Enum):
+    """
+    Mappers available to `tr_map`.
+    """
+
+    IDENTITY = 0
+    """
+    Leave the transitions unchanged.
+    """
+    INPUT_EPSILON = 1
+    """
+    Replace the input labels with epsilon.
+    """
+    OUTPUT_EPSILON = 2
+    """
+    Replace the output labels with epsilon.
+    """
+    RM_WEIGHT = 3
+    """
+    Replace every non-zero weight with one.
+    """
+    INVERT_WEIGHT = 4
+    """
+    Replace every weight with its inverse.
+    """
+    QUANTIZE = 5
+    """
+    Quantize the weights using `value` as delta.
+    """
+    PLUS = 6
+    """
+    ⊕-sum every weight with `value`.
+    """
+    TIMES = 7
+    """
+    ⊗-multiply every weight with `value`.
+    """
+
+
+class TrMapper:
+    def __init__(self, mapper_type: TrMapperType, value: Optional[float] = None):
+        self.ptr = None
+        if value is None:
+            if mapper_type in (TrMapperType.PLUS, TrMapperType.TIMES):
+                raise ValueError(f"A value is required by the {mapper_type} mapper")
+            value = KDELTA
+        config = ctypes.pointer(ctypes.c_void_p())
+        ret_code = lib.fst_tr_mapper_new(
+            ctypes.c_size_t(mapper_type.value),
+            ctypes.c_float(value),
+            ctypes.byref(config),
+        )
+        err_msg = "Error creating TrMapper"
+        check_ffi_error(ret_code, err_msg)
+        self.ptr = config
+
+    def __del__(self):
+        if self.ptr is not None:
+            lib.fst_tr_mapper_destroy(self.ptr)
+
+
+def tr_map(fst: VectorFst, mapper: TrMapper) -> VectorFst:
+    """
+    Map in-place every transition and final weight of the Fst using a `TrMapper`.
+
+    Args:
+        fst: Fst on which to apply the mapper.
+        mapper: Mapper to apply.
+    Returns:
+        self
+    """
+
+    ret_code = lib.fst_tr_map(fst.ptr, mapper.ptr)
+    err_msg = "Error during tr_map"
+    check_ffi_error(ret_code, err_msg)
+
+    return fst
@@ -7,6 +7,7 @@ from rustfst.ffi_utils import (
 
 from rustfst.symbol_table import SymbolTable
 from rustfst.iterators import TrsIterator
+from rustfst.weight import WeightType
 from typing import Optional
 
 
@@ -35,6 +36,19 @@ class Fst:
             return None
         return int(start.value)
 
+    def weight_type(self) -> WeightType:
+        """
+        Returns the semiring of the weights stored in the Fst.
+        Returns :
+            The WeightType of the Fst.
+        """
+        weight_type = ctypes.c_size_t()
+        ret_code = lib.fst_weight_type(self.ptr, ctypes.byref(weight_type))
+        err_msg = "Error getting weight type"
+        check_ffi_error(ret_code, err_msg)
+
+        return WeightType(weight_type.value)
+
     def final(self, state: int) -> Optional[float]:
         """
         Returns the final weight of a state.
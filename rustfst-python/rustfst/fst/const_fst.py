@@ -8,11 +8,18 @@ from rustfst.ffi_utils import (
 from rustfst.fst import Fst
 from rustfst.symbol_table import SymbolTable
 from rustfst.drawing_config import DrawingConfig
+from rustfst.weight import WeightType
 from typing import Optional, Union
 from pathlib import Path
 
 
 class ConstFst(Fst):
+    """
+    Immutable Fst over the Tropical Semiring.
+    """
+
+    _weight_type = WeightType.TROPICAL
+
     def __init__(self, ptr=None):
         if ptr:
             self.ptr = ptr
@@ -98,7 +105,9 @@ class ConstFst(Fst):
         """
         fst = ctypes.pointer(ctypes.c_void_p())
         ret_code = lib.const_fst_from_path(
-            ctypes.byref(fst), str(filename).encode("utf-8")
+            ctypes.byref(fst),
+            str(filename).encode("utf-8"),
+            ctypes.c_size_t(cls._weight_type.value),
         )
         err_msg = f"Read failed. file: {filename}"
         check_ffi_error(ret_code, err_msg)
@@ -144,7 +153,7 @@ class ConstFst(Fst):
         err_msg = "Error copying fst"
         check_ffi_error(ret_code, err_msg)
 
-        return ConstFst(cloned_fst)
+        return type(self)(cloned_fst)
 
     def __str__(self):
         s = ctypes.c_void_p()
@@ -153,3 +162,19 @@ class ConstFst(Fst):
         check_ffi_error(ret_code, err_msg)
 
         return ctypes.string_at(s).decode("utf8")
+
+
+class LogConstFst(ConstFst):
+    """
+    Immutable Fst over the Log Semiring.
+    """
+
+    _weight_type = WeightType.LOG
+
+
+class BooleanConstFst(ConstFst):
+    """
+    Immutable Fst over the Boolean Semiring.
+    """
+
+    _weight_type = WeightType.BOOLEAN
@@ -12,7 +12,7 @@ from rustfst.symbol_table import SymbolTable
 from rustfst.drawing_config import DrawingConfig
 from rustfst.iterators import MutableTrsIterator, StateIterator
 from rustfst.tr import Tr
-from rustfst.weight import weight_one
+from rustfst.weight import weight_one, WeightType
 from typing import Optional, Union
 from pathlib import Path
 
@@ -20,6 +20,12 @@ from typing import List
 
 
 class VectorFst(Fst):
+    """
+    Mutable Fst over the Tropical Semiring.
+    """
+
+    _weight_type = WeightType.TROPICAL
+
     def __init__(self, ptr=None):
         """
         Creates an empty VectorFst.
@@ -48,7 +54,9 @@ class VectorFst(Fst):
 
         else:
             fst_ptr = ctypes.pointer(ctypes.c_void_p())
-            ret_code = lib.vec_fst_new(ctypes.byref(fst_ptr))
+            ret_code = lib.vec_fst_new(
+                ctypes.byref(fst_ptr), ctypes.c_size_t(self._weight_type.value)
+            )
 
             err_msg = "Something went wrong when creating the Fst struct"
             check_ffi_error(ret_code, err_msg)
@@ -102,7 +110,7 @@ class VectorFst(Fst):
         See also: `set_start`.
         """
         if weight is None:
-            weight = weight_one()
+            weight = weight_one(self._weight_type)
 
         state = ctypes.c_size_t(state)
         weight = ctypes.c_float(weight)
@@ -313,7 +321,9 @@ class VectorFst(Fst):
         """
         fst = ctypes.pointer(ctypes.c_void_p())
         ret_code = lib.vec_fst_from_path(
-            ctypes.byref(fst), str(filename).encode("utf-8")
+            ctypes.byref(fst),
+            str(filename).encode("utf-8"),
+            ctypes.c_size_t(cls._weight_type.value),
         )
         err_msg = f"Read failed. file: {filename}"
         check_ffi_error(ret_code, err_msg)
@@ -351,11 +361,15 @@ class VectorFst(Fst):
 
         c_bytes = BytesArray(data, len(data))
 
-        ret_code = lib.vec_fst_from_bytes(ctypes.byref(c_bytes), ctypes.byref(fst_ptr))
+        ret_code = lib.vec_fst_from_bytes(
+            ctypes.byref(c_bytes),
+            ctypes.byref(fst_ptr),
+            ctypes.c_size_t(cls._weight_type.value),
+        )
         error_msg = "`from_bytes` failed"
         check_ffi_error(ret_code, error_msg)
 
-        return VectorFst(ptr=fst_ptr)
+        return cls(ptr=fst_ptr)
 
     def to_bytes(self) -> bytes:
         """
@@ -406,7 +420,7 @@ class VectorFst(Fst):
         err_msg = "Error copying fst"
         check_ffi_error(ret_code, err_msg)
 
-        return VectorFst(cloned_fst)
+        return type(self)(cloned_fst)
 
     def compose(self, other: VectorFst, config=None) -> VectorFst:
         from rustfst.algorithms.compose import compose, compose_with_config
@@ -557,6 +571,62 @@ class VectorFst(Fst):
 
         return isomorphic(self, other)
 
+    def invert(self) -> VectorFst:
+        from rustfst.algorithms.invert import invert
+
+        return invert(self)
+
+    def closure(self, closure_type=None) -> VectorFst:
+        from rustfst.algorithms.closure import closure, ClosureType
+
+        if closure_type:
+            return closure(self, closure_type)
+        return closure(self, ClosureType.CLOSURE_STAR)
+
+    def push_weights(
+        self, reweight_type, remove_total_weight: bool = False
+    ) -> VectorFst:
+        from rustfst.algorithms.push import push_weights
+
+        return push_weights(self, reweight_type, remove_total_weight)
+
+    def push(self, reweight_type, push_type=None) -> VectorFst:
+        from rustfst.algorithms.push import push, PushType
+
+        if push_type:
+            return push(self, reweight_type, push_type)
+        return push(self, reweight_type, PushType.PUSH_WEIGHTS)
+
+    def shortest_distance(self, reverse: bool = False) -> List[float]:
+        from rustfst.algorithms.shortest_distance import shortest_distance
+
+        return shortest_distance(self, reverse)
+
+    def encode(self, encode_labels: bool = True, encode_weights: bool = False):
+        from rustfst.algorithms.encode import encode
+
+        return encode(self, encode_labels, encode_weights)
+
+    def decode(self, encode_table) -> VectorFst:
+        from rustfst.algorithms.encode import decode
+
+        return decode(self, encode_table)
+
+    def tr_map(self, mapper) -> VectorFst:
+        from rustfst.algorithms.tr_map import tr_map
+
+        return tr_map(self, mapper)
+
+    def state_sort(self, order: List[int]) -> VectorFst:
+        from rustfst.algorithms.state_sort import state_sort
+
+        return state_sort(self, order)
+
+    def relabel_pairs(self, ipairs=None, opairs=None) -> VectorFst:
+        from rustfst.algorithms.relabel_pairs import relabel_pairs
+
+        return relabel_pairs(self, ipairs, opairs)
+
     def __add__(self, other: VectorFst) -> VectorFst:
         """
         `fst_1 + fst_2` is a shortcut to perform the concatenation of `fst_1` and `fst_2`.
@@ -605,3 +675,21 @@ class VectorFst(Fst):
 
     def string_paths(self) -> StringPathsIterator:
         return StringPathsIterator(self)
+
+
+class LogVectorFst(VectorFst):
+    """
+    Mutable Fst over the Log Semiring.
+    Useful to compute posteriors as weights are summed across paths.
+    """
+
+    _weight_type = WeightType.LOG
+
+
+class BooleanVectorFst(VectorFst):
+    """
+    Mutable Fst over the Boolean Semiring.
+    Weights are exposed as floats : 1.0 for `True` and 0.0 for `False`.
+    """
+
+    _weight_type = WeightType.BOOLEAN
@@ -6,18 +6,23 @@ from rustfst.ffi_utils import (
 )
 
 from rustfst.tr import Tr
+from rustfst.weight import WeightType
 
 
 class Trs:
     """Structure representing list of transitions."""
 
-    def __init__(self, ptr=None) -> Trs:
+    def __init__(
+        self, ptr=None, weight_type: WeightType = WeightType.TROPICAL
+    ) -> Trs:
         """
         Create an empty list of transitions.
+        Args:
+            weight_type: Semiring of the transitions' weights.
         """
         if ptr is None:
             self._ptr = c_void_p()
-            exit_code = lib.trs_vec_new(byref(self._ptr))
+            exit_code = lib.trs_vec_new(byref(self._ptr), c_size_t(weight_type.value))
             err_msg = "Something went wrong when creating the Trs struct"
             check_ffi_error(exit_code, err_msg)
         else:
@@ -1,31 +1,60 @@
 import ctypes
+from enum import Enum
+
 from rustfst.ffi_utils import (
     lib,
     check_ffi_error,
 )
 
 
-def weight_one() -> float:
+class WeightType(Enum):
+    """
+    Semirings supported by the bindings. Weights are always exposed as floats.
+    """
+
+    TROPICAL = 0
+    """
+    Tropical semiring : (min, +, inf, 0).
+    """
+    LOG = 1
+    """
+    Log semiring : (-log(e^-x + e^-y), +, inf, 0).
+    """
+    BOOLEAN = 2
+    """
+    Boolean semiring : (or, and, false, true). `True` is mapped to 1.0 and `False` to 0.0.
+    """
+
+
+def weight_one(weight_type: WeightType = WeightType.TROPICAL) -> float:
     """
-    Compute One() in the Tropical Semiring.
+    Compute One() in the given semiring.
+    Args:
+        weight_type: Semiring to use. Defaults to the Tropical Semiring.
     Returns:
-        Float value corresponding to One() in the Tropical Semiring.
+        Float value corresponding to One() in the semiring.
     """
     weight = ctypes.c_float()
-    ret_code = lib.fst_weight_one(ctypes.byref(weight))
+    ret_code = lib.fst_weight_one(
+        ctypes.byref(weight), ctypes.c_size_t(weight_type.value)
+    )
     err_msg = "weight_one failed"
     check_ffi_error(ret_code, err_msg)
     return float(weight.value)
 
 
-def weight_zero() -> float:
+def weight_zero(weight_type: WeightType = WeightType.TROPICAL) -> float:
     """
-    Compute Zero() in the Tropical Semiring.
+    Compute Zero() in the given semiring.
+    Args:
+        weight_type: Semiring to use. Defaults to the Tropical Semiring.
     Returns:
-        Float value corresponding to Zero() in the Tropical Semiring.
+        Float value corresponding to Zero() in the semiring.
     """
     weight = ctypes.c_float()
-    ret_code = lib.fst_weight_zero(ctypes.byref(weight))
+    ret_code = lib.fst_weight_zero(
+        ctypes.byref(weight), ctypes.c_size_t(weight_type.value)
+    )
     err_msg = "weight_zero failed"
     check_ffi_error(ret_code, err_msg)
     return float(weight.value)
@@ -0,0 +1,68 @@
+from rustfst import VectorFst, Tr
+from rustfst.algorithms.closure import ClosureType
+
+
+def build_fst():
+    fst = VectorFst()
+
+    s1 = fst.add_state()
+    s2 = fst.add_state()
+
+    fst.set_start(s1)
+    fst.set_final(s2, 0.5)
+
+    tr_1 = Tr(1, 2, 1.0, s2)
+    fst.add_tr(s1, tr_1)
+
+    return fst
+
+
+def test_closure_plus():
+    fst1 = build_fst()
+
+    # Expected FST
+    expected_fst = VectorFst()
+
+    s1 = expected_fst.add_state()
+    s2 = expected_fst.add_state()
+
+    expected_fst.set_start(s1)
+    expected_fst.set_final(s2, 0.5)
+
+    tr_1 = Tr(1, 2, 1.0, s2)
+    expected_fst.add_tr(s1, tr_1)
+
+    tr_2 = Tr(0, 0, 0.5, s1)
+    expected_fst.add_tr(s2, tr_2)
+
+    fst1.closure(ClosureType.CLOSURE_PLUS)
+
+    assert fst1 == expected_fst
+
+
+def test_closure_star():
+    fst1 = build_fst()
+
+    # Expected FST
+    expected_fst = VectorFst()
+
+    s1 = expected_fst.add_state()
+    s2 = expected_fst.add_state()
+    s3 = expected_fst.add_state()
+
+    expected_fst.set_start(s3)
+    expected_fst.set_final(s2, 0.5)
+    expected_fst.set_final(s3, 0.0)
+
+    tr_1 = Tr(1, 2, 1.0, s2)
+    expected_fst.add_tr(s1, tr_1)
+
+    tr_2 = Tr(0, 0, 0.5, s1)
+    expected_fst.add_tr(s2, tr_2)
+
+    tr_3 = Tr(0, 0, 0.0, s1)
+    expected_fst.add_tr(s3, tr_3)
+
+    fst1.closure()
+
+    assert fst1 == expected_fst
@@ -0,0 +1,27 @@
+from rustfst import VectorFst, Tr
+
+
+def test_encode_decode():
+    fst = VectorFst()
+
+    s1 = fst.add_state()
+    s2 = fst.add_state()
+
+    fst.set_start(s1)
+    fst.set_final(s2, 0.5)
+
+    fst.add_tr(s1, Tr(1, 2, 1.0, s2))
+    fst.add_tr(s1, Tr(3, 4, 2.0, s2))
+    fst.add_tr(s1, Tr(1, 2, 3.0, s2))
+
+    original_fst = fst.copy()
+
+    encode_table = fst.encode(encode_labels=True, encode_weights=True)
+
+    labels = [(tr.ilabel, tr.olabel) for tr in fst.trs(s1)]
+    assert labels == [(1, 1), (2, 2), (3, 3)]
+    assert all(tr.weight == 0.0 for tr in fst.trs(s1))
+
+    fst.decode(encode_table)
+
+    assert fst == original_fst
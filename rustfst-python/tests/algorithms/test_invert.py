@@ -0,0 +1,37 @@
+from rustfst import VectorFst, Tr
+
+
+def test_invert():
+    # FST 1
+    fst1 = VectorFst()
+
+    s1 = fst1.add_state()
+    s2 = fst1.add_state()
+
+    fst1.set_start(s1)
+    fst1.set_final(s2, 0.5)
+
+    tr1_1 = Tr(1, 2, 1.0, s2)
+    fst1.add_tr(s1, tr1_1)
+
+    tr1_2 = Tr(3, 4, 2.0, s2)
+    fst1.add_tr(s1, tr1_2)
+
+    # Expected FST
+    expected_fst = VectorFst()
+
+    s1 = expected_fst.add_state()
+    s2 = expected_fst.add_state()
+
+    expected_fst.set_start(s1)
+    expected_fst.set_final(s2, 0.5)
+
+    tr_1 = Tr(2, 1, 1.0, s2)
+    expected_fst.add_tr(s1, tr_1)
+
+    tr_2 = Tr(4, 3, 2.0, s2)
+    expected_fst.add_tr(s1, tr_2)
+
+    fst1.invert()
+
+    assert fst1 == expected_fst
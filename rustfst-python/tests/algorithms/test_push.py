@@ -0,0 +1,52 @@
+import pytest
+
+from rustfst import VectorFst, Tr
+from rustfst.algorithms.push import PushType, ReweightType
+
+
+def build_fst():
+    fst = VectorFst()
+
+    s1 = fst.add_state()
+    s2 = fst.add_state()
+
+    fst.set_start(s1)
+    fst.set_final(s2, 1.0)
+
+    fst.add_tr(s1, Tr(1, 1, 2.0, s2))
+    fst.add_tr(s1, Tr(2, 2, 3.0, s2))
+
+    return fst
+
+
+def test_push_weights_to_initial():
+    fst = build_fst()
+
+    # Expected FST
+    expected_fst = VectorFst()
+
+    s1 = expected_fst.add_state()
+    s2 = expected_fst.add_state()
+
+    expected_fst.set_start(s1)
+    expected_fst.set_final(s2, 0.0)
+
+    expected_fst.add_tr(s1, Tr(1, 1, 3.0, s2))
+    expected_fst.add_tr(s1, Tr(2, 2, 4.0, s2))
+
+    fst.push_weights(ReweightType.REWEIGHT_TO_INITIAL)
+
+    assert fst == expected_fst
+
+
+def test_push_remove_total_weight():
+    fst = build_fst()
+
+    pushed_fst = fst.push(
+        ReweightType.REWEIGHT_TO_INITIAL,
+        PushType.PUSH_WEIGHTS | PushType.REMOVE_TOTAL_WEIGHT,
+    )
+
+    weights = [tr.weight for tr in pushed_fst.trs(pushed_fst.start())]
+    assert weights == [pytest.approx(0.0), pytest.approx(1.0)]
+    assert pushed_fst.final(1) == pytest.approx(0.0)
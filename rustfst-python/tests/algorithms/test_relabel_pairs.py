@@ -0,0 +1,31 @@
+from rustfst import VectorFst, Tr
+
+
+def test_relabel_pairs():
+    # FST 1
+    fst1 = VectorFst()
+
+    s1 = fst1.add_state()
+    s2 = fst1.add_state()
+
+    fst1.set_start(s1)
+    fst1.set_final(s2, 0.0)
+
+    fst1.add_tr(s1, Tr(1, 2, 1.0, s2))
+    fst1.add_tr(s1, Tr(3, 4, 2.0, s2))
+
+    # Expected FST
+    expected_fst = VectorFst()
+
+    s1 = expected_fst.add_state()
+    s2 = expected_fst.add_state()
+
+    expected_fst.set_start(s1)
+    expected_fst.set_final(s2, 0.0)
+
+    expected_fst.add_tr(s1, Tr(5, 2, 1.0, s2))
+    expected_fst.add_tr(s1, Tr(3, 6, 2.0, s2))
+
+    fst1.relabel_pairs(ipairs=[(1, 5)], opairs=[(4, 6)])
+
+    assert fst1 == expected_fst
@@ -0,0 +1,45 @@
+import pytest
+
+from rustfst import VectorFst, LogVectorFst, Tr
+
+
+def test_shortest_distance():
+    fst = VectorFst()
+
+    s1 = fst.add_state()
+    s2 = fst.add_state()
+    s3 = fst.add_state()
+
+    fst.set_start(s1)
+    fst.set_final(s3, 0.0)
+
+    fst.add_tr(s1, Tr(1, 1, 1.0, s2))
+    fst.add_tr(s1, Tr(2, 2, 4.0, s3))
+    fst.add_tr(s2, Tr(3, 3, 2.0, s3))
+
+    assert fst.shortest_distance() == [
+        pytest.approx(0.0),
+        pytest.approx(1.0),
+        pytest.approx(3.0),
+    ]
+    assert fst.shortest_distance(reverse=True) == [
+        pytest.approx(3.0),
+        pytest.approx(2.0),
+        pytest.approx(0.0),
+    ]
+
+
+def test_shortest_distance_log():
+    fst = LogVectorFst()
+
+    s1 = fst.add_state()
+    s2 = fst.add_state()
+
+    fst.set_start(s1)
+    fst.set_final(s2, 0.0)
+
+    fst.add_tr(s1, Tr(1, 1, 0.6931472, s2))
+    fst.add_tr(s1, Tr(2, 2, 0.6931472, s2))
+
+    # -log(0.5 + 0.5) = 0
+    assert fst.shortest_distance() == [pytest.approx(0.0), pytest.approx(0.0, abs=1e-5)]
@@ -0,0 +1,46 @@
+import pytest
+
+from rustfst import VectorFst, Tr
+
+
+def test_state_sort():
+    # FST 1
+    fst1 = VectorFst()
+
+    s1 = fst1.add_state()
+    s2 = fst1.add_state()
+    s3 = fst1.add_state()
+
+    fst1.set_start(s1)
+    fst1.set_final(s3, 0.5)
+
+    fst1.add_tr(s1, Tr(1, 1, 1.0, s2))
+    fst1.add_tr(s2, Tr(2, 2, 2.0, s3))
+
+    # Expected FST
+    expected_fst = VectorFst()
+
+    s1 = expected_fst.add_state()
+    s2 = expected_fst.add_state()
+    s3 = expected_fst.add_state()
+
+    expected_fst.set_start(s3)
+    expected_fst.set_final(s2, 0.5)
+
+    expected_fst.add_tr(s3, Tr(1, 1, 1.0, s1))
+    expected_fst.add_tr(s1, Tr(2, 2, 2.0, s2))
+
+    fst1.state_sort([2, 0, 1])
+
+    assert fst1 == expected_fst
+
+
+def test_state_sort_not_a_permutation():
+    fst = VectorFst()
+    fst.add_state()
+    fst.add_state()
+    fst.set_start(0)
+
+    for order in [[0, 0], [0, 2], [0]]:
+        with pytest.raises(ValueError):
+            fst.state_sort(order)
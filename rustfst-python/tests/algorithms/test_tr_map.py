@@ -0,0 +1,52 @@
+import pytest
+
+from rustfst import VectorFst, Tr
+from rustfst.algorithms.tr_map import TrMapper, TrMapperType
+
+
+def build_fst():
+    fst = VectorFst()
+
+    s1 = fst.add_state()
+    s2 = fst.add_state()
+
+    fst.set_start(s1)
+    fst.set_final(s2, 0.5)
+
+    fst.add_tr(s1, Tr(1, 2, 1.0, s2))
+    fst.add_tr(s1, Tr(3, 4, 2.0, s2))
+
+    return fst
+
+
+def test_tr_map_times():
+    fst = build_fst()
+
+    fst.tr_map(TrMapper(TrMapperType.TIMES, 1.5))
+
+    weights = [tr.weight for tr in fst.trs(0)]
+    assert weights == [pytest.approx(2.5), pytest.approx(3.5)]
+    assert fst.final(1) == pytest.approx(2.0)
+
+
+def test_tr_map_rm_weight():
+    fst = build_fst()
+
+    fst.tr_map(TrMapper(TrMapperType.RM_WEIGHT))
+
+    assert [tr.weight for tr in fst.trs(0)] == [0.0, 0.0]
+    assert fst.final(1) == 0.0
+
+
+def test_tr_map_input_epsilon():
+    fst = build_fst()
+
+    fst.tr_map(TrMapper(TrMapperType.INPUT_EPSILON))
+
+    labels = [(tr.ilabel, tr.olabel) for tr in fst.trs(0)]
+    assert labels == [(0, 2), (0, 4)]
+
+
+def test_tr_map_missing_value():
+    with pytest.raises(ValueError):
+        TrMapper(TrMapperType.PLUS)
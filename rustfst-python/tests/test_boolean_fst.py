@@ -0,0 +1,68 @@
+from tempfile import NamedTemporaryFile
+
+import pytest
+
+from rustfst import BooleanVectorFst, VectorFst, Tr, SymbolTable, WeightType
+from rustfst.algorithms import acceptor
+from rustfst.weight import weight_one, weight_zero
+
+
+def build_boolean_fst():
+    fst = BooleanVectorFst()
+    s1 = fst.add_state()
+    s2 = fst.add_state()
+    fst.set_start(s1)
+    fst.set_final(s2)
+    fst.add_tr(s1, Tr(1, 2, 1.0, s2))
+    fst.add_tr(s1, Tr(3, 4, 1.0, s2))
+    return fst
+
+
+def test_boolean_fst_weight_type():
+    assert BooleanVectorFst().weight_type() == WeightType.BOOLEAN
+    assert VectorFst().weight_type() == WeightType.TROPICAL
+
+
+def test_boolean_weight_one_zero():
+    assert weight_one(WeightType.BOOLEAN) == 1.0
+    assert weight_zero(WeightType.BOOLEAN) == 0.0
+
+
+def test_boolean_fst_weights():
+    fst = build_boolean_fst()
+
+    assert fst.final(1) == 1.0
+    weights = [tr.weight for tr in fst.trs(0)]
+    assert weights == [1.0, 1.0]
+
+
+def test_boolean_fst_read_write():
+    fst = build_boolean_fst()
+
+    with NamedTemporaryFile() as f:
+        fst.write(f.name)
+        read_fst = BooleanVectorFst.read(f.name)
+
+        assert isinstance(read_fst, BooleanVectorFst)
+        assert read_fst == fst
+
+        with pytest.raises(Exception):
+            VectorFst.read(f.name)
+
+
+def test_boolean_fst_algorithms_keep_weight_type():
+    fst = build_boolean_fst()
+
+    assert isinstance(fst.copy(), BooleanVectorFst)
+    assert isinstance(fst.reverse(), BooleanVectorFst)
+
+
+def test_boolean_acceptor():
+    symt = SymbolTable()
+    symt.add_symbol("hello")
+
+    fst = acceptor("hello", symt, fst_type=BooleanVectorFst)
+
+    assert isinstance(fst, BooleanVectorFst)
+    assert fst.weight_type() == WeightType.BOOLEAN
+    assert fst.num_states() == 2
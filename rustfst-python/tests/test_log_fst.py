@@ -0,0 +1,68 @@
+from tempfile import NamedTemporaryFile
+
+import pytest
+
+from rustfst import LogVectorFst, VectorFst, Tr, SymbolTable, WeightType
+from rustfst.algorithms import acceptor
+from rustfst.weight import weight_one, weight_zero
+
+
+def build_log_fst():
+    fst = LogVectorFst()
+    s1 = fst.add_state()
+    s2 = fst.add_state()
+    fst.set_start(s1)
+    fst.set_final(s2, 0.5)
+    fst.add_tr(s1, Tr(1, 2, 0.7, s2))
+    fst.add_tr(s1, Tr(3, 4, 1.2, s2))
+    return fst
+
+
+def test_log_fst_weight_type():
+    assert LogVectorFst().weight_type() == WeightType.LOG
+    assert VectorFst().weight_type() == WeightType.TROPICAL
+
+
+def test_log_weight_one_zero():
+    assert weight_one(WeightType.LOG) == pytest.approx(0.0)
+    assert weight_zero(WeightType.LOG) == float("inf")
+
+
+def test_log_fst_weights():
+    fst = build_log_fst()
+
+    assert fst.final(1) == pytest.approx(0.5)
+    weights = [tr.weight for tr in fst.trs(0)]
+    assert weights == [pytest.approx(0.7), pytest.approx(1.2)]
+
+
+def test_log_fst_read_write():
+    fst = build_log_fst()
+
+    with NamedTemporaryFile() as f:
+        fst.write(f.name)
+        read_fst = LogVectorFst.read(f.name)
+
+        assert isinstance(read_fst, LogVectorFst)
+        assert read_fst == fst
+
+        with pytest.raises(Exception):
+            VectorFst.read(f.name)
+
+
+def test_log_fst_algorithms_keep_weight_type():
+    fst = build_log_fst()
+
+    assert isinstance(fst.copy(), LogVectorFst)
+    assert isinstance(fst.reverse(), LogVectorFst)
+
+
+def test_log_acceptor():
+    symt = SymbolTable()
+    symt.add_symbol("hello")
+
+    fst = acceptor("hello", symt, fst_type=LogVectorFst)
+
+    assert isinstance(fst, LogVectorFst)
+    assert fst.weight_type() == WeightType.LOG
+    assert fst.num_states() == 2
@@ -11,7 +11,7 @@ use crate::algorithms::compose::{
     ComposeFstOp, ComposeFstOpOptions, ComposeFstOpState, ComposeStateTuple,
 };
 use crate::algorithms::lazy::{
-    FstCache, LazyFst, SerializableCache, SerializableLazyFst, SimpleVecCache,
+    FstCache, FstOp, LazyFst, SerializableCache, SerializableLazyFst, SimpleVecCache,
 };
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::{AllocableFst, CoreFst, Fst, FstIterator, MutableFst, StateIterator};
@@ -162,6 +162,18 @@ where
     pub fn compute<F: MutableFst<W> + AllocableFst<W>>(&self) -> Result<F> {
         self.0.compute()
     }
+
+    /// Same as `compute` but the states are expanded concurrently on the rayon thread pool
+    /// and renumbered in breadth-first order.
+    #[cfg(feature = "rayon")]
+    pub fn compute_parallel<F: MutableFst<W> + AllocableFst<W>>(&self) -> Result<F>
+    where
+        W: Send + Sync,
+        ComposeFstOp<W, F1, F2, B1, B2, M1, M2, CFB>: Sync,
+        Cache: Sync,
+    {
+        self.0.compute_parallel()
+    }
 }
 
 impl<W, F1, F2, B1, B2>
@@ -198,6 +210,20 @@ where
         let fst = LazyFst::from_op_and_cache(compose_impl, fst_cache, isymt, osymt);
         Ok(ComposeFst(fst))
     }
+
+    /// Same as `new_auto` for two FSTs known to be acceptors, even if their stored properties
+    /// don't say it. The result is then flagged as an acceptor.
+    pub(crate) fn new_auto_acceptors(fst1: B1, fst2: B2) -> Result<Self> {
+        let isymt = fst1.borrow().input_symbols().cloned();
+        let osymt = fst2.borrow().output_symbols().cloned();
+        let mut compose_impl = create_base(fst1, fst2)?;
+        let properties = compose_impl.properties();
+        compose_impl
+            .set_properties((properties - FstProperties::NOT_ACCEPTOR) | FstProperties::ACCEPTOR);
+        let fst_cache = SimpleVecCache::default();
+        let fst = LazyFst::from_op_and_cache(compose_impl, fst_cache, isymt, osymt);
+        Ok(ComposeFst(fst))
+    }
 }
 
 impl<W, F1, F2, B1, B2, M1, M2, CFB, Cache> SerializableLazyFst
@@ -394,4 +420,68 @@ mod test {
             >,
         >();
     }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_compose_fst_compute_parallel() -> Result<()> {
+        use crate::fst_traits::ExpandedFst;
+        use crate::Trs;
+
+        fn branching_fst(
+            num_states: StateId,
+            olabel_offset: u32,
+        ) -> Result<VectorFst<TropicalWeight>> {
+            let mut fst = VectorFst::new();
+            fst.add_states(num_states as usize);
+            fst.set_start(0)?;
+            for s in 0..num_states {
+                for label in 1..5 {
+                    fst.emplace_tr(
+                        s,
+                        label,
+                        label + olabel_offset,
+                        TropicalWeight::new((s * label) as f32),
+                        (s * 5 + label * 3) % num_states,
+                    )?;
+                }
+                if s % 3 == 0 {
+                    fst.set_final(s, TropicalWeight::new(s as f32))?;
+                }
+            }
+            Ok(fst)
+        }
+
+        let fst1 = Arc::new(branching_fst(30, 0)?);
+        let fst2 = Arc::new(branching_fst(20, 10)?);
+        type F = VectorFst<TropicalWeight>;
+        type M = SortedMatcher<TropicalWeight, F, Arc<F>>;
+        type CFst = ComposeFst<
+            TropicalWeight,
+            F,
+            F,
+            Arc<F>,
+            Arc<F>,
+            M,
+            M,
+            SequenceComposeFilterBuilder<TropicalWeight, F, F, Arc<F>, Arc<F>, M, M>,
+        >;
+
+        let serial: VectorFst<_> = CFst::new(fst1.clone(), fst2.clone())?.compute()?;
+        let parallel: VectorFst<_> = CFst::new(fst1.clone(), fst2.clone())?.compute_parallel()?;
+        assert!(serial.num_states() > 1);
+        assert_eq!(parallel, serial);
+
+        // The ids don't depend on the states already expanded, even if the op numbered them
+        // out of breadth-first order.
+        let compose_fst = CFst::new(fst1, fst2)?;
+        let start = compose_fst.start().unwrap();
+        let first_level = compose_fst.get_trs(start)?;
+        for tr in first_level.trs().iter().rev() {
+            compose_fst.get_trs(tr.nextstate)?;
+        }
+        let parallel: VectorFst<_> = compose_fst.compute_parallel()?;
+        assert_ne!(parallel, compose_fst.compute()?);
+        assert_eq!(parallel, serial);
+        Ok(())
+    }
 }
@@ -166,6 +166,12 @@ where
         })
     }
 
+    /// Replaces the properties computed from the ones stored in the input FSTs, for callers
+    /// knowing more about them (e.g `IntersectFst` which checks that both are acceptors).
+    pub(crate) fn set_properties(&mut self, properties: FstProperties) {
+        self.properties = properties;
+    }
+
     fn match_type(matcher1: &CFB::IM1, matcher2: &CFB::IM2) -> Result<MatchType> {
         if matcher1.flags().contains(MatcherFlags::REQUIRE_MATCH)
             && matcher1.match_type(true)? != MatchType::MatchOutput
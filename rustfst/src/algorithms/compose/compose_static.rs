@@ -8,7 +8,9 @@ use crate::algorithms::compose::compose_filters::{
     AltSequenceComposeFilterBuilder, MatchComposeFilterBuilder, NoMatchComposeFilterBuilder,
     NullComposeFilterBuilder, SequenceComposeFilterBuilder, TrivialComposeFilterBuilder,
 };
-use crate::algorithms::compose::matchers::{Matcher, SigmaMatcher, SortedMatcher};
+use crate::algorithms::compose::matchers::{
+    Matcher, PhiMatcher, RhoMatcher, SigmaMatcher, SortedMatcher,
+};
 use crate::algorithms::compose::ComposeFst;
 use crate::fst_traits::{AllocableFst, ExpandedFst, Fst, MutableFst};
 use crate::prelude::compose::matchers::{MatchType, MatcherRewriteMode};
@@ -34,14 +36,31 @@ pub struct SigmaMatcherConfig {
     pub sigma_allowed_matches: Option<Vec<Label>>,
 }
 
+#[derive(PartialEq, PartialOrd, Debug, Clone)]
+pub struct RhoMatcherConfig {
+    pub rho_label: Label,
+    pub rewrite_mode: MatcherRewriteMode,
+}
+
+#[derive(PartialEq, PartialOrd, Debug, Clone)]
+pub struct PhiMatcherConfig {
+    pub phi_label: Label,
+    pub phi_loop: bool,
+    pub rewrite_mode: MatcherRewriteMode,
+}
+
 #[derive(Default, PartialEq, PartialOrd, Debug, Clone)]
 pub struct MatcherConfig {
     pub sigma_matcher_config: Option<SigmaMatcherConfig>,
+    pub rho_matcher_config: Option<RhoMatcherConfig>,
+    pub phi_matcher_config: Option<PhiMatcherConfig>,
 }
 
 impl MatcherConfig {
     pub fn empty(&self) -> bool {
         self.sigma_matcher_config.is_none()
+            && self.rho_matcher_config.is_none()
+            && self.phi_matcher_config.is_none()
     }
 }
 
@@ -65,6 +84,7 @@ impl Default for ComposeConfig {
 }
 
 #[derive(Clone)]
+#[allow(clippy::enum_variant_names)]
 pub enum MatcherEnum<W, F, B>
 where
     W: Semiring,
@@ -73,6 +93,8 @@ where
 {
     SortedMatcher(SortedMatcher<W, F, B>),
     SigmaMatcher(SigmaMatcher<W, F, B, SortedMatcher<W, F, B>>),
+    RhoMatcher(RhoMatcher<W, F, B, SortedMatcher<W, F, B>>),
+    PhiMatcher(PhiMatcher<W, F, B, SortedMatcher<W, F, B>>),
 }
 
 impl MatcherConfig {
@@ -86,13 +108,20 @@ impl MatcherConfig {
         F: Fst<W>,
         B: Borrow<F> + Debug,
     {
-        if self.sigma_matcher_config.is_none() {
-            let matcher = SortedMatcher::new(fst, match_type)?;
+        let num_special_matchers = [
+            self.sigma_matcher_config.is_some(),
+            self.rho_matcher_config.is_some(),
+            self.phi_matcher_config.is_some(),
+        ]
+        .iter()
+        .filter(|v| **v)
+        .count();
+        if num_special_matchers > 1 {
+            bail!("Only one of sigma_matcher_config, rho_matcher_config and phi_matcher_config can be set")
+        }
 
-            Ok(MatcherEnum::SortedMatcher(matcher))
-        } else {
-            let sigma_config = self.sigma_matcher_config.clone().unwrap();
-            let matcher = SortedMatcher::new(fst, match_type)?;
+        let matcher = SortedMatcher::new(fst, match_type)?;
+        if let Some(sigma_config) = self.sigma_matcher_config.clone() {
             let matcher = SigmaMatcher::new(
                 match_type,
                 sigma_config.sigma_label,
@@ -104,6 +133,27 @@ impl MatcherConfig {
             )?;
 
             Ok(MatcherEnum::SigmaMatcher(matcher))
+        } else if let Some(rho_config) = &self.rho_matcher_config {
+            let matcher = RhoMatcher::new(
+                match_type,
+                rho_config.rho_label,
+                rho_config.rewrite_mode,
+                Arc::new(matcher),
+            )?;
+
+            Ok(MatcherEnum::RhoMatcher(matcher))
+        } else if let Some(phi_config) = &self.phi_matcher_config {
+            let matcher = PhiMatcher::new(
+                match_type,
+                phi_config.phi_label,
+                phi_config.phi_loop,
+                phi_config.rewrite_mode,
+                Arc::new(matcher),
+            )?;
+
+            Ok(MatcherEnum::PhiMatcher(matcher))
+        } else {
+            Ok(MatcherEnum::SortedMatcher(matcher))
         }
     }
 }
@@ -131,31 +181,63 @@ macro_rules! run_compose {
     }};
 }
 
-macro_rules! compose_generate_matchers {
+macro_rules! compose_generate_matcher2 {
     (
         $fst1: expr, $fst2: expr, $f1: ty, $f2: ty,
-        $builder: tt, $matcher1_enum: expr, $matcher2_enum: expr
+        $builder: tt, $m1: expr, $matcher1_ty: ty, $matcher2_enum: expr
     ) => {
         {
-            match ($matcher1_enum, $matcher2_enum) {
-                (MatcherEnum::SortedMatcher(m1), MatcherEnum::SortedMatcher(m2)) => {
+            match $matcher2_enum {
+                MatcherEnum::SortedMatcher(m2) => {
                     run_compose!(
-                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some(m1), SortedMatcher<_, _, _>, Some(m2), SortedMatcher<_,_,_>
+                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some($m1), $matcher1_ty, Some(m2), SortedMatcher<_,_,_>
                     )
                 },
-                (MatcherEnum::SigmaMatcher(m1), MatcherEnum::SortedMatcher(m2)) => {
+                MatcherEnum::SigmaMatcher(m2) => {
                     run_compose!(
-                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some(m1), SigmaMatcher<_, _, _, _>, Some(m2), SortedMatcher<_,_,_>
+                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some($m1), $matcher1_ty, Some(m2), SigmaMatcher<_,_,_,_>
                     )
                 },
-                (MatcherEnum::SortedMatcher(m1), MatcherEnum::SigmaMatcher(m2)) => {
+                MatcherEnum::RhoMatcher(m2) => {
                     run_compose!(
-                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some(m1), SortedMatcher<_, _, _>, Some(m2), SigmaMatcher<_,_,_,_>
+                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some($m1), $matcher1_ty, Some(m2), RhoMatcher<_,_,_,_>
                     )
                 },
-                (MatcherEnum::SigmaMatcher(m1), MatcherEnum::SigmaMatcher(m2)) => {
+                MatcherEnum::PhiMatcher(m2) => {
                     run_compose!(
-                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some(m1), SigmaMatcher<_, _, _, _>, Some(m2), SigmaMatcher<_,_,_,_>
+                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some($m1), $matcher1_ty, Some(m2), PhiMatcher<_,_,_,_>
+                    )
+                }
+            }
+        }
+    };
+}
+
+macro_rules! compose_generate_matchers {
+    (
+        $fst1: expr, $fst2: expr, $f1: ty, $f2: ty,
+        $builder: tt, $matcher1_enum: expr, $matcher2_enum: expr
+    ) => {
+        {
+            match $matcher1_enum {
+                MatcherEnum::SortedMatcher(m1) => {
+                    compose_generate_matcher2!(
+                        $fst1, $fst2, $f1, $f2, $builder, m1, SortedMatcher<_, _, _>, $matcher2_enum
+                    )
+                },
+                MatcherEnum::SigmaMatcher(m1) => {
+                    compose_generate_matcher2!(
+                        $fst1, $fst2, $f1, $f2, $builder, m1, SigmaMatcher<_, _, _, _>, $matcher2_enum
+                    )
+                },
+                MatcherEnum::RhoMatcher(m1) => {
+                    compose_generate_matcher2!(
+                        $fst1, $fst2, $f1, $f2, $builder, m1, RhoMatcher<_, _, _, _>, $matcher2_enum
+                    )
+                },
+                MatcherEnum::PhiMatcher(m1) => {
+                    compose_generate_matcher2!(
+                        $fst1, $fst2, $f1, $f2, $builder, m1, PhiMatcher<_, _, _, _>, $matcher2_enum
                     )
                 }
             }
@@ -0,0 +1,389 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::iter::Peekable;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::compose::matchers::{
+    IterItemMatcher, MatchType, Matcher, MatcherFlags, MatcherRewriteMode, REQUIRE_PRIORITY,
+};
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::Fst;
+use crate::{Label, Semiring, StateId, EPS_LABEL, NO_LABEL};
+
+/// Matcher where the phi label is a failure transition: when a label can't be matched at a
+/// state, the phi transition is followed (without consuming the label) and the match is
+/// attempted again at its destination. The weights of the phi transitions followed are
+/// multiplied into the weight of the matched transition.
+///
+/// When `phi_loop` is true, a phi self-loop matches any label that couldn't be matched
+/// otherwise and consumes it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhiMatcher<W, F, B, M>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+    M: Matcher<W, F, B>,
+{
+    match_type: MatchType,
+    w: PhantomData<(W, F, B)>,
+    phi_label: Label,
+    matcher: Arc<M>,
+    rewrite_both: bool,
+    phi_loop: bool,
+}
+
+impl<W, F, B, M> PhiMatcher<W, F, B, M>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+    M: Matcher<W, F, B>,
+{
+    pub fn new(
+        match_type: MatchType,
+        phi_label: Label,
+        phi_loop: bool,
+        rewrite_mode: MatcherRewriteMode,
+        matcher: Arc<M>,
+    ) -> Result<Self> {
+        if match_type == MatchType::MatchBoth {
+            bail!("PhiMatcher: Bad match type")
+        }
+        if phi_label == EPS_LABEL {
+            bail!("PhiMatcher: {} cannot be used as phi_label", EPS_LABEL)
+        }
+        let rewrite_both = match rewrite_mode {
+            MatcherRewriteMode::MatcherRewriteAuto => matcher
+                .fst()
+                .borrow()
+                .properties()
+                .contains(FstProperties::ACCEPTOR),
+            MatcherRewriteMode::MatcherRewriteAlways => true,
+            MatcherRewriteMode::MatcherRewriteNever => false,
+        };
+        Ok(Self {
+            match_type,
+            w: PhantomData,
+            phi_label,
+            matcher,
+            rewrite_both,
+            phi_loop,
+        })
+    }
+
+    pub fn phi_label(&self) -> Label {
+        self.phi_label
+    }
+}
+
+impl<W, F, B, M> Matcher<W, F, B> for PhiMatcher<W, F, B, M>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    M: Matcher<W, F, B>,
+{
+    type Iter = IteratorPhiMatcher<W, F, B, M>;
+
+    fn new(_fst: B, _match_type: MatchType) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        bail!("This constructor can't be used for initializing PhiMatcher.")
+    }
+
+    fn iter(&self, state: StateId, label: Label) -> Result<Self::Iter> {
+        IteratorPhiMatcher::new(
+            state,
+            label,
+            self.phi_label,
+            self.phi_loop,
+            self.match_type,
+            &self.matcher,
+            self.rewrite_both,
+        )
+    }
+
+    fn final_weight(&self, state: StateId) -> Result<Option<W>> {
+        self.matcher.final_weight(state)
+    }
+
+    fn match_type(&self, test: bool) -> Result<MatchType> {
+        self.matcher.match_type(test)
+    }
+
+    fn flags(&self) -> MatcherFlags {
+        if self.phi_label == NO_LABEL || self.match_type == MatchType::MatchNone {
+            self.matcher.flags()
+        } else {
+            self.matcher.flags() | MatcherFlags::REQUIRE_MATCH
+        }
+    }
+
+    fn priority(&self, state: StateId) -> Result<usize> {
+        if self.phi_label != NO_LABEL && self.matcher.iter(state, self.phi_label)?.next().is_some()
+        {
+            Ok(REQUIRE_PRIORITY)
+        } else {
+            self.matcher.priority(state)
+        }
+    }
+
+    fn fst(&self) -> &B {
+        self.matcher.fst()
+    }
+}
+
+pub struct IteratorPhiMatcher<W, F, B, M>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    M: Matcher<W, F, B>,
+{
+    /// State reached after following the phi transitions.
+    state: StateId,
+    phi_label: Label,
+    match_type: MatchType,
+    /// Label consumed by a phi self-loop. `None` when the requested label is matched
+    /// directly.
+    phi_match: Option<Label>,
+    /// Product of the weights of the phi transitions followed.
+    phi_weight: W,
+    matcher_iterator: Peekable<M::Iter>,
+    rewrite_both: bool,
+    w: PhantomData<(F, B)>,
+}
+
+impl<W, F, B, M> IteratorPhiMatcher<W, F, B, M>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    M: Matcher<W, F, B>,
+{
+    pub fn new(
+        state: StateId,
+        match_label: Label,
+        phi_label: Label,
+        phi_loop: bool,
+        match_type: MatchType,
+        matcher: &Arc<M>,
+        rewrite_both: bool,
+    ) -> Result<Self> {
+        if match_label == phi_label && phi_label != NO_LABEL {
+            bail!("PhiMatcher::Find: bad label (phi)")
+        }
+
+        let mut state = state;
+        let mut phi_weight = W::one();
+        let mut phi_match = None;
+        let mut matcher_iterator = matcher.iter(state, match_label)?.peekable();
+
+        if phi_label != NO_LABEL && match_label != EPS_LABEL && match_label != NO_LABEL {
+            while matcher_iterator.peek().is_none() {
+                let mut phi_iterator = matcher.iter(state, phi_label)?;
+                let phi_tr = match phi_iterator.next() {
+                    Some(item) => item.into_tr(state, match_type)?,
+                    // No phi transition : the iterator is empty.
+                    None => break,
+                };
+                if phi_iterator.next().is_some() {
+                    bail!("PhiMatcher: Phi non-determinism not supported")
+                }
+                if phi_tr.nextstate == state {
+                    if phi_loop {
+                        phi_match = Some(match_label);
+                        matcher_iterator = matcher.iter(state, phi_label)?.peekable();
+                    }
+                    // Without phi_loop, following a phi self-loop would never terminate.
+                    break;
+                }
+                phi_weight.times_assign(&phi_tr.weight)?;
+                state = phi_tr.nextstate;
+                matcher_iterator = matcher.iter(state, match_label)?.peekable();
+            }
+        }
+
+        Ok(Self {
+            state,
+            phi_label,
+            match_type,
+            phi_match,
+            phi_weight,
+            matcher_iterator,
+            rewrite_both,
+            w: PhantomData,
+        })
+    }
+}
+
+impl<W, F, B, M> Iterator for IteratorPhiMatcher<W, F, B, M>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    M: Matcher<W, F, B>,
+{
+    type Item = IterItemMatcher<W>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.matcher_iterator.next()?;
+        if self.phi_match.is_none() && self.phi_weight.is_one() {
+            return Some(item);
+        }
+
+        let mut phi_tr = item.into_tr(self.state, self.match_type).unwrap();
+        phi_tr.weight = self.phi_weight.times(&phi_tr.weight).unwrap();
+        if let Some(phi_match) = self.phi_match {
+            if self.rewrite_both {
+                if phi_tr.ilabel == self.phi_label {
+                    phi_tr.ilabel = phi_match;
+                }
+                if phi_tr.olabel == self.phi_label {
+                    phi_tr.olabel = phi_match;
+                }
+            } else if self.match_type == MatchType::MatchInput {
+                phi_tr.ilabel = phi_match;
+            } else {
+                phi_tr.olabel = phi_match;
+            }
+        }
+        Some(IterItemMatcher::Tr(phi_tr))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithms::compose::compose_filters::SequenceComposeFilterBuilder;
+    use crate::algorithms::compose::matchers::SortedMatcher;
+    use crate::algorithms::compose::{
+        compose_with_config, ComposeConfig, ComposeFilterEnum, ComposeFst, ComposeFstOpOptions,
+        MatcherConfig, PhiMatcherConfig,
+    };
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::MutableFst;
+    use crate::semirings::TropicalWeight;
+    use crate::utils::acceptor;
+    use crate::{FstPath, Tr};
+
+    use super::*;
+
+    const PHI: Label = 1;
+    const A: Label = 2;
+    const B: Label = 3;
+    const C: Label = 4;
+    const D: Label = 5;
+
+    /// Back-off bigram model : state 0 is the unigram state and state 1 the history `a`.
+    fn lm_fst() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        fst.add_states(2);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(PHI, PHI, TropicalWeight::new(5.0), 0))?;
+        fst.add_tr(0, Tr::new(A, A, TropicalWeight::new(1.0), 1))?;
+        fst.add_tr(0, Tr::new(B, B, TropicalWeight::new(2.0), 0))?;
+        fst.add_tr(0, Tr::new(C, C, TropicalWeight::new(3.0), 0))?;
+        fst.add_tr(1, Tr::new(PHI, PHI, TropicalWeight::new(0.25), 0))?;
+        fst.add_tr(1, Tr::new(C, C, TropicalWeight::new(0.5), 0))?;
+        fst.set_final(0, TropicalWeight::one())?;
+        fst.set_final(1, TropicalWeight::one())?;
+        Ok(fst)
+    }
+
+    fn compose_query(labels: &[Label]) -> Result<Vec<FstPath<TropicalWeight>>> {
+        let query: VectorFst<TropicalWeight> = acceptor(labels, TropicalWeight::one());
+        let lm = lm_fst()?;
+        let config = ComposeConfig {
+            compose_filter: ComposeFilterEnum::SequenceFilter,
+            matcher2_config: MatcherConfig {
+                phi_matcher_config: Some(PhiMatcherConfig {
+                    phi_label: PHI,
+                    phi_loop: false,
+                    rewrite_mode: MatcherRewriteMode::MatcherRewriteAuto,
+                }),
+                ..MatcherConfig::default()
+            },
+            ..ComposeConfig::default()
+        };
+        let composed: VectorFst<_> =
+            compose_with_config::<_, VectorFst<_>, VectorFst<_>, _, _, _>(&query, &lm, config)?;
+        Ok(composed.paths_iter().collect())
+    }
+
+    #[test]
+    fn test_phi_matcher_backoff() -> Result<()> {
+        assert_eq!(
+            compose_query(&[A, B])?,
+            vec![FstPath::new(
+                vec![A, B],
+                vec![A, B],
+                TropicalWeight::new(3.25)
+            )]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_phi_matcher_direct_match() -> Result<()> {
+        // The phi transition must not be taken when a direct match exists.
+        assert_eq!(
+            compose_query(&[A, C])?,
+            vec![FstPath::new(
+                vec![A, C],
+                vec![A, C],
+                TropicalWeight::new(1.5)
+            )]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_phi_matcher_no_match() -> Result<()> {
+        assert_eq!(compose_query(&[A, D])?, vec![]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_phi_matcher_phi_loop() -> Result<()> {
+        let query: VectorFst<TropicalWeight> = acceptor(&[A, D], TropicalWeight::one());
+        let lm = lm_fst()?;
+        let compose_fst_op_opts = ComposeFstOpOptions::new(
+            None,
+            PhiMatcher::new(
+                MatchType::MatchInput,
+                PHI,
+                true,
+                MatcherRewriteMode::MatcherRewriteAlways,
+                Arc::new(SortedMatcher::new(lm.clone(), MatchType::MatchInput)?),
+            )?,
+            None,
+            None,
+        );
+        let compose_lazy = ComposeFst::<
+            _,                                                 // W
+            _,                                                 // F1
+            _,                                                 // F2
+            _,                                                 // B1
+            _,                                                 // B2
+            SortedMatcher<_, _, _>,                            // M1
+            PhiMatcher<_, _, _, SortedMatcher<_, _, _>>,       // M2
+            SequenceComposeFilterBuilder<_, _, _, _, _, _, _>, // CFB
+        >::new_with_options(query, lm, compose_fst_op_opts)?;
+        let composed: VectorFst<_> = compose_lazy.compute()?;
+
+        assert_eq!(
+            composed.paths_iter().collect::<Vec<_>>(),
+            vec![FstPath::new(
+                vec![A, D],
+                vec![A, D],
+                TropicalWeight::new(6.25)
+            )]
+        );
+        Ok(())
+    }
+}
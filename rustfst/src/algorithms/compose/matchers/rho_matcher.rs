@@ -0,0 +1,306 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::iter::Peekable;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::compose::matchers::{
+    IterItemMatcher, MatchType, Matcher, MatcherFlags, MatcherRewriteMode, REQUIRE_PRIORITY,
+};
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::Fst;
+use crate::{Label, Semiring, StateId, EPS_LABEL, NO_LABEL};
+
+/// Matcher where the rho label matches any label not otherwise matched at a state.
+/// Unlike the sigma label, the rho transitions are only used when no other transition
+/// matches the requested label.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RhoMatcher<W, F, B, M>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+    M: Matcher<W, F, B>,
+{
+    match_type: MatchType,
+    w: PhantomData<(W, F, B)>,
+    rho_label: Label,
+    matcher: Arc<M>,
+    rewrite_both: bool,
+}
+
+impl<W, F, B, M> RhoMatcher<W, F, B, M>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+    M: Matcher<W, F, B>,
+{
+    pub fn new(
+        match_type: MatchType,
+        rho_label: Label,
+        rewrite_mode: MatcherRewriteMode,
+        matcher: Arc<M>,
+    ) -> Result<Self> {
+        if match_type == MatchType::MatchBoth {
+            bail!("RhoMatcher: Bad match type")
+        }
+        if rho_label == EPS_LABEL {
+            bail!("RhoMatcher: {} cannot be used as rho_label", EPS_LABEL)
+        }
+        let rewrite_both = match rewrite_mode {
+            MatcherRewriteMode::MatcherRewriteAuto => matcher
+                .fst()
+                .borrow()
+                .properties()
+                .contains(FstProperties::ACCEPTOR),
+            MatcherRewriteMode::MatcherRewriteAlways => true,
+            MatcherRewriteMode::MatcherRewriteNever => false,
+        };
+        Ok(Self {
+            match_type,
+            w: PhantomData,
+            rho_label,
+            matcher,
+            rewrite_both,
+        })
+    }
+
+    pub fn rho_label(&self) -> Label {
+        self.rho_label
+    }
+}
+
+impl<W, F, B, M> Matcher<W, F, B> for RhoMatcher<W, F, B, M>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    M: Matcher<W, F, B>,
+{
+    type Iter = IteratorRhoMatcher<W, F, B, M>;
+
+    fn new(_fst: B, _match_type: MatchType) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        bail!("This constructor can't be used for initializing RhoMatcher.")
+    }
+
+    fn iter(&self, state: StateId, label: Label) -> Result<Self::Iter> {
+        IteratorRhoMatcher::new(
+            state,
+            label,
+            self.rho_label,
+            self.match_type,
+            &self.matcher,
+            self.rewrite_both,
+        )
+    }
+
+    fn final_weight(&self, state: StateId) -> Result<Option<W>> {
+        self.matcher.final_weight(state)
+    }
+
+    fn match_type(&self, test: bool) -> Result<MatchType> {
+        self.matcher.match_type(test)
+    }
+
+    fn flags(&self) -> MatcherFlags {
+        if self.rho_label == NO_LABEL || self.match_type == MatchType::MatchNone {
+            self.matcher.flags()
+        } else {
+            self.matcher.flags() | MatcherFlags::REQUIRE_MATCH
+        }
+    }
+
+    fn priority(&self, state: StateId) -> Result<usize> {
+        if self.rho_label != NO_LABEL && self.matcher.iter(state, self.rho_label)?.next().is_some()
+        {
+            Ok(REQUIRE_PRIORITY)
+        } else {
+            self.matcher.priority(state)
+        }
+    }
+
+    fn fst(&self) -> &B {
+        self.matcher.fst()
+    }
+}
+
+pub struct IteratorRhoMatcher<W, F, B, M>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    M: Matcher<W, F, B>,
+{
+    state: StateId,
+    rho_label: Label,
+    match_type: MatchType,
+    /// Label matched by the rho transitions. `None` when the requested label is matched
+    /// directly.
+    rho_match: Option<Label>,
+    matcher_iterator: Peekable<M::Iter>,
+    rewrite_both: bool,
+    w: PhantomData<(W, F, B)>,
+}
+
+impl<W, F, B, M> IteratorRhoMatcher<W, F, B, M>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    M: Matcher<W, F, B>,
+{
+    pub fn new(
+        state: StateId,
+        match_label: Label,
+        rho_label: Label,
+        match_type: MatchType,
+        matcher: &Arc<M>,
+        rewrite_both: bool,
+    ) -> Result<Self> {
+        if match_label == rho_label && rho_label != NO_LABEL {
+            bail!("RhoMatcher::Find: bad label (rho)")
+        }
+
+        let mut matcher_iterator = matcher.iter(state, match_label)?.peekable();
+        let rho_match = if matcher_iterator.peek().is_some() {
+            None
+        } else if rho_label != NO_LABEL && match_label != EPS_LABEL && match_label != NO_LABEL {
+            // The iterator is empty if the state has no rho transition.
+            matcher_iterator = matcher.iter(state, rho_label)?.peekable();
+            Some(match_label)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            state,
+            rho_label,
+            match_type,
+            rho_match,
+            matcher_iterator,
+            rewrite_both,
+            w: PhantomData,
+        })
+    }
+}
+
+impl<W, F, B, M> Iterator for IteratorRhoMatcher<W, F, B, M>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    M: Matcher<W, F, B>,
+{
+    type Item = IterItemMatcher<W>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.matcher_iterator.next()?;
+        let rho_match = match self.rho_match {
+            None => return Some(item),
+            Some(rho_match) => rho_match,
+        };
+
+        let mut rho_tr = item.into_tr(self.state, self.match_type).unwrap();
+        if self.rewrite_both {
+            if rho_tr.ilabel == self.rho_label {
+                rho_tr.ilabel = rho_match;
+            }
+            if rho_tr.olabel == self.rho_label {
+                rho_tr.olabel = rho_match;
+            }
+        } else if self.match_type == MatchType::MatchInput {
+            rho_tr.ilabel = rho_match;
+        } else {
+            rho_tr.olabel = rho_match;
+        }
+        Some(IterItemMatcher::Tr(rho_tr))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithms::compose::{
+        compose_with_config, ComposeConfig, ComposeFilterEnum, MatcherConfig, RhoMatcherConfig,
+    };
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::MutableFst;
+    use crate::semirings::TropicalWeight;
+    use crate::utils::acceptor;
+    use crate::{FstPath, Tr};
+
+    use super::*;
+
+    const RHO: Label = 1;
+    const PLAY: Label = 2;
+    const BOWIE: Label = 3;
+    const QUEEN: Label = 4;
+    const PLEASE: Label = 5;
+
+    fn grammar_fst() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(PLAY, PLAY, TropicalWeight::one(), 1))?;
+        fst.add_tr(1, Tr::new(RHO, RHO, TropicalWeight::new(2.0), 2))?;
+        fst.add_tr(1, Tr::new(BOWIE, BOWIE, TropicalWeight::new(1.0), 2))?;
+        fst.add_tr(2, Tr::new(PLEASE, PLEASE, TropicalWeight::one(), 3))?;
+        fst.set_final(3, TropicalWeight::one())?;
+        Ok(fst)
+    }
+
+    fn compose_query(artist: Label) -> Result<Vec<FstPath<TropicalWeight>>> {
+        let query: VectorFst<TropicalWeight> =
+            acceptor(&[PLAY, artist, PLEASE], TropicalWeight::one());
+        let config = ComposeConfig {
+            compose_filter: ComposeFilterEnum::SequenceFilter,
+            matcher2_config: MatcherConfig {
+                rho_matcher_config: Some(RhoMatcherConfig {
+                    rho_label: RHO,
+                    rewrite_mode: MatcherRewriteMode::MatcherRewriteAuto,
+                }),
+                ..MatcherConfig::default()
+            },
+            ..ComposeConfig::default()
+        };
+        let grammar = grammar_fst()?;
+        let composed: VectorFst<_> = compose_with_config::<_, VectorFst<_>, VectorFst<_>, _, _, _>(
+            &query, &grammar, config,
+        )?;
+        Ok(composed.paths_iter().collect())
+    }
+
+    #[test]
+    fn test_rho_matcher_unmatched_label() -> Result<()> {
+        let paths = compose_query(QUEEN)?;
+        assert_eq!(
+            paths,
+            vec![FstPath::new(
+                vec![PLAY, QUEEN, PLEASE],
+                vec![PLAY, QUEEN, PLEASE],
+                TropicalWeight::new(2.0)
+            )]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_rho_matcher_matched_label() -> Result<()> {
+        // The rho transition must not be taken when a direct match exists.
+        let paths = compose_query(BOWIE)?;
+        assert_eq!(
+            paths,
+            vec![FstPath::new(
+                vec![PLAY, BOWIE, PLEASE],
+                vec![PLAY, BOWIE, PLEASE],
+                TropicalWeight::new(1.0)
+            )]
+        );
+        Ok(())
+    }
+}
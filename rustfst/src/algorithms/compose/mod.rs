@@ -5,7 +5,7 @@ pub use self::compose_fst_op_options::ComposeFstOpOptions;
 pub use self::compose_state_tuple::ComposeStateTuple;
 pub use self::compose_static::{
     compose, compose_with_config, ComposeConfig, ComposeFilterEnum, MatcherConfig,
-    SigmaMatcherConfig,
+    PhiMatcherConfig, RhoMatcherConfig, SigmaMatcherConfig,
 };
 pub use self::interval_reach_visitor::IntervalReachVisitor;
 pub use self::interval_set::{IntInterval, IntervalSet};
@@ -62,7 +62,7 @@ where
     }
 
     fn properties(&self) -> FstProperties {
-        unimplemented!()
+        self.0.properties()
     }
 
     fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
@@ -0,0 +1,288 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::determinize::divisors::{CommonDivisor, DefaultCommonDivisor};
+use crate::algorithms::determinize::{DeterminizeConfig, DeterminizeFstOp};
+use crate::algorithms::lazy::{FstCache, LazyFst, SimpleHashMapCache};
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{AllocableFst, CoreFst, Fst, FstIterator, MutableFst, StateIterator};
+use crate::semirings::{WeaklyDivisibleSemiring, WeightQuantize};
+use crate::{StateId, SymbolTable, TrsVec};
+
+type InnerLazyFst<W, F, B, CD, Cache> = LazyFst<W, DeterminizeFstOp<W, F, CD, B>, Cache>;
+
+/// Determinize an FST on demand : the states and transitions of the result are only
+/// computed when they are accessed. Acceptors are determinized directly using the
+/// `CommonDivisor` `CD`, transducers through their Gallic acceptor according to the
+/// `DeterminizeType` of the config. This version is a Delayed FST.
+///
+/// Epsilon transitions are treated as regular symbols.
+pub struct DeterminizeFst<W, F, B, CD = DefaultCommonDivisor, Cache = SimpleHashMapCache<W>>(
+    InnerLazyFst<W, F, B, CD, Cache>,
+)
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+    CD: CommonDivisor<W> + 'static;
+
+impl<W, F, B, CD, Cache> CoreFst<W> for DeterminizeFst<W, F, B, CD, Cache>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+    CD: CommonDivisor<W> + 'static,
+    Cache: FstCache<W> + 'static,
+{
+    type TRS = TrsVec<W>;
+
+    fn start(&self) -> Option<StateId> {
+        self.0.start()
+    }
+
+    fn final_weight(&self, state_id: StateId) -> Result<Option<W>> {
+        self.0.final_weight(state_id)
+    }
+
+    unsafe fn final_weight_unchecked(&self, state_id: StateId) -> Option<W> {
+        self.0.final_weight_unchecked(state_id)
+    }
+
+    fn num_trs(&self, s: StateId) -> Result<usize> {
+        self.0.num_trs(s)
+    }
+
+    unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
+        self.0.num_trs_unchecked(s)
+    }
+
+    fn get_trs(&self, state_id: StateId) -> Result<Self::TRS> {
+        self.0.get_trs(state_id)
+    }
+
+    unsafe fn get_trs_unchecked(&self, state_id: StateId) -> Self::TRS {
+        self.0.get_trs_unchecked(state_id)
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.0.properties()
+    }
+
+    fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_input_epsilons(state)
+    }
+
+    fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_output_epsilons(state)
+    }
+}
+
+impl<'a, W, F, B, CD, Cache> StateIterator<'a> for DeterminizeFst<W, F, B, CD, Cache>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+    CD: CommonDivisor<W> + 'static,
+    Cache: FstCache<W> + 'static,
+{
+    type Iter = <InnerLazyFst<W, F, B, CD, Cache> as StateIterator<'a>>::Iter;
+
+    fn states_iter(&'a self) -> Self::Iter {
+        self.0.states_iter()
+    }
+}
+
+impl<'a, W, F, B, CD, Cache> FstIterator<'a, W> for DeterminizeFst<W, F, B, CD, Cache>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+    CD: CommonDivisor<W> + 'static,
+    Cache: FstCache<W> + 'static,
+{
+    type FstIter = <InnerLazyFst<W, F, B, CD, Cache> as FstIterator<'a, W>>::FstIter;
+
+    fn fst_iter(&'a self) -> Self::FstIter {
+        self.0.fst_iter()
+    }
+}
+
+impl<W, F, B, CD, Cache> Fst<W> for DeterminizeFst<W, F, B, CD, Cache>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+    CD: CommonDivisor<W> + 'static,
+    Cache: FstCache<W> + 'static,
+{
+    fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.input_symbols()
+    }
+
+    fn output_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.output_symbols()
+    }
+
+    fn set_input_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_input_symbols(symt)
+    }
+
+    fn set_output_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_output_symbols(symt)
+    }
+
+    fn take_input_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_input_symbols()
+    }
+
+    fn take_output_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_output_symbols()
+    }
+}
+
+impl<W, F, B, CD, Cache> Debug for DeterminizeFst<W, F, B, CD, Cache>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+    CD: CommonDivisor<W> + 'static,
+    Cache: FstCache<W> + 'static,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<W, F, B> DeterminizeFst<W, F, B>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+{
+    pub fn new(fst: B) -> Result<Self> {
+        Self::new_with_config(fst, DeterminizeConfig::default())
+    }
+
+    pub fn new_with_config(fst: B, config: DeterminizeConfig) -> Result<Self> {
+        Self::new_with_common_divisor(fst, config)
+    }
+}
+
+impl<W, F, B, CD> DeterminizeFst<W, F, B, CD>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+    CD: CommonDivisor<W> + 'static,
+{
+    /// Same as `new_with_config` but with a custom `CommonDivisor` used to determinize
+    /// acceptors.
+    pub fn new_with_common_divisor(fst: B, config: DeterminizeConfig) -> Result<Self> {
+        Self::new_with_cache(fst, config, SimpleHashMapCache::default())
+    }
+}
+
+impl<W, F, B, CD, Cache> DeterminizeFst<W, F, B, CD, Cache>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+    CD: CommonDivisor<W> + 'static,
+    Cache: FstCache<W> + 'static,
+{
+    /// Same as `new_with_common_divisor` but the computed states are stored in `fst_cache`.
+    pub fn new_with_cache(fst: B, config: DeterminizeConfig, fst_cache: Cache) -> Result<Self> {
+        let isymt = fst.borrow().input_symbols().cloned();
+        let osymt = fst.borrow().output_symbols().cloned();
+        let fst_op = DeterminizeFstOp::new(fst, config)?;
+        let lazy_fst = LazyFst::from_op_and_cache(fst_op, fst_cache, isymt, osymt);
+        Ok(DeterminizeFst(lazy_fst))
+    }
+
+    /// Turns the Lazy FST into a static one.
+    pub fn compute<F2: MutableFst<W> + AllocableFst<W>>(&self) -> Result<F2> {
+        self.0.compute()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithms::determinize::{determinize_with_config, DeterminizeType};
+    use crate::algorithms::isomorphic;
+    use crate::fst_impls::VectorFst;
+    use crate::semirings::{Semiring, TropicalWeight};
+    use crate::{Tr, KDELTA};
+
+    #[test]
+    fn test_determinize_fst_sync() {
+        fn is_sync<T: Sync>() {}
+        is_sync::<DeterminizeFst<TropicalWeight, VectorFst<_>, VectorFst<_>>>();
+    }
+
+    #[test]
+    fn test_determinize_fst_acceptor() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(1, 1, 2.0, 2))?;
+        fst.add_tr(1, Tr::new(2, 2, 1.0, 3))?;
+        fst.add_tr(2, Tr::new(2, 2, 3.0, 3))?;
+        fst.add_tr(2, Tr::new(3, 3, 1.0, 3))?;
+        fst.set_final(3, 0.5)?;
+
+        let fst_static: VectorFst<_> = determinize_with_config(&fst, DeterminizeConfig::default())?;
+        let fst_lazy: VectorFst<_> = DeterminizeFst::new(fst)?.compute()?;
+        assert!(isomorphic(&fst_static, &fst_lazy)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_determinize_fst_transducer() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 2, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(1, 3, 2.0, 2))?;
+        fst.add_tr(1, Tr::new(2, 4, 1.0, 3))?;
+        fst.add_tr(2, Tr::new(2, 5, 3.0, 3))?;
+        fst.add_tr(2, Tr::new(3, 6, 1.0, 3))?;
+        fst.set_final(3, 0.5)?;
+
+        for det_type in &[
+            DeterminizeType::DeterminizeNonFunctional,
+            DeterminizeType::DeterminizeDisambiguate,
+        ] {
+            let config = DeterminizeConfig::new(KDELTA, *det_type);
+            let fst_static: VectorFst<_> = determinize_with_config(&fst, config)?;
+            let fst_lazy: VectorFst<_> =
+                DeterminizeFst::new_with_config(fst.clone(), config)?.compute()?;
+            assert!(isomorphic(&fst_static, &fst_lazy)?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_determinize_fst_functional() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 2, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(1, 0, 1.0, 2))?;
+        fst.add_tr(1, Tr::new(2, 0, 1.0, 3))?;
+        fst.add_tr(2, Tr::new(2, 2, 1.0, 3))?;
+        fst.set_final(3, TropicalWeight::one())?;
+
+        let config = DeterminizeConfig::new(KDELTA, DeterminizeType::DeterminizeFunctional);
+        let fst_static: VectorFst<_> = determinize_with_config(&fst, config)?;
+        let fst_lazy = DeterminizeFst::new_with_config(fst, config)?;
+        assert_eq!(fst_lazy.start(), Some(0));
+        let fst_lazy: VectorFst<_> = fst_lazy.compute()?;
+        assert!(isomorphic(&fst_static, &fst_lazy)?);
+        Ok(())
+    }
+}
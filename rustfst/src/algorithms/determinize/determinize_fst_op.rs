@@ -0,0 +1,270 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::determinize::divisors::CommonDivisor;
+use crate::algorithms::determinize::to_gallic_op::{ToGallicFst, ToGallicOp};
+use crate::algorithms::determinize::{
+    DeterminizeConfig, DeterminizeFsa, DeterminizeType, GallicCommonDivisor,
+};
+use crate::algorithms::factor_weight::factor_iterators::{
+    GallicFactor, GallicFactorMin, GallicFactorRestrict,
+};
+use crate::algorithms::factor_weight::{
+    FactorIterator, FactorWeightFst, FactorWeightOptions, FactorWeightType,
+};
+use crate::algorithms::lazy::{FstOp, LazyFst, SimpleHashMapCache, StateTable};
+use crate::algorithms::weight_converters::{FromGallicConverter, ToGallicConverter};
+use crate::algorithms::{FinalTr, WeightConverter};
+use crate::fst_properties::mutable_properties::determinize_properties;
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{CoreFst, Fst};
+use crate::semirings::{
+    GallicWeight, GallicWeightMin, GallicWeightRestrict, SemiringProperties,
+    WeaklyDivisibleSemiring, WeightQuantize,
+};
+use crate::{Semiring, StateId, Tr, Trs, TrsVec, EPS_LABEL, KDELTA};
+
+/// Determinization of the Gallic acceptor built from a transducer, with the final
+/// weights factored so that each of them carries at most one output label.
+type GallicDeterminizeFst<W, G, F, B, FI> = FactorWeightFst<
+    G,
+    DeterminizeFsa<
+        G,
+        ToGallicFst<W, G, F, B>,
+        GallicCommonDivisor,
+        ToGallicFst<W, G, F, B>,
+        Vec<G>,
+    >,
+    DeterminizeFsa<
+        G,
+        ToGallicFst<W, G, F, B>,
+        GallicCommonDivisor,
+        ToGallicFst<W, G, F, B>,
+        Vec<G>,
+    >,
+    FI,
+>;
+
+#[derive(Debug)]
+enum DeterminizeFstImpl<W, F, CD, B>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize,
+    F: Fst<W> + 'static,
+    CD: CommonDivisor<W>,
+    B: Borrow<F> + Debug + 'static,
+{
+    Fsa(DeterminizeFsa<W, F, CD, B, Vec<W>>),
+    Functional(GallicDeterminizeFst<W, GallicWeightRestrict<W>, F, B, GallicFactorRestrict<W>>),
+    NonFunctional(GallicDeterminizeFst<W, GallicWeight<W>, F, B, GallicFactor<W>>),
+    Disambiguate(GallicDeterminizeFst<W, GallicWeightMin<W>, F, B, GallicFactorMin<W>>),
+}
+
+#[derive(Debug)]
+pub struct DeterminizeFstOp<W, F, CD, B>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize,
+    F: Fst<W> + 'static,
+    CD: CommonDivisor<W>,
+    B: Borrow<F> + Debug + 'static,
+{
+    fst_impl: DeterminizeFstImpl<W, F, CD, B>,
+    // Maps the states of the determinized Gallic acceptor to the states of the output
+    // transducer. `None` stands for the super-final state added when a final weight
+    // carries an output label. Unused for acceptors.
+    state_table: StateTable<Option<StateId>>,
+    properties: FstProperties,
+}
+
+fn gallic_determinize<W, G, F, B, FI>(
+    fst: B,
+    delta: f32,
+) -> Result<GallicDeterminizeFst<W, G, F, B, FI>>
+where
+    W: Semiring,
+    G: WeaklyDivisibleSemiring + WeightQuantize,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+    FI: FactorIterator<G>,
+    ToGallicConverter: WeightConverter<W, G>,
+    GallicCommonDivisor: CommonDivisor<G>,
+{
+    let gallic_fst = LazyFst::from_op_and_cache(
+        ToGallicOp::new(fst),
+        SimpleHashMapCache::default(),
+        None,
+        None,
+    );
+    let determinized_fst = DeterminizeFsa::new(gallic_fst, None, delta)?;
+    let factor_opts = FactorWeightOptions {
+        delta: KDELTA,
+        mode: FactorWeightType::FACTOR_FINAL_WEIGHTS,
+        final_ilabel: EPS_LABEL,
+        final_olabel: EPS_LABEL,
+        increment_final_ilabel: false,
+        increment_final_olabel: false,
+    };
+    FactorWeightFst::new(determinized_fst, factor_opts)
+}
+
+impl<W, F, CD, B> DeterminizeFstOp<W, F, CD, B>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize,
+    F: Fst<W> + 'static,
+    CD: CommonDivisor<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+{
+    pub fn new(fst: B, config: DeterminizeConfig) -> Result<Self> {
+        if !W::properties().contains(SemiringProperties::LEFT_SEMIRING) {
+            bail!("DeterminizeFst : weight must be left distributive")
+        }
+        let delta = config.delta;
+        let det_type = config.det_type;
+        let iprops = fst.borrow().properties();
+        let fst_impl = if iprops.contains(FstProperties::ACCEPTOR) {
+            DeterminizeFstImpl::Fsa(DeterminizeFsa::new(fst, None, delta)?)
+        } else {
+            match det_type {
+                DeterminizeType::DeterminizeFunctional => {
+                    DeterminizeFstImpl::Functional(gallic_determinize(fst, delta)?)
+                }
+                DeterminizeType::DeterminizeNonFunctional => {
+                    DeterminizeFstImpl::NonFunctional(gallic_determinize(fst, delta)?)
+                }
+                DeterminizeType::DeterminizeDisambiguate => {
+                    if !W::properties().contains(SemiringProperties::PATH) {
+                        bail!("DeterminizeFst : weight needs to have the path property to disambiguate output")
+                    }
+                    DeterminizeFstImpl::Disambiguate(gallic_determinize(fst, delta)?)
+                }
+            }
+        };
+        let distinct_psubsequential_labels = det_type != DeterminizeType::DeterminizeNonFunctional;
+        Ok(Self {
+            fst_impl,
+            state_table: StateTable::new(),
+            properties: determinize_properties(iprops, false, distinct_psubsequential_labels),
+        })
+    }
+
+    fn gallic_start<G: Semiring, FG: CoreFst<G>>(&self, fst: &FG) -> Option<StateId> {
+        fst.start().map(|s| self.state_table.find_id(Some(s)))
+    }
+
+    fn gallic_trs<G, FG>(&self, fst: &FG, state: StateId) -> Result<TrsVec<W>>
+    where
+        G: Semiring,
+        FG: CoreFst<G>,
+        FromGallicConverter: WeightConverter<G, W>,
+    {
+        let mut trs = vec![];
+        if let Some(gallic_state) = self.state_table.find_tuple(state) {
+            let mut converter = FromGallicConverter {
+                superfinal_label: EPS_LABEL,
+            };
+            for gallic_tr in fst.get_trs(gallic_state)?.trs() {
+                let mut tr = converter.tr_map(gallic_tr)?;
+                tr.nextstate = self.state_table.find_id(Some(tr.nextstate));
+                trs.push(tr);
+            }
+            if let Some(final_tr) = self.gallic_final_tr(fst, gallic_state, &mut converter)? {
+                if final_tr.ilabel != EPS_LABEL || final_tr.olabel != EPS_LABEL {
+                    let superfinal = self.state_table.find_id(None);
+                    trs.push(Tr::new(
+                        final_tr.ilabel,
+                        final_tr.olabel,
+                        final_tr.weight,
+                        superfinal,
+                    ));
+                }
+            }
+        }
+        Ok(TrsVec(Arc::new(trs)))
+    }
+
+    fn gallic_final_weight<G, FG>(&self, fst: &FG, state: StateId) -> Result<Option<W>>
+    where
+        G: Semiring,
+        FG: CoreFst<G>,
+        FromGallicConverter: WeightConverter<G, W>,
+    {
+        let gallic_state = match self.state_table.find_tuple(state) {
+            Some(gallic_state) => gallic_state,
+            None => return Ok(Some(W::one())),
+        };
+        let mut converter = FromGallicConverter {
+            superfinal_label: EPS_LABEL,
+        };
+        match self.gallic_final_tr(fst, gallic_state, &mut converter)? {
+            Some(final_tr) if final_tr.ilabel == EPS_LABEL && final_tr.olabel == EPS_LABEL => {
+                Ok(Some(final_tr.weight))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn gallic_final_tr<G, FG>(
+        &self,
+        fst: &FG,
+        gallic_state: StateId,
+        converter: &mut FromGallicConverter,
+    ) -> Result<Option<FinalTr<W>>>
+    where
+        G: Semiring,
+        FG: CoreFst<G>,
+        FromGallicConverter: WeightConverter<G, W>,
+    {
+        match fst.final_weight(gallic_state)? {
+            Some(weight) => {
+                let final_tr = FinalTr {
+                    ilabel: EPS_LABEL,
+                    olabel: EPS_LABEL,
+                    weight,
+                };
+                Ok(Some(converter.final_tr_map(&final_tr)?))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+impl<W, F, CD, B> FstOp<W> for DeterminizeFstOp<W, F, CD, B>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize,
+    F: Fst<W> + 'static,
+    CD: CommonDivisor<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+{
+    fn compute_start(&self) -> Result<Option<StateId>> {
+        match &self.fst_impl {
+            DeterminizeFstImpl::Fsa(fst) => Ok(fst.start()),
+            DeterminizeFstImpl::Functional(fst) => Ok(self.gallic_start(fst)),
+            DeterminizeFstImpl::NonFunctional(fst) => Ok(self.gallic_start(fst)),
+            DeterminizeFstImpl::Disambiguate(fst) => Ok(self.gallic_start(fst)),
+        }
+    }
+
+    fn compute_trs(&self, id: StateId) -> Result<TrsVec<W>> {
+        match &self.fst_impl {
+            DeterminizeFstImpl::Fsa(fst) => fst.get_trs(id),
+            DeterminizeFstImpl::Functional(fst) => self.gallic_trs(fst, id),
+            DeterminizeFstImpl::NonFunctional(fst) => self.gallic_trs(fst, id),
+            DeterminizeFstImpl::Disambiguate(fst) => self.gallic_trs(fst, id),
+        }
+    }
+
+    fn compute_final_weight(&self, id: StateId) -> Result<Option<W>> {
+        match &self.fst_impl {
+            DeterminizeFstImpl::Fsa(fst) => fst.final_weight(id),
+            DeterminizeFstImpl::Functional(fst) => self.gallic_final_weight(fst, id),
+            DeterminizeFstImpl::NonFunctional(fst) => self.gallic_final_weight(fst, id),
+            DeterminizeFstImpl::Disambiguate(fst) => self.gallic_final_weight(fst, id),
+        }
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.properties
+    }
+}
@@ -1,18 +1,24 @@
 use determinize_fsa::DeterminizeFsa;
 use determinize_fsa_op::DeterminizeFsaOp;
+pub use determinize_fst::DeterminizeFst;
+use determinize_fst_op::DeterminizeFstOp;
 pub use determinize_static::{
     determinize, determinize_with_config, determinize_with_distance, DeterminizeConfig,
 };
-use divisors::{DefaultCommonDivisor, GallicCommonDivisor};
-use element::{DeterminizeElement, DeterminizeStateTuple, DeterminizeTr, WeightedSubset};
+pub use divisors::{CommonDivisor, DefaultCommonDivisor, GallicCommonDivisor};
+use element::DeterminizeTr;
+pub(crate) use element::{DeterminizeElement, DeterminizeStateTuple, WeightedSubset};
 use state_table::DeterminizeStateTable;
 
 mod determinize_fsa;
 mod determinize_fsa_op;
+mod determinize_fst;
+mod determinize_fst_op;
 mod determinize_static;
 mod divisors;
 mod element;
 mod state_table;
+mod to_gallic_op;
 
 /// Determinization type.
 #[derive(Debug, Clone, PartialEq, PartialOrd, Copy)]
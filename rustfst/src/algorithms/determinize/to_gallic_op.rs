@@ -0,0 +1,95 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::lazy::{FstOp, LazyFst, SimpleHashMapCache};
+use crate::algorithms::weight_converters::ToGallicConverter;
+use crate::algorithms::{FinalTr, WeightConverter};
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::Fst;
+use crate::semirings::Semiring;
+use crate::{StateId, Trs, TrsVec, EPS_LABEL};
+
+/// Lazy view of an FST as an acceptor over Gallic weights. Used to determinize
+/// transducers on demand.
+pub type ToGallicFst<W, G, F, B> = LazyFst<G, ToGallicOp<W, G, F, B>, SimpleHashMapCache<G>>;
+
+#[derive(Debug)]
+pub struct ToGallicOp<W, G, F, B>
+where
+    W: Semiring,
+    G: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+{
+    fst: B,
+    properties: FstProperties,
+    ghost: PhantomData<(W, G, F)>,
+}
+
+impl<W, G, F, B> ToGallicOp<W, G, F, B>
+where
+    W: Semiring,
+    G: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    ToGallicConverter: WeightConverter<W, G>,
+{
+    pub fn new(fst: B) -> Self {
+        // Output labels are moved to the weights : the result is always an acceptor.
+        let properties =
+            ToGallicConverter {}.properties(fst.borrow().properties()) | FstProperties::ACCEPTOR;
+        Self {
+            fst,
+            properties,
+            ghost: PhantomData,
+        }
+    }
+}
+
+impl<W, G, F, B> FstOp<G> for ToGallicOp<W, G, F, B>
+where
+    W: Semiring,
+    G: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    ToGallicConverter: WeightConverter<W, G>,
+{
+    fn compute_start(&self) -> Result<Option<StateId>> {
+        Ok(self.fst.borrow().start())
+    }
+
+    fn compute_trs(&self, id: StateId) -> Result<TrsVec<G>> {
+        let mut converter = ToGallicConverter {};
+        let trs = self
+            .fst
+            .borrow()
+            .get_trs(id)?
+            .trs()
+            .iter()
+            .map(|tr| converter.tr_map(tr))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(TrsVec(Arc::new(trs)))
+    }
+
+    fn compute_final_weight(&self, id: StateId) -> Result<Option<G>> {
+        match self.fst.borrow().final_weight(id)? {
+            Some(weight) => {
+                let final_tr = FinalTr {
+                    ilabel: EPS_LABEL,
+                    olabel: EPS_LABEL,
+                    weight,
+                };
+                Ok(Some(ToGallicConverter {}.final_tr_map(&final_tr)?.weight))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.properties
+    }
+}
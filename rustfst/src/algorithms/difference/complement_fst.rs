@@ -0,0 +1,195 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::difference::complement_op::ComplementOp;
+use crate::algorithms::lazy::{FstCache, LazyFst, SimpleHashMapCache};
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{CoreFst, ExpandedFst, Fst, FstIterator, StateIterator};
+use crate::semirings::Semiring;
+use crate::{StateId, SymbolTable, TrsVec};
+
+type InnerLazyFst<W, F, B, Cache> = LazyFst<W, ComplementOp<W, F, B>, Cache>;
+
+/// Complement of an unweighted, epsilon-free and deterministic acceptor. The
+/// complement accepts all the strings not accepted by the input FST.
+///
+/// The states of the input FST are kept with the same ids and an extra final state,
+/// the sink, is added with id `fst.num_states()`. Final states become non-final and
+/// vice versa. The transitions to the sink on all the labels not leaving a state are not
+/// materialized : they are only visible through the [`ComplementMatcher`](super::ComplementMatcher)
+/// which must be used to compose with a `ComplementFst`.
+pub struct ComplementFst<
+    W: Semiring,
+    F: ExpandedFst<W>,
+    B: Borrow<F>,
+    Cache = SimpleHashMapCache<W>,
+> {
+    fst: InnerLazyFst<W, F, B, Cache>,
+    sink: StateId,
+}
+
+impl<W, F, B, Cache> CoreFst<W> for ComplementFst<W, F, B, Cache>
+where
+    W: Semiring,
+    F: ExpandedFst<W>,
+    B: Borrow<F>,
+    Cache: FstCache<W>,
+{
+    type TRS = TrsVec<W>;
+
+    fn start(&self) -> Option<StateId> {
+        self.fst.start()
+    }
+
+    fn final_weight(&self, state_id: StateId) -> Result<Option<W>> {
+        self.fst.final_weight(state_id)
+    }
+
+    unsafe fn final_weight_unchecked(&self, state_id: StateId) -> Option<W> {
+        self.fst.final_weight_unchecked(state_id)
+    }
+
+    fn num_trs(&self, s: StateId) -> Result<usize> {
+        self.fst.num_trs(s)
+    }
+
+    unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
+        self.fst.num_trs_unchecked(s)
+    }
+
+    fn get_trs(&self, state_id: StateId) -> Result<Self::TRS> {
+        self.fst.get_trs(state_id)
+    }
+
+    unsafe fn get_trs_unchecked(&self, state_id: StateId) -> Self::TRS {
+        self.fst.get_trs_unchecked(state_id)
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.fst.properties()
+    }
+
+    fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
+        self.fst.num_input_epsilons(state)
+    }
+
+    fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
+        self.fst.num_output_epsilons(state)
+    }
+}
+
+impl<'a, W, F, B, Cache> StateIterator<'a> for ComplementFst<W, F, B, Cache>
+where
+    W: Semiring,
+    F: ExpandedFst<W> + 'a,
+    B: Borrow<F> + 'a,
+    Cache: FstCache<W> + 'a,
+{
+    type Iter = <InnerLazyFst<W, F, B, Cache> as StateIterator<'a>>::Iter;
+
+    fn states_iter(&'a self) -> Self::Iter {
+        self.fst.states_iter()
+    }
+}
+
+impl<'a, W, F, B, Cache> FstIterator<'a, W> for ComplementFst<W, F, B, Cache>
+where
+    W: Semiring,
+    F: ExpandedFst<W> + 'a,
+    B: Borrow<F> + 'a,
+    Cache: FstCache<W> + 'a,
+{
+    type FstIter = <InnerLazyFst<W, F, B, Cache> as FstIterator<'a, W>>::FstIter;
+
+    fn fst_iter(&'a self) -> Self::FstIter {
+        self.fst.fst_iter()
+    }
+}
+
+impl<W, F, B, Cache> Fst<W> for ComplementFst<W, F, B, Cache>
+where
+    W: Semiring,
+    F: ExpandedFst<W> + 'static,
+    B: Borrow<F> + 'static,
+    Cache: FstCache<W> + 'static,
+{
+    fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.fst.input_symbols()
+    }
+
+    fn output_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.fst.output_symbols()
+    }
+
+    fn set_input_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.fst.set_input_symbols(symt)
+    }
+
+    fn set_output_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.fst.set_output_symbols(symt)
+    }
+
+    fn take_input_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.fst.take_input_symbols()
+    }
+
+    fn take_output_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.fst.take_output_symbols()
+    }
+}
+
+impl<W, F, B, Cache> Debug for ComplementFst<W, F, B, Cache>
+where
+    W: Semiring,
+    F: ExpandedFst<W>,
+    B: Borrow<F>,
+    Cache: FstCache<W>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ComplementFst")
+            .field("fst", &self.fst)
+            .field("sink", &self.sink)
+            .finish()
+    }
+}
+
+impl<W, F, B> ComplementFst<W, F, B>
+where
+    W: Semiring,
+    F: ExpandedFst<W>,
+    B: Borrow<F>,
+{
+    pub fn new(fst: B) -> Self {
+        Self::new_with_cache(fst, SimpleHashMapCache::default())
+    }
+}
+
+impl<W, F, B, Cache> ComplementFst<W, F, B, Cache>
+where
+    W: Semiring,
+    F: ExpandedFst<W>,
+    B: Borrow<F>,
+    Cache: FstCache<W>,
+{
+    /// Same as `new` but the computed states are stored in `fst_cache`.
+    pub fn new_with_cache(fst: B, fst_cache: Cache) -> Self {
+        let isymt = fst.borrow().input_symbols().cloned();
+        let osymt = fst.borrow().output_symbols().cloned();
+        let sink = fst.borrow().num_states() as StateId;
+        let fst_op = ComplementOp::new(fst);
+        let lazy_fst = LazyFst::from_op_and_cache(fst_op, fst_cache, isymt, osymt);
+        ComplementFst {
+            fst: lazy_fst,
+            sink,
+        }
+    }
+
+    /// Id of the sink state, reached when reading a label that
+    /// doesn't match any transition of the input FST.
+    pub fn sink(&self) -> StateId {
+        self.sink
+    }
+}
@@ -0,0 +1,113 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::compose::matchers::{
+    IterItemMatcher, MatchType, Matcher, MatcherFlags, REQUIRE_PRIORITY,
+};
+use crate::algorithms::difference::ComplementFst;
+use crate::fst_traits::{CoreFst, ExpandedFst};
+use crate::semirings::Semiring;
+use crate::{Label, StateId, Tr, Trs, EPS_LABEL, NO_LABEL};
+
+/// Matcher on a [`ComplementFst`]. Any label that doesn't match a transition leaving the
+/// current state is matched by the implicit transition to the sink state. The sink state
+/// matches all the labels.
+///
+/// The matching is always performed on this side of the composition.
+pub struct ComplementMatcher<W, F, B>
+where
+    W: Semiring,
+    F: ExpandedFst<W> + 'static,
+    B: Borrow<F> + 'static,
+{
+    fst: Arc<ComplementFst<W, F, B>>,
+    match_type: MatchType,
+}
+
+impl<W, F, B> Debug for ComplementMatcher<W, F, B>
+where
+    W: Semiring,
+    F: ExpandedFst<W> + 'static,
+    B: Borrow<F> + 'static,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ComplementMatcher")
+            .field("fst", &self.fst)
+            .field("match_type", &self.match_type)
+            .finish()
+    }
+}
+
+impl<W, F, B> Clone for ComplementMatcher<W, F, B>
+where
+    W: Semiring,
+    F: ExpandedFst<W> + 'static,
+    B: Borrow<F> + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            fst: Arc::clone(&self.fst),
+            match_type: self.match_type,
+        }
+    }
+}
+
+impl<W, F, B> Matcher<W, ComplementFst<W, F, B>, Arc<ComplementFst<W, F, B>>>
+    for ComplementMatcher<W, F, B>
+where
+    W: Semiring,
+    F: ExpandedFst<W> + 'static,
+    B: Borrow<F> + 'static,
+{
+    type Iter = std::option::IntoIter<IterItemMatcher<W>>;
+
+    fn new(fst: Arc<ComplementFst<W, F, B>>, match_type: MatchType) -> Result<Self> {
+        Ok(Self { fst, match_type })
+    }
+
+    fn iter(&self, state: StateId, label: Label) -> Result<Self::Iter> {
+        // The complement is epsilon-free : only the epsilon loop matches epsilon
+        // and there is no non-consuming transition to match NoLabel.
+        if label == EPS_LABEL {
+            return Ok(Some(IterItemMatcher::EpsLoop).into_iter());
+        }
+        if label == NO_LABEL {
+            return Ok(None.into_iter());
+        }
+        let sink = self.fst.sink();
+        if state != sink {
+            let trs = self.fst.get_trs(state)?;
+            let tr = trs.trs().iter().find(|tr| match self.match_type {
+                MatchType::MatchInput => tr.ilabel == label,
+                _ => tr.olabel == label,
+            });
+            if let Some(tr) = tr {
+                return Ok(Some(IterItemMatcher::Tr(tr.clone())).into_iter());
+            }
+        }
+        Ok(Some(IterItemMatcher::Tr(Tr::new(label, label, W::one(), sink))).into_iter())
+    }
+
+    fn final_weight(&self, state: StateId) -> Result<Option<W>> {
+        self.fst.final_weight(state)
+    }
+
+    fn match_type(&self, _test: bool) -> Result<MatchType> {
+        Ok(self.match_type)
+    }
+
+    fn flags(&self) -> MatcherFlags {
+        MatcherFlags::REQUIRE_MATCH
+    }
+
+    fn priority(&self, _state: StateId) -> Result<usize> {
+        Ok(REQUIRE_PRIORITY)
+    }
+
+    fn fst(&self) -> &Arc<ComplementFst<W, F, B>> {
+        &self.fst
+    }
+}
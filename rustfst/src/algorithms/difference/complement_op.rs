@@ -0,0 +1,69 @@
+use std::borrow::Borrow;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::lazy::FstOp;
+use crate::fst_properties::mutable_properties::complement_properties;
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::ExpandedFst;
+use crate::semirings::Semiring;
+use crate::{StateId, Trs, TrsVec};
+
+pub struct ComplementOp<W: Semiring, F: ExpandedFst<W>, B: Borrow<F>> {
+    fst: B,
+    sink: StateId,
+    properties: FstProperties,
+    w: PhantomData<(W, F)>,
+}
+
+impl<W: Semiring, F: ExpandedFst<W>, B: Borrow<F>> std::fmt::Debug for ComplementOp<W, F, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ComplementOp {{ sink : {:?}, fst : {:?} }}",
+            self.sink,
+            self.fst.borrow()
+        )
+    }
+}
+
+impl<W: Semiring, F: ExpandedFst<W>, B: Borrow<F>> ComplementOp<W, F, B> {
+    pub fn new(fst: B) -> Self {
+        let sink = fst.borrow().num_states() as StateId;
+        let properties = complement_properties(fst.borrow().properties());
+        Self {
+            fst,
+            sink,
+            properties,
+            w: PhantomData,
+        }
+    }
+}
+
+impl<W: Semiring, F: ExpandedFst<W>, B: Borrow<F>> FstOp<W> for ComplementOp<W, F, B> {
+    fn compute_start(&self) -> Result<Option<StateId>> {
+        Ok(Some(self.fst.borrow().start().unwrap_or(self.sink)))
+    }
+
+    fn compute_trs(&self, state: StateId) -> Result<TrsVec<W>> {
+        if state == self.sink {
+            return Ok(TrsVec::default());
+        }
+        let trs = self.fst.borrow().get_trs(state)?;
+        Ok(TrsVec(Arc::new(trs.trs().to_vec())))
+    }
+
+    fn compute_final_weight(&self, state: StateId) -> Result<Option<W>> {
+        if state == self.sink || self.fst.borrow().final_weight(state)?.is_none() {
+            Ok(Some(W::one()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.properties
+    }
+}
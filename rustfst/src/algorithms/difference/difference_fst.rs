@@ -0,0 +1,220 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::compose::compose_filters::SequenceComposeFilterBuilder;
+use crate::algorithms::compose::matchers::{MatchType, Matcher, SortedMatcher};
+use crate::algorithms::compose::{ComposeFst, ComposeFstOpOptions};
+use crate::algorithms::difference::{ComplementFst, ComplementMatcher};
+use crate::fst_properties::{compute_fst_properties, FstProperties};
+use crate::fst_traits::{
+    AllocableFst, CoreFst, ExpandedFst, Fst, FstIterator, MutableFst, StateIterator,
+};
+use crate::semirings::Semiring;
+use crate::{StateId, SymbolTable, TrsVec};
+
+type InnerComposeFst<W, F1, F2, B1, B2> = ComposeFst<
+    W,
+    F1,
+    ComplementFst<W, F2, B2>,
+    B1,
+    Arc<ComplementFst<W, F2, B2>>,
+    SortedMatcher<W, F1, B1>,
+    ComplementMatcher<W, F2, B2>,
+    SequenceComposeFilterBuilder<
+        W,
+        F1,
+        ComplementFst<W, F2, B2>,
+        B1,
+        Arc<ComplementFst<W, F2, B2>>,
+        SortedMatcher<W, F1, B1>,
+        ComplementMatcher<W, F2, B2>,
+    >,
+>;
+
+/// Computes the difference between two FSAs. This version is a Delayed FST.
+///
+/// Only strings that are in the first automaton but not in the second are retained
+/// in the result. The first argument must be an acceptor and the second argument must
+/// be an unweighted, epsilon-free and deterministic acceptor. The result is computed
+/// by composing the first acceptor with the complement of the second one.
+pub struct DifferenceFst<W, F1, F2, B1, B2>(InnerComposeFst<W, F1, F2, B1, B2>)
+where
+    W: Semiring,
+    F1: ExpandedFst<W>,
+    F2: ExpandedFst<W> + 'static,
+    B1: Borrow<F1> + Debug + Clone,
+    B2: Borrow<F2> + 'static;
+
+impl<W, F1, F2, B1, B2> CoreFst<W> for DifferenceFst<W, F1, F2, B1, B2>
+where
+    W: Semiring,
+    F1: ExpandedFst<W>,
+    F2: ExpandedFst<W> + 'static,
+    B1: Borrow<F1> + Debug + Clone,
+    B2: Borrow<F2> + 'static,
+{
+    type TRS = TrsVec<W>;
+
+    fn start(&self) -> Option<StateId> {
+        self.0.start()
+    }
+
+    fn final_weight(&self, state_id: StateId) -> Result<Option<W>> {
+        self.0.final_weight(state_id)
+    }
+
+    unsafe fn final_weight_unchecked(&self, state_id: StateId) -> Option<W> {
+        self.0.final_weight_unchecked(state_id)
+    }
+
+    fn num_trs(&self, s: StateId) -> Result<usize> {
+        self.0.num_trs(s)
+    }
+
+    unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
+        self.0.num_trs_unchecked(s)
+    }
+
+    fn get_trs(&self, state_id: StateId) -> Result<Self::TRS> {
+        self.0.get_trs(state_id)
+    }
+
+    unsafe fn get_trs_unchecked(&self, state_id: StateId) -> Self::TRS {
+        self.0.get_trs_unchecked(state_id)
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.0.properties()
+    }
+
+    fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_input_epsilons(state)
+    }
+
+    fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_output_epsilons(state)
+    }
+}
+
+impl<'a, W, F1, F2, B1, B2> StateIterator<'a> for DifferenceFst<W, F1, F2, B1, B2>
+where
+    W: Semiring,
+    F1: ExpandedFst<W> + 'a,
+    F2: ExpandedFst<W> + 'static,
+    B1: Borrow<F1> + Debug + Clone + 'a,
+    B2: Borrow<F2> + 'static,
+{
+    type Iter = <InnerComposeFst<W, F1, F2, B1, B2> as StateIterator<'a>>::Iter;
+
+    fn states_iter(&'a self) -> Self::Iter {
+        self.0.states_iter()
+    }
+}
+
+impl<'a, W, F1, F2, B1, B2> FstIterator<'a, W> for DifferenceFst<W, F1, F2, B1, B2>
+where
+    W: Semiring,
+    F1: ExpandedFst<W> + 'a,
+    F2: ExpandedFst<W> + 'static,
+    B1: Borrow<F1> + Debug + Clone + 'a,
+    B2: Borrow<F2> + 'static,
+{
+    type FstIter = <InnerComposeFst<W, F1, F2, B1, B2> as FstIterator<'a, W>>::FstIter;
+
+    fn fst_iter(&'a self) -> Self::FstIter {
+        self.0.fst_iter()
+    }
+}
+
+impl<W, F1, F2, B1, B2> Fst<W> for DifferenceFst<W, F1, F2, B1, B2>
+where
+    W: Semiring,
+    F1: ExpandedFst<W> + 'static,
+    F2: ExpandedFst<W> + 'static,
+    B1: Borrow<F1> + Debug + Clone + 'static,
+    B2: Borrow<F2> + 'static,
+{
+    fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.input_symbols()
+    }
+
+    fn output_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.output_symbols()
+    }
+
+    fn set_input_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_input_symbols(symt)
+    }
+
+    fn set_output_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_output_symbols(symt)
+    }
+
+    fn take_input_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_input_symbols()
+    }
+
+    fn take_output_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_output_symbols()
+    }
+}
+
+impl<W, F1, F2, B1, B2> Debug for DifferenceFst<W, F1, F2, B1, B2>
+where
+    W: Semiring,
+    F1: ExpandedFst<W>,
+    F2: ExpandedFst<W> + 'static,
+    B1: Borrow<F1> + Debug + Clone,
+    B2: Borrow<F2> + 'static,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<W, F1, F2, B1, B2> DifferenceFst<W, F1, F2, B1, B2>
+where
+    W: Semiring,
+    F1: ExpandedFst<W>,
+    F2: ExpandedFst<W> + 'static,
+    B1: Borrow<F1> + Debug + Clone,
+    B2: Borrow<F2> + 'static,
+{
+    pub fn new(fst1: B1, fst2: B2) -> Result<Self> {
+        let mut known = FstProperties::empty();
+        let props1 =
+            compute_fst_properties(fst1.borrow(), FstProperties::ACCEPTOR, &mut known, true)?;
+        if !props1.contains(FstProperties::ACCEPTOR) {
+            bail!("DifferenceFst: 1st argument must be an acceptor");
+        }
+
+        let mask2 = FstProperties::ACCEPTOR
+            | FstProperties::UNWEIGHTED
+            | FstProperties::NO_EPSILONS
+            | FstProperties::I_DETERMINISTIC;
+        let props2 = compute_fst_properties(fst2.borrow(), mask2, &mut known, true)?;
+        if !props2.contains(mask2) {
+            bail!(
+                "DifferenceFst: 2nd argument must be an unweighted, epsilon-free and \
+                 deterministic acceptor. Missing properties : {:?}",
+                mask2 - props2
+            );
+        }
+
+        let fst2 = Arc::new(ComplementFst::new(fst2));
+        let matcher1 = SortedMatcher::new(fst1.clone(), MatchType::MatchOutput)?;
+        let matcher2 = ComplementMatcher::new(Arc::clone(&fst2), MatchType::MatchInput)?;
+        let opts = ComposeFstOpOptions::new(matcher1, matcher2, None, None);
+        Ok(DifferenceFst(ComposeFst::new_with_options(
+            fst1, fst2, opts,
+        )?))
+    }
+
+    /// Turns the Lazy FST into a static one.
+    pub fn compute<F: MutableFst<W> + AllocableFst<W>>(&self) -> Result<F> {
+        self.0.compute()
+    }
+}
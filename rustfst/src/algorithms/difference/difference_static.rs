@@ -0,0 +1,170 @@
+use anyhow::Result;
+
+use crate::algorithms::connect;
+use crate::algorithms::difference::DifferenceFst;
+use crate::fst_traits::{AllocableFst, ExpandedFst, MutableFst};
+use crate::semirings::Semiring;
+
+/// This operation computes the difference between two FSAs.
+/// Only strings that are in the first automaton but not in the second are retained
+/// in the result.
+///
+/// The first argument must be an acceptor; the second argument must be an unweighted,
+/// epsilon-free, deterministic acceptor. An error is returned otherwise.
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use anyhow::Result;
+/// # use rustfst::utils::acceptor;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::Fst;
+/// # use rustfst::FstPath;
+/// # use rustfst::algorithms::union::union;
+/// # use rustfst::algorithms::difference::difference;
+/// # use std::collections::HashSet;
+/// # fn main() -> Result<()> {
+/// let mut fst_a : VectorFst<TropicalWeight> = fst![1, 2];
+/// let fst_c : VectorFst<TropicalWeight> = fst![1, 3];
+/// union(&mut fst_a, &fst_c)?;
+/// let fst_b : VectorFst<TropicalWeight> = fst![1, 2];
+///
+/// let fst_res : VectorFst<_> = difference(&fst_a, &fst_b)?;
+/// let paths : HashSet<_> = fst_res.paths_iter().collect();
+///
+/// let mut paths_ref = HashSet::<FstPath<TropicalWeight>>::new();
+/// paths_ref.insert(fst_path![1, 3]);
+///
+/// assert_eq!(paths, paths_ref);
+/// # Ok(())
+/// # }
+/// ```
+pub fn difference<W, F1, F2, F3>(fst1: &F1, fst2: &F2) -> Result<F3>
+where
+    W: Semiring,
+    F1: ExpandedFst<W>,
+    F2: ExpandedFst<W> + Clone + 'static,
+    F3: MutableFst<W> + AllocableFst<W>,
+{
+    // The complement of fst2 is a delayed FST that needs to own its input.
+    let difference_fst: DifferenceFst<_, F1, F2, _, _> = DifferenceFst::new(fst1, fst2.clone())?;
+    let mut ofst: F3 = difference_fst.compute()?;
+    connect(&mut ofst)?;
+    Ok(ofst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    use crate::algorithms::union::union;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_properties::FstProperties;
+    use crate::fst_traits::{CoreFst, Fst};
+    use crate::semirings::{Semiring, TropicalWeight};
+    use crate::utils::{acceptor, transducer};
+    use crate::{FstPath, Label, Tr, EPS_LABEL};
+
+    fn acc(labels: &[Label]) -> VectorFst<TropicalWeight> {
+        acceptor(labels, TropicalWeight::one())
+    }
+
+    fn paths(fst: &VectorFst<TropicalWeight>) -> HashSet<FstPath<TropicalWeight>> {
+        fst.paths_iter().collect()
+    }
+
+    #[test]
+    fn test_difference_removes_strings() -> Result<()> {
+        let mut fst1 = acc(&[1, 2]);
+        union(&mut fst1, &acc(&[1, 3]))?;
+        union(&mut fst1, &acc(&[4]))?;
+
+        let mut fst2 = VectorFst::<TropicalWeight>::new();
+        fst2.add_states(4);
+        fst2.set_start(0)?;
+        fst2.add_tr(0, Tr::new(1, 1, TropicalWeight::one(), 1))?;
+        fst2.add_tr(1, Tr::new(2, 2, TropicalWeight::one(), 2))?;
+        fst2.add_tr(0, Tr::new(4, 4, TropicalWeight::one(), 3))?;
+        fst2.set_final(2, TropicalWeight::one())?;
+        fst2.set_final(3, TropicalWeight::one())?;
+
+        let res: VectorFst<_> = difference(&fst1, &fst2)?;
+        let mut paths_ref = HashSet::new();
+        paths_ref.insert(FstPath::new(vec![1, 3], vec![1, 3], TropicalWeight::one()));
+        assert_eq!(paths(&res), paths_ref);
+        assert!(res.properties().contains(FstProperties::ACCEPTOR));
+        Ok(())
+    }
+
+    #[test]
+    fn test_difference_prefixes_and_epsilons() -> Result<()> {
+        // fst1 accepts "1", "1 2" and "1 2 2", with an epsilon transition.
+        let mut fst1 = VectorFst::<TropicalWeight>::new();
+        fst1.add_states(5);
+        fst1.set_start(0)?;
+        fst1.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+        fst1.add_tr(1, Tr::new(EPS_LABEL, EPS_LABEL, 2.0, 2))?;
+        fst1.add_tr(2, Tr::new(2, 2, 3.0, 3))?;
+        fst1.add_tr(3, Tr::new(2, 2, 4.0, 4))?;
+        fst1.set_final(1, 0.5)?;
+        fst1.set_final(3, 0.0)?;
+        fst1.set_final(4, 0.0)?;
+
+        // fst2 only accepts "1 2".
+        let fst2 = acc(&[1, 2]);
+
+        let res: VectorFst<_> = difference(&fst1, &fst2)?;
+        let mut paths_ref = HashSet::new();
+        paths_ref.insert(FstPath::new(vec![1], vec![1], TropicalWeight::new(1.5)));
+        paths_ref.insert(FstPath::new(
+            vec![1, 2, 2],
+            vec![1, 2, 2],
+            TropicalWeight::new(10.0),
+        ));
+        assert_eq!(paths(&res), paths_ref);
+        Ok(())
+    }
+
+    #[test]
+    fn test_difference_with_empty_fst() -> Result<()> {
+        let mut fst1 = acc(&[1, 2]);
+        union(&mut fst1, &acc(&[3]))?;
+        let fst2 = VectorFst::<TropicalWeight>::new();
+
+        let res: VectorFst<_> = difference(&fst1, &fst2)?;
+        assert_eq!(paths(&res), paths(&fst1));
+
+        let res: VectorFst<_> = difference(&fst2, &acc(&[1, 2]))?;
+        assert_eq!(res.start(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_difference_invalid_arguments() -> Result<()> {
+        let fst1 = acc(&[1, 2]);
+        let not_acceptor: VectorFst<TropicalWeight> =
+            transducer(&[1, 2], &[3, 4], TropicalWeight::one());
+        let weighted: VectorFst<TropicalWeight> = acceptor(&[1, 2], TropicalWeight::new(3.0));
+        let mut with_eps = VectorFst::<TropicalWeight>::new();
+        with_eps.add_states(2);
+        with_eps.set_start(0)?;
+        with_eps.add_tr(0, Tr::new(EPS_LABEL, EPS_LABEL, TropicalWeight::one(), 1))?;
+        with_eps.set_final(1, TropicalWeight::one())?;
+        let mut non_det = acc(&[1, 2]);
+        union(&mut non_det, &acc(&[1, 3]))?;
+
+        for (fst1, fst2) in &[
+            (&not_acceptor, &fst1),
+            (&fst1, &not_acceptor),
+            (&fst1, &weighted),
+            (&fst1, &with_eps),
+            (&fst1, &non_det),
+        ] {
+            let res: Result<VectorFst<_>> = difference(*fst1, *fst2);
+            assert!(res.is_err());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,10 @@
+mod complement_fst;
+mod complement_matcher;
+mod complement_op;
+mod difference_fst;
+mod difference_static;
+
+pub use complement_fst::ComplementFst;
+pub use complement_matcher::ComplementMatcher;
+pub use difference_fst::DifferenceFst;
+pub use difference_static::difference;
@@ -0,0 +1,585 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::determinize::{DeterminizeElement, DeterminizeStateTuple, WeightedSubset};
+use crate::algorithms::lazy::StateTable;
+use crate::algorithms::tr_compares::{ILabelCompare, TrCompare};
+use crate::algorithms::union_find::UnionFind;
+use crate::algorithms::{connect, fst_convert_from_ref, tr_sort};
+use crate::fst_impls::VectorFst;
+use crate::fst_properties::{compute_fst_properties, FstProperties};
+use crate::fst_traits::{AllocableFst, CoreFst, ExpandedFst, MutableFst};
+use crate::semirings::{
+    DivideType, Semiring, SemiringProperties, WeaklyDivisibleSemiring, WeightQuantize,
+};
+use crate::{Label, StateId, Tr, Trs, EPS_LABEL, KDELTA};
+
+/// Configuration for [`disambiguate_with_config`].
+#[derive(Clone, Debug, Copy, PartialOrd, PartialEq)]
+pub struct DisambiguateConfig {
+    /// Quantization delta used when comparing the weighted subsets.
+    pub delta: f32,
+}
+
+impl DisambiguateConfig {
+    pub fn new(delta: f32) -> Self {
+        Self { delta }
+    }
+
+    pub fn with_delta(self, delta: f32) -> Self {
+        Self { delta }
+    }
+}
+
+impl Default for DisambiguateConfig {
+    fn default() -> Self {
+        Self { delta: KDELTA }
+    }
+}
+
+/// Compare input labels and then next states.
+struct ILabelNextStateCompare {}
+
+impl TrCompare for ILabelNextStateCompare {
+    fn compare<W: Semiring>(a: &Tr<W>, b: &Tr<W>) -> Ordering {
+        a.ilabel
+            .cmp(&b.ilabel)
+            .then_with(|| a.nextstate.cmp(&b.nextstate))
+    }
+
+    fn properties(inprops: FstProperties) -> FstProperties {
+        ILabelCompare::properties(inprops)
+    }
+}
+
+/// A transition identified by its source state and its position among the trs of
+/// that state. A position of `None` stands for the super-final transition.
+type TrId = (StateId, Option<usize>);
+
+/// Relation that determines if two states share a common future : states `s1` and
+/// `s2` are related iff there is a path from `s1` to a final state that has the same
+/// label as some path from `s2` to a final state.
+struct CommonFuture {
+    related: HashSet<(StateId, StateId)>,
+}
+
+impl CommonFuture {
+    fn new<W: Semiring, F: ExpandedFst<W>>(fst: &F) -> Result<Self> {
+        let start = match fst.start() {
+            Some(start) => start,
+            None => {
+                return Ok(Self {
+                    related: HashSet::new(),
+                })
+            }
+        };
+
+        // Explores the composition of the acceptor with itself.
+        let mut pairs = vec![(start, start)];
+        let mut pair_ids = HashMap::new();
+        pair_ids.insert((start, start), 0);
+        let mut predecessors: Vec<Vec<usize>> = vec![vec![]];
+        let mut coaccessible = VecDeque::new();
+        let mut pair_id = 0;
+        while pair_id < pairs.len() {
+            let (s1, s2) = pairs[pair_id];
+            if fst.is_final(s1)? && fst.is_final(s2)? {
+                coaccessible.push_back(pair_id);
+            }
+
+            let trs1 = fst.get_trs(s1)?;
+            let trs2 = fst.get_trs(s2)?;
+            let mut next_pairs = vec![];
+            for tr1 in trs1.trs() {
+                if tr1.ilabel == EPS_LABEL {
+                    next_pairs.push((tr1.nextstate, s2));
+                }
+                for tr2 in trs2.trs().iter().filter(|tr2| tr2.ilabel == tr1.ilabel) {
+                    next_pairs.push((tr1.nextstate, tr2.nextstate));
+                }
+            }
+            for tr2 in trs2.trs().iter().filter(|tr2| tr2.ilabel == EPS_LABEL) {
+                next_pairs.push((s1, tr2.nextstate));
+            }
+
+            for next_pair in next_pairs {
+                let next_id = *pair_ids.entry(next_pair).or_insert_with(|| {
+                    pairs.push(next_pair);
+                    predecessors.push(vec![]);
+                    pairs.len() - 1
+                });
+                predecessors[next_id].push(pair_id);
+            }
+            pair_id += 1;
+        }
+
+        // Keeps the pairs from which a final pair can be reached.
+        let mut is_coaccessible = vec![false; pairs.len()];
+        for pair_id in coaccessible.iter() {
+            is_coaccessible[*pair_id] = true;
+        }
+        while let Some(pair_id) = coaccessible.pop_front() {
+            for predecessor in predecessors[pair_id].iter() {
+                if !is_coaccessible[*predecessor] {
+                    is_coaccessible[*predecessor] = true;
+                    coaccessible.push_back(*predecessor);
+                }
+            }
+        }
+
+        let related = pairs
+            .into_iter()
+            .zip(is_coaccessible)
+            .filter(|(_, coaccessible)| *coaccessible)
+            .map(|(pair, _)| pair)
+            .collect();
+        Ok(Self { related })
+    }
+
+    fn contains(&self, s1: StateId, s2: StateId) -> bool {
+        self.related.contains(&(s1, s2))
+    }
+}
+
+#[derive(Default)]
+struct Disambiguator {
+    // States s1 and s2 are in this relation iff there is a path from the initial
+    // state to s1 that has the same label as some path from the initial state to
+    // s2. Only pairs with s1 <= s2 are stored.
+    coreachable: HashSet<(StateId, StateId)>,
+    // Queue of disambiguation-related states to be processed.
+    queue: VecDeque<(StateId, StateId)>,
+    // Head state in the pre-disambiguation for a given state.
+    head: Vec<StateId>,
+    // Pairs of candidate ambiguous transitions (a, b) : b has the same label and
+    // destination state as a, their source states are coreachable and the head of
+    // the source of b is smaller than the head of the source of a.
+    candidates: Vec<(TrId, TrId)>,
+    // Set of ambiguous transitions to be removed.
+    ambiguous: HashSet<TrId>,
+    // States to merge due to quantization issues.
+    merge: Option<UnionFind>,
+}
+
+impl Disambiguator {
+    /// Outputs an equivalent FST whose states are subsets of states that have a
+    /// future path in common.
+    fn pre_disambiguate<W, F>(&mut self, ifst: &VectorFst<W>, delta: f32) -> Result<F>
+    where
+        W: WeaklyDivisibleSemiring + WeightQuantize,
+        F: MutableFst<W> + AllocableFst<W>,
+    {
+        let mut ofst = F::new();
+        let start = match ifst.start() {
+            Some(start) => start,
+            None => return Ok(ofst),
+        };
+        let common_future = CommonFuture::new(ifst)?;
+
+        let state_table = StateTable::new();
+        let start_tuple = DeterminizeStateTuple {
+            subset: WeightedSubset::from_vec(vec![DeterminizeElement::new(start, W::one())]),
+            filter_state: start,
+        };
+        let ostart = state_table.find_id(start_tuple);
+        ofst.add_state();
+
+        let mut state = ostart;
+        while (state as usize) < ofst.num_states() {
+            let tuple = state_table.find_tuple(state);
+            let head = tuple.filter_state;
+            self.head.push(head);
+
+            if ifst.is_final(head)? {
+                let mut final_weight = W::zero();
+                for elt in tuple.subset.iter() {
+                    if let Some(w) = ifst.final_weight(elt.state)? {
+                        final_weight.plus_assign(elt.weight.times(w)?)?;
+                    }
+                }
+                if !final_weight.is_zero() {
+                    ofst.set_final(state, final_weight)?;
+                }
+            }
+
+            // Each transition of the head state (up to multi-trs) gives a
+            // destination subset headed by its next state.
+            let mut label_map: Vec<(Label, StateId, Vec<DeterminizeElement<W>>)> = vec![];
+            for tr in ifst.get_trs(head)?.trs() {
+                if let Some((label, nextstate, _)) = label_map.last() {
+                    if *label == tr.ilabel && *nextstate == tr.nextstate {
+                        continue;
+                    }
+                }
+                label_map.push((tr.ilabel, tr.nextstate, vec![]));
+            }
+
+            // Adds an element to a destination subset if its state is related to the
+            // head of that subset.
+            for src_elt in tuple.subset.iter() {
+                for tr in ifst.get_trs(src_elt.state)?.trs() {
+                    for (label, dest_head, dest_elts) in label_map.iter_mut() {
+                        if *label == tr.ilabel && common_future.contains(tr.nextstate, *dest_head) {
+                            dest_elts.push(DeterminizeElement::new(
+                                tr.nextstate,
+                                src_elt.weight.times(&tr.weight)?,
+                            ));
+                        }
+                    }
+                }
+            }
+
+            for (label, dest_head, dest_elts) in label_map {
+                let (weight, subset) = norm_subset(dest_elts, delta)?;
+                let dest_tuple = DeterminizeStateTuple {
+                    subset,
+                    filter_state: dest_head,
+                };
+                let nextstate = state_table.find_id(dest_tuple);
+                if nextstate as usize == ofst.num_states() {
+                    ofst.add_state();
+                }
+                ofst.add_tr(state, Tr::new(label, label, weight, nextstate))?;
+            }
+
+            state += 1;
+        }
+
+        ofst.set_start(ostart)?;
+        Ok(ofst)
+    }
+
+    /// Finds transitions that are ambiguous candidates in the result of
+    /// `pre_disambiguate`.
+    fn find_ambiguities<W: Semiring, F: ExpandedFst<W>>(&mut self, fst: &F) -> Result<()> {
+        let start = match fst.start() {
+            Some(start) => start,
+            None => return Ok(()),
+        };
+        self.coreachable.insert((start, start));
+        self.queue.push_back((start, start));
+        while let Some((s1, s2)) = self.queue.pop_front() {
+            self.find_ambiguous_pairs(fst, s1, s2)?;
+        }
+        Ok(())
+    }
+
+    /// Finds transition pairs that are ambiguous candidates from two specified
+    /// source states.
+    fn find_ambiguous_pairs<W: Semiring, F: ExpandedFst<W>>(
+        &mut self,
+        fst: &F,
+        s1: StateId,
+        s2: StateId,
+    ) -> Result<()> {
+        let trs1 = fst.get_trs(s1)?;
+        let trs2 = fst.get_trs(s2)?;
+        let trs2 = trs2.trs();
+        for (pos1, tr1) in trs1.trs().iter().enumerate() {
+            // Trs are sorted by input label.
+            let first = trs2.partition_point(|tr2| tr2.ilabel < tr1.ilabel);
+            for (pos2, tr2) in trs2
+                .iter()
+                .enumerate()
+                .skip(first)
+                .take_while(|(_, tr2)| tr2.ilabel == tr1.ilabel)
+            {
+                // Actual transition is ambiguous.
+                if s1 != s2 && tr1.nextstate == tr2.nextstate {
+                    self.insert_candidate(s1, s2, (s1, Some(pos1)), (s2, Some(pos2)));
+                }
+                let pair = if tr1.nextstate <= tr2.nextstate {
+                    (tr1.nextstate, tr2.nextstate)
+                } else {
+                    (tr2.nextstate, tr1.nextstate)
+                };
+                // Not already marked as coreachable ?
+                if self.coreachable.insert(pair) {
+                    // Only possible if state split by quantization issues.
+                    if pair.0 != pair.1 && self.head[pair.0 as usize] == self.head[pair.1 as usize]
+                    {
+                        self.merge
+                            .get_or_insert_with(|| UnionFind::new(fst.num_states()))
+                            .union(pair.0 as usize, pair.1 as usize);
+                    } else {
+                        self.queue.push_back(pair);
+                    }
+                }
+            }
+        }
+
+        // Super-final transition is ambiguous.
+        if s1 != s2 && fst.is_final(s1)? && fst.is_final(s2)? {
+            self.insert_candidate(s1, s2, (s1, None), (s2, None));
+        }
+        Ok(())
+    }
+
+    fn insert_candidate(&mut self, s1: StateId, s2: StateId, a1: TrId, a2: TrId) {
+        let candidate = if self.head[s1 as usize] > self.head[s2 as usize] {
+            (a1, a2)
+        } else {
+            (a2, a1)
+        };
+        self.candidates.push(candidate);
+    }
+
+    /// Deletes spurious ambiguous transitions (due to quantization).
+    fn remove_splits<W: Semiring, F: MutableFst<W>>(&mut self, ofst: &mut F) -> Result<()> {
+        let mut merge = match self.merge.take() {
+            Some(merge) => merge,
+            None => return Ok(()),
+        };
+
+        // Merges split states to remove spurious ambiguities.
+        for state in 0..ofst.num_states() as StateId {
+            let mut it_tr = ofst.tr_iter_mut(state)?;
+            for idx in 0..it_tr.len() {
+                let nextstate = merge.find(it_tr[idx].nextstate as usize) as StateId;
+                if nextstate != it_tr[idx].nextstate {
+                    it_tr.set_nextstate(idx, nextstate)?;
+                }
+            }
+        }
+
+        // Repeats search for actual ambiguities on modified FST.
+        self.coreachable.clear();
+        self.candidates.clear();
+        self.find_ambiguities(ofst)?;
+        if self.merge.is_some() {
+            bail!("Disambiguate: Unable to remove spurious ambiguities");
+        }
+        Ok(())
+    }
+
+    /// Marks ambiguous transitions to be removed.
+    fn mark_ambiguities(&mut self) {
+        let head = &self.head;
+        self.candidates.sort_by(|(a1, _), (a2, _)| {
+            head[a1.0 as usize]
+                .cmp(&head[a2.0 as usize])
+                .then_with(|| a1.1.cmp(&a2.1))
+        });
+        for (a, b) in self.candidates.iter() {
+            // If b is not to be removed, then a is.
+            if !self.ambiguous.contains(b) {
+                self.ambiguous.insert(*a);
+            }
+        }
+        self.coreachable.clear();
+        self.candidates.clear();
+    }
+
+    /// Deletes actual ambiguous transitions.
+    fn remove_ambiguities<W: Semiring, F: MutableFst<W>>(&mut self, ofst: &mut F) -> Result<()> {
+        if self.ambiguous.is_empty() {
+            return Ok(());
+        }
+        // Adds dead state to redirect ambiguous transitions to be removed.
+        let dead = ofst.add_state();
+        for (state, pos) in self.ambiguous.drain() {
+            match pos {
+                Some(pos) => ofst.tr_iter_mut(state)?.set_nextstate(pos, dead)?,
+                None => ofst.delete_final_weight(state)?,
+            }
+        }
+        connect(ofst)
+    }
+}
+
+/// Merges the elements of a subset with the same state, then divides their weights by
+/// the weight of the transition leading to that subset.
+fn norm_subset<W: WeaklyDivisibleSemiring + WeightQuantize>(
+    elts: Vec<DeterminizeElement<W>>,
+    delta: f32,
+) -> Result<(W, WeightedSubset<W>)> {
+    let mut merged: BTreeMap<StateId, W> = BTreeMap::new();
+    let mut weight = W::zero();
+    for elt in elts {
+        weight.plus_assign(&elt.weight)?;
+        merged
+            .entry(elt.state)
+            .or_insert_with(W::zero)
+            .plus_assign(elt.weight)?;
+    }
+    let mut pairs = Vec::with_capacity(merged.len());
+    for (state, elt_weight) in merged {
+        let mut elt_weight = elt_weight.divide(&weight, DivideType::DivideLeft)?;
+        elt_weight.quantize_assign(delta)?;
+        pairs.push(DeterminizeElement::new(state, elt_weight));
+    }
+    Ok((weight, WeightedSubset::from_vec(pairs)))
+}
+
+/// Disambiguates a weighted acceptor : the result is an equivalent acceptor with no
+/// two successful paths having the same label sequence. Contrary to `determinize`,
+/// the result is not necessarily deterministic and is often smaller.
+///
+/// The weights must form a path semiring (e.g. `TropicalWeight`) : for each label
+/// sequence, only the path with the best weight is kept.
+///
+/// # Example
+/// ```
+/// # use anyhow::Result;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::{MutableFst, Fst};
+/// # use rustfst::algorithms::disambiguate;
+/// # use rustfst::{Tr, FstPath};
+/// # fn main() -> Result<()> {
+/// let mut fst = VectorFst::<TropicalWeight>::new();
+/// fst.add_states(3);
+/// fst.set_start(0)?;
+/// fst.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+/// fst.add_tr(0, Tr::new(1, 1, 2.0, 2))?;
+/// fst.add_tr(1, Tr::new(2, 2, 1.0, 2))?;
+/// fst.add_tr(2, Tr::new(2, 2, 1.0, 2))?;
+/// fst.set_final(2, 0.0)?;
+///
+/// let disambiguated_fst: VectorFst<_> = disambiguate(&fst)?;
+///
+/// // "1 2" only appears once, with its best weight.
+/// let paths: Vec<_> = disambiguated_fst.paths_iter().take(2).collect();
+/// assert!(paths.contains(&FstPath::new(vec![1], vec![1], TropicalWeight::new(2.0))));
+/// assert!(paths.contains(&FstPath::new(vec![1, 2], vec![1, 2], TropicalWeight::new(2.0))));
+/// # Ok(())
+/// # }
+/// ```
+pub fn disambiguate<W, F1, F2>(ifst: &F1) -> Result<F2>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize,
+    F1: ExpandedFst<W>,
+    F2: MutableFst<W> + AllocableFst<W>,
+{
+    disambiguate_with_config(ifst, DisambiguateConfig::default())
+}
+
+/// Disambiguates a weighted acceptor, with a configurable delta for weight comparison.
+/// See [`disambiguate`].
+pub fn disambiguate_with_config<W, F1, F2>(ifst: &F1, config: DisambiguateConfig) -> Result<F2>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize,
+    F1: ExpandedFst<W>,
+    F2: MutableFst<W> + AllocableFst<W>,
+{
+    let weight_props = SemiringProperties::PATH | SemiringProperties::LEFT_SEMIRING;
+    if !W::properties().contains(weight_props) {
+        bail!("Disambiguate: Weight must have path property and be left distributive");
+    }
+    let mut known = FstProperties::empty();
+    let props = compute_fst_properties(ifst, FstProperties::ACCEPTOR, &mut known, true)?;
+    if !props.contains(FstProperties::ACCEPTOR) {
+        bail!("Disambiguate: Input must be an acceptor");
+    }
+
+    let mut sfst: VectorFst<W> = fst_convert_from_ref(ifst);
+    connect(&mut sfst)?;
+    tr_sort(&mut sfst, ILabelNextStateCompare {});
+
+    let mut disambiguator = Disambiguator::default();
+    let mut ofst: F2 = disambiguator.pre_disambiguate(&sfst, config.delta)?;
+    tr_sort(&mut ofst, ILabelNextStateCompare {});
+    disambiguator.find_ambiguities(&ofst)?;
+    disambiguator.remove_splits(&mut ofst)?;
+    disambiguator.mark_ambiguities();
+    disambiguator.remove_ambiguities(&mut ofst)?;
+
+    if let Some(isymt) = ifst.input_symbols() {
+        ofst.set_input_symbols(Arc::clone(isymt));
+    }
+    if let Some(osymt) = ifst.output_symbols() {
+        ofst.set_output_symbols(Arc::clone(osymt));
+    }
+    Ok(ofst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::algorithms::determinize::determinize;
+    use crate::algorithms::rand_equivalent;
+    use crate::fst_traits::Fst;
+    use crate::semirings::TropicalWeight;
+    use crate::FstPath;
+
+    fn ambiguous_fst() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(5);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(1, 1, 2.0, 2))?;
+        fst.add_tr(1, Tr::new(2, 2, 3.0, 3))?;
+        fst.add_tr(1, Tr::new(3, 3, 1.0, 4))?;
+        fst.add_tr(2, Tr::new(2, 2, 1.0, 3))?;
+        fst.add_tr(2, Tr::new(4, 4, 1.0, 4))?;
+        fst.set_final(3, 0.0)?;
+        fst.set_final(4, 0.0)?;
+        Ok(fst)
+    }
+
+    fn sorted_paths(fst: &VectorFst<TropicalWeight>) -> Vec<FstPath<TropicalWeight>> {
+        let mut paths: Vec<_> = fst.paths_iter().collect();
+        paths.sort_by(|a, b| a.ilabels.cmp(&b.ilabels));
+        paths
+    }
+
+    #[test]
+    fn test_disambiguate() -> Result<()> {
+        let fst = ambiguous_fst()?;
+        let disambiguated_fst: VectorFst<_> = disambiguate(&fst)?;
+
+        assert_eq!(
+            sorted_paths(&disambiguated_fst),
+            vec![
+                FstPath::new(vec![1, 2], vec![1, 2], TropicalWeight::new(3.0)),
+                FstPath::new(vec![1, 3], vec![1, 3], TropicalWeight::new(2.0)),
+                FstPath::new(vec![1, 4], vec![1, 4], TropicalWeight::new(3.0)),
+            ]
+        );
+        assert!(rand_equivalent(&fst, &disambiguated_fst, 20, 2022)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_disambiguate_same_paths_as_determinize() -> Result<()> {
+        let fst = ambiguous_fst()?;
+        let disambiguated_fst: VectorFst<_> = disambiguate(&fst)?;
+        let determinized_fst: VectorFst<_> = determinize(&fst)?;
+        assert_eq!(
+            sorted_paths(&disambiguated_fst),
+            sorted_paths(&determinized_fst)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_disambiguate_unambiguous_fst() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(2, 2, 2.0, 2))?;
+        fst.add_tr(1, Tr::new(3, 3, 1.0, 2))?;
+        fst.set_final(2, 0.5)?;
+
+        let disambiguated_fst: VectorFst<_> = disambiguate(&fst)?;
+        assert_eq!(disambiguated_fst.num_states(), 3);
+        assert_eq!(sorted_paths(&disambiguated_fst), sorted_paths(&fst));
+        Ok(())
+    }
+
+    #[test]
+    fn test_disambiguate_transducer() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(2);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 2, 1.0, 1))?;
+        fst.set_final(1, 0.0)?;
+        assert!(disambiguate::<_, _, VectorFst<_>>(&fst).is_err());
+        Ok(())
+    }
+}
@@ -94,7 +94,21 @@ where
     W: Semiring,
     F: MutableFst<W>,
 {
-    let mut encode_mapper = EncodeMapper::new(encode_type);
+    let encode_mapper = EncodeMapper::new(encode_type);
+    encode_with_table(fst, encode_mapper.encode_table)
+}
+
+/// Same as `encode` but re-uses an existing `EncodeTable`. This allows several FSTs
+/// to share the same encoding.
+pub(crate) fn encode_with_table<W, F>(
+    fst: &mut F,
+    encode_table: EncodeTable<W>,
+) -> Result<EncodeTable<W>>
+where
+    W: Semiring,
+    F: MutableFst<W>,
+{
+    let mut encode_mapper = EncodeMapper { encode_table };
     fst.tr_map(&mut encode_mapper)
         .with_context(|| format_err!("Error calling TrMap with EncodeMapper."))?;
     Ok(encode_mapper.encode_table)
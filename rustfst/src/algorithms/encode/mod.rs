@@ -1,5 +1,6 @@
 pub use decode_static::decode;
 pub use encode_static::encode;
+pub(crate) use encode_static::encode_with_table;
 pub use encode_type::EncodeType;
 pub use table::EncodeTable;
 use table::EncodeTableMut;
@@ -13,6 +13,7 @@ pub struct EncodeTuple<W: Semiring> {
     pub weight: W,
 }
 
+#[derive(Clone)]
 pub struct EncodeTableMut<W: Semiring> {
     pub encode_type: EncodeType,
     // FIXME : Store references ?
@@ -20,6 +21,7 @@ pub struct EncodeTableMut<W: Semiring> {
     tuple_to_id: HashMap<EncodeTuple<W>, usize>,
 }
 
+#[derive(Clone)]
 pub struct EncodeTable<W: Semiring>(pub RefCell<EncodeTableMut<W>>);
 
 impl<W: Semiring> EncodeTableMut<W> {
@@ -0,0 +1,173 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::factor_weight::factor_iterators::GallicFactor;
+use crate::algorithms::factor_weight::{factor_weight, FactorWeightOptions, FactorWeightType};
+use crate::algorithms::rm_epsilon::rm_epsilon;
+use crate::algorithms::weight_converters::{FromGallicConverter, ToGallicConverter};
+use crate::algorithms::{fst_convert_from_ref, invert, weight_convert};
+use crate::fst_impls::VectorFst;
+use crate::fst_traits::{AllocableFst, ExpandedFst, MutableFst};
+use crate::semirings::{GallicWeight, WeightQuantize};
+use crate::EPS_LABEL;
+
+#[derive(Debug, Clone, PartialEq, PartialOrd, Copy)]
+/// Different types of epsilon normalization.
+pub enum EpsNormalizeType {
+    /// Input epsilon normalization : input epsilons are pushed after the non-epsilon
+    /// input labels on each path.
+    Input,
+    /// Output epsilon normalization : output epsilons are pushed after the non-epsilon
+    /// output labels on each path.
+    Output,
+}
+
+/// Returns an equivalent FST that is epsilon-normalized. An FST is input
+/// epsilon-normalized if each input epsilon transition on a successful path comes
+/// after all the transitions with a non-epsilon input label on that path. An output
+/// epsilon-normalized FST is defined similarly.
+///
+/// # Example
+/// ```
+/// # use anyhow::Result;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::{CoreFst, MutableFst};
+/// # use rustfst::algorithms::{eps_normalize, EpsNormalizeType};
+/// # use rustfst::{Tr, EPS_LABEL};
+/// # fn main() -> Result<()> {
+/// let mut fst = VectorFst::<TropicalWeight>::new();
+/// fst.add_states(3);
+/// fst.set_start(0)?;
+/// fst.add_tr(0, Tr::new(EPS_LABEL, 1, 1.0, 1))?;
+/// fst.add_tr(1, Tr::new(2, 3, 2.0, 2))?;
+/// fst.set_final(2, 0.5)?;
+///
+/// let normalized_fst: VectorFst<_> = eps_normalize(&fst, EpsNormalizeType::Input)?;
+///
+/// let mut fst_ref = VectorFst::<TropicalWeight>::new();
+/// fst_ref.add_states(3);
+/// fst_ref.set_start(0)?;
+/// fst_ref.add_tr(0, Tr::new(2, 1, 3.0, 1))?;
+/// fst_ref.add_tr(1, Tr::new(EPS_LABEL, 3, 0.5, 2))?;
+/// fst_ref.set_final(2, 0.0)?;
+///
+/// assert_eq!(normalized_fst, fst_ref);
+/// # Ok(())
+/// # }
+/// ```
+pub fn eps_normalize<W, F1, F2>(ifst: &F1, eps_norm_type: EpsNormalizeType) -> Result<F2>
+where
+    W: WeightQuantize,
+    F1: ExpandedFst<W>,
+    F2: MutableFst<W> + AllocableFst<W>,
+{
+    let mut to_gallic = ToGallicConverter {};
+    let mut gfst: VectorFst<GallicWeight<W>> = match eps_norm_type {
+        EpsNormalizeType::Input => weight_convert(ifst, &mut to_gallic)?,
+        EpsNormalizeType::Output => {
+            let mut inverted_fst: VectorFst<W> = fst_convert_from_ref(ifst);
+            invert(&mut inverted_fst);
+            weight_convert(&inverted_fst, &mut to_gallic)?
+        }
+    };
+
+    rm_epsilon(&mut gfst)?;
+
+    let factor_opts = FactorWeightOptions::new(
+        FactorWeightType::FACTOR_ARC_WEIGHTS | FactorWeightType::FACTOR_FINAL_WEIGHTS,
+    );
+    let fwfst: VectorFst<GallicWeight<W>> =
+        factor_weight::<_, VectorFst<_>, _, _, GallicFactor<W>>(&gfst, factor_opts)?;
+
+    let mut from_gallic = FromGallicConverter {
+        superfinal_label: EPS_LABEL,
+    };
+    let mut ofst: F2 = weight_convert(&fwfst, &mut from_gallic)?;
+
+    if eps_norm_type == EpsNormalizeType::Output {
+        invert(&mut ofst);
+    }
+
+    if let Some(isymt) = ifst.input_symbols() {
+        ofst.set_input_symbols(Arc::clone(isymt));
+    }
+    if let Some(osymt) = ifst.output_symbols() {
+        ofst.set_output_symbols(Arc::clone(osymt));
+    }
+    Ok(ofst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fst_traits::{CoreFst, Fst};
+    use crate::semirings::{Semiring, TropicalWeight};
+    use crate::{SymbolTable, Tr};
+
+    fn eps_fst() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(EPS_LABEL, 1, 1.0, 1))?;
+        fst.add_tr(1, Tr::new(2, EPS_LABEL, 2.0, 2))?;
+        fst.add_tr(2, Tr::new(3, 4, 3.0, 3))?;
+        fst.set_final(3, 0.5)?;
+        Ok(fst)
+    }
+
+    #[test]
+    fn test_eps_normalize_input() -> Result<()> {
+        let fst = eps_fst()?;
+        let normalized_fst: VectorFst<TropicalWeight> =
+            eps_normalize(&fst, EpsNormalizeType::Input)?;
+
+        let mut fst_ref = VectorFst::<TropicalWeight>::new();
+        fst_ref.add_states(3);
+        fst_ref.set_start(0)?;
+        fst_ref.add_tr(0, Tr::new(2, 1, 3.0, 1))?;
+        fst_ref.add_tr(1, Tr::new(3, 4, 3.0, 2))?;
+        fst_ref.set_final(2, 0.5)?;
+
+        assert_eq!(normalized_fst, fst_ref);
+        Ok(())
+    }
+
+    #[test]
+    fn test_eps_normalize_output() -> Result<()> {
+        let fst = eps_fst()?;
+        let normalized_fst: VectorFst<TropicalWeight> =
+            eps_normalize(&fst, EpsNormalizeType::Output)?;
+
+        let mut fst_ref = VectorFst::<TropicalWeight>::new();
+        fst_ref.add_states(4);
+        fst_ref.set_start(0)?;
+        fst_ref.add_tr(0, Tr::new(EPS_LABEL, 1, 1.0, 1))?;
+        fst_ref.add_tr(1, Tr::new(2, 4, 5.0, 2))?;
+        fst_ref.add_tr(2, Tr::new(3, EPS_LABEL, 0.5, 3))?;
+        fst_ref.set_final(3, TropicalWeight::one())?;
+
+        assert_eq!(normalized_fst, fst_ref);
+        Ok(())
+    }
+
+    #[test]
+    fn test_eps_normalize_keeps_symbol_tables() -> Result<()> {
+        let mut fst = eps_fst()?;
+        let mut symt = SymbolTable::new();
+        symt.add_symbols(vec!["a", "b", "c", "d"]);
+        let symt = Arc::new(symt);
+        fst.set_input_symbols(Arc::clone(&symt));
+        fst.set_output_symbols(Arc::clone(&symt));
+
+        let normalized_fst: VectorFst<TropicalWeight> =
+            eps_normalize(&fst, EpsNormalizeType::Output)?;
+
+        assert_eq!(normalized_fst.input_symbols(), Some(&symt));
+        assert_eq!(normalized_fst.output_symbols(), Some(&symt));
+        assert_eq!(normalized_fst.final_weight(3)?, Some(TropicalWeight::one()));
+        Ok(())
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+use crate::algorithms::encode::{encode, encode_with_table, EncodeType};
+use crate::algorithms::tr_mappers::QuantizeMapper;
+use crate::algorithms::union_find::UnionFind;
+use crate::algorithms::{
+    connect, fst_convert_from_ref, push_weights_with_config, PushWeightsConfig, ReweightType,
+};
+use crate::fst_impls::VectorFst;
+use crate::fst_properties::{compute_fst_properties, FstProperties};
+use crate::fst_traits::{CoreFst, ExpandedFst, MutableFst};
+use crate::semirings::{WeaklyDivisibleSemiring, WeightQuantize};
+use crate::{Label, StateId, Trs};
+
+// States of both FSTs are mapped to a single id space : even ids for the states
+// of the first FST and odd ids for the states of the second one.
+fn mapped_id_fst1(state: StateId) -> usize {
+    2 * state as usize
+}
+
+fn mapped_id_fst2(state: StateId) -> usize {
+    2 * state as usize + 1
+}
+
+/// Tests whether two deterministic acceptors recognize the same weighted language.
+/// Both FSTs must be epsilon-free, input-deterministic acceptors. Weights are pushed
+/// towards the initial state and compared after quantization with `delta`.
+///
+/// Contrary to `isomorphic`, the two FSTs don't need to have the same structure : a
+/// non-minimal FST is equivalent to its minimized version.
+///
+/// # Example
+/// ```
+/// # use anyhow::Result;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::algorithms::equivalent;
+/// # use rustfst::{Tr, KDELTA};
+/// # fn main() -> Result<()> {
+/// let mut fst1 = VectorFst::<TropicalWeight>::new();
+/// fst1.add_states(2);
+/// fst1.set_start(0)?;
+/// fst1.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+/// fst1.add_tr(1, Tr::new(1, 1, 1.0, 1))?;
+/// fst1.set_final(1, 0.0)?;
+///
+/// let mut fst2 = VectorFst::<TropicalWeight>::new();
+/// fst2.add_states(3);
+/// fst2.set_start(0)?;
+/// fst2.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+/// fst2.add_tr(1, Tr::new(1, 1, 1.0, 2))?;
+/// fst2.add_tr(2, Tr::new(1, 1, 1.0, 1))?;
+/// fst2.set_final(1, 0.0)?;
+/// fst2.set_final(2, 0.0)?;
+///
+/// assert!(equivalent(&fst1, &fst2, KDELTA)?);
+/// # Ok(())
+/// # }
+/// ```
+pub fn equivalent<W, F1, F2>(fst1: &F1, fst2: &F2, delta: f32) -> Result<bool>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize,
+    F1: ExpandedFst<W>,
+    F2: ExpandedFst<W>,
+{
+    let props =
+        FstProperties::NO_EPSILONS | FstProperties::I_DETERMINISTIC | FstProperties::ACCEPTOR;
+    let mut known = FstProperties::empty();
+    if !compute_fst_properties(fst1, props, &mut known, true)?.contains(props) {
+        bail!("Equivalent: 1st argument not an epsilon-free deterministic acceptor");
+    }
+    let mut known = FstProperties::empty();
+    if !compute_fst_properties(fst2, props, &mut known, true)?.contains(props) {
+        bail!("Equivalent: 2nd argument not an epsilon-free deterministic acceptor");
+    }
+
+    let mut efst1: VectorFst<W> = fst_convert_from_ref(fst1);
+    let mut efst2: VectorFst<W> = fst_convert_from_ref(fst2);
+    connect(&mut efst1)?;
+    connect(&mut efst2)?;
+
+    let (start1, start2) = match (efst1.start(), efst2.start()) {
+        (Some(start1), Some(start2)) => (start1, start2),
+        (None, None) => return Ok(true),
+        _ => return Ok(false),
+    };
+
+    // Pushes the weights so that equivalent FSTs carry the same weights on
+    // matching transitions, then turns them into unweighted acceptors sharing
+    // the same encoding.
+    let push_weights_config = PushWeightsConfig::default().with_delta(delta);
+    push_weights_with_config(
+        &mut efst1,
+        ReweightType::ReweightToInitial,
+        push_weights_config,
+    )?;
+    push_weights_with_config(
+        &mut efst2,
+        ReweightType::ReweightToInitial,
+        push_weights_config,
+    )?;
+    let mut quantize_mapper = QuantizeMapper::new(delta);
+    efst1.tr_map(&mut quantize_mapper)?;
+    efst2.tr_map(&mut quantize_mapper)?;
+    let encode_table = encode(&mut efst1, EncodeType::EncodeWeightsAndLabels)?;
+    encode_with_table(&mut efst2, encode_table)?;
+
+    let num_states = efst1.num_states().max(efst2.num_states());
+    let mut eq_classes = UnionFind::new(2 * num_states);
+    eq_classes.union(mapped_id_fst1(start1), mapped_id_fst2(start2));
+    let mut queue = vec![(start1, start2)];
+
+    while let Some((s1, s2)) = queue.pop() {
+        if efst1.final_weight(s1)? != efst2.final_weight(s2)? {
+            return Ok(false);
+        }
+
+        let mut tr_pairs: HashMap<Label, (Option<StateId>, Option<StateId>)> = HashMap::new();
+        for tr in efst1.get_trs(s1)?.trs() {
+            tr_pairs.entry(tr.ilabel).or_default().0 = Some(tr.nextstate);
+        }
+        for tr in efst2.get_trs(s2)?.trs() {
+            tr_pairs.entry(tr.ilabel).or_default().1 = Some(tr.nextstate);
+        }
+
+        for (next1, next2) in tr_pairs.values() {
+            let (next1, next2) = match (next1, next2) {
+                (Some(next1), Some(next2)) => (*next1, *next2),
+                _ => return Ok(false),
+            };
+            let c1 = eq_classes.find(mapped_id_fst1(next1));
+            let c2 = eq_classes.find(mapped_id_fst2(next2));
+            if c1 != c2 {
+                eq_classes.union(c1, c2);
+                queue.push((next1, next2));
+            }
+        }
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::algorithms::minimize;
+    use crate::semirings::{Semiring, TropicalWeight};
+    use crate::utils::acceptor;
+    use crate::{Tr, KDELTA};
+
+    fn det_fst() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(5);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(2, 2, 1.0, 2))?;
+        fst.add_tr(1, Tr::new(3, 3, 2.0, 3))?;
+        fst.add_tr(2, Tr::new(3, 3, 2.0, 4))?;
+        fst.set_final(3, 0.5)?;
+        fst.set_final(4, 0.5)?;
+        Ok(fst)
+    }
+
+    #[test]
+    fn test_equivalent_minimized() -> Result<()> {
+        let fst = det_fst()?;
+        let mut min_fst = fst.clone();
+        minimize(&mut min_fst)?;
+        assert!(min_fst.num_states() < fst.num_states());
+        assert!(equivalent(&fst, &min_fst, KDELTA)?);
+        assert!(equivalent(&min_fst, &fst, KDELTA)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_equivalent_different_languages() -> Result<()> {
+        let fst1: VectorFst<TropicalWeight> = acceptor(&[1, 2, 3], TropicalWeight::one());
+        let fst2: VectorFst<TropicalWeight> = acceptor(&[1, 2], TropicalWeight::one());
+        assert!(!equivalent(&fst1, &fst2, KDELTA)?);
+
+        let fst3: VectorFst<TropicalWeight> = acceptor(&[1, 2, 3], TropicalWeight::new(2.0));
+        assert!(!equivalent(&fst1, &fst3, KDELTA)?);
+
+        let empty_fst = VectorFst::<TropicalWeight>::new();
+        assert!(!equivalent(&fst1, &empty_fst, KDELTA)?);
+        assert!(equivalent(&empty_fst, &empty_fst, KDELTA)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_equivalent_non_deterministic() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(1, 1, 1.0, 2))?;
+        fst.set_final(1, 0.0)?;
+        fst.set_final(2, 0.0)?;
+        assert!(equivalent(&fst, &fst, KDELTA).is_err());
+        Ok(())
+    }
+}
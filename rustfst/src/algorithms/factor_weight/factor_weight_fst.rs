@@ -6,29 +6,34 @@ use anyhow::Result;
 
 use crate::algorithms::factor_weight::factor_weight_op::FactorWeightOp;
 use crate::algorithms::factor_weight::{FactorIterator, FactorWeightOptions};
-use crate::algorithms::lazy::{LazyFst, SimpleHashMapCache};
+use crate::algorithms::lazy::{FstCache, LazyFst, SimpleHashMapCache};
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::{AllocableFst, CoreFst, Fst, FstIterator, MutableFst, StateIterator};
 use crate::semirings::WeightQuantize;
 use crate::{StateId, SymbolTable, TrsVec};
 
-type InnerLazyFst<W, F, B, FI> = LazyFst<W, FactorWeightOp<W, F, B, FI>, SimpleHashMapCache<W>>;
+type InnerLazyFst<W, F, B, FI, Cache> = LazyFst<W, FactorWeightOp<W, F, B, FI>, Cache>;
 
 /// The result of weight factoring is a transducer equivalent to the
 /// input whose path weights have been factored according to the FactorIterator.
 /// States and transitions will be added as necessary. The algorithm is a
 /// generalization to arbitrary weights of the second step of the input
 /// epsilon-normalization algorithm. This version is a Delayed FST.
-pub struct FactorWeightFst<W: WeightQuantize, F: Fst<W>, B: Borrow<F>, FI: FactorIterator<W>>(
-    InnerLazyFst<W, F, B, FI>,
-);
+pub struct FactorWeightFst<
+    W: WeightQuantize,
+    F: Fst<W>,
+    B: Borrow<F>,
+    FI: FactorIterator<W>,
+    Cache = SimpleHashMapCache<W>,
+>(InnerLazyFst<W, F, B, FI, Cache>);
 
-impl<W, F, B, FI> CoreFst<W> for FactorWeightFst<W, F, B, FI>
+impl<W, F, B, FI, Cache> CoreFst<W> for FactorWeightFst<W, F, B, FI, Cache>
 where
     W: WeightQuantize,
     F: Fst<W>,
     B: Borrow<F>,
     FI: FactorIterator<W>,
+    Cache: FstCache<W>,
 {
     type TRS = TrsVec<W>;
 
@@ -73,40 +78,43 @@ where
     }
 }
 
-impl<'a, W, F, B, FI> StateIterator<'a> for FactorWeightFst<W, F, B, FI>
+impl<'a, W, F, B, FI, Cache> StateIterator<'a> for FactorWeightFst<W, F, B, FI, Cache>
 where
     W: WeightQuantize,
     F: Fst<W> + 'a,
     B: Borrow<F> + 'a,
     FI: FactorIterator<W> + 'a,
+    Cache: FstCache<W> + 'a,
 {
-    type Iter = <InnerLazyFst<W, F, B, FI> as StateIterator<'a>>::Iter;
+    type Iter = <InnerLazyFst<W, F, B, FI, Cache> as StateIterator<'a>>::Iter;
 
     fn states_iter(&'a self) -> Self::Iter {
         self.0.states_iter()
     }
 }
 
-impl<'a, W, F, B, FI> FstIterator<'a, W> for FactorWeightFst<W, F, B, FI>
+impl<'a, W, F, B, FI, Cache> FstIterator<'a, W> for FactorWeightFst<W, F, B, FI, Cache>
 where
     W: WeightQuantize,
     F: Fst<W> + 'a,
     B: Borrow<F> + 'a,
     FI: FactorIterator<W> + 'a,
+    Cache: FstCache<W> + 'a,
 {
-    type FstIter = <InnerLazyFst<W, F, B, FI> as FstIterator<'a, W>>::FstIter;
+    type FstIter = <InnerLazyFst<W, F, B, FI, Cache> as FstIterator<'a, W>>::FstIter;
 
     fn fst_iter(&'a self) -> Self::FstIter {
         self.0.fst_iter()
     }
 }
 
-impl<W, F, B, FI> Fst<W> for FactorWeightFst<W, F, B, FI>
+impl<W, F, B, FI, Cache> Fst<W> for FactorWeightFst<W, F, B, FI, Cache>
 where
     W: WeightQuantize,
     F: Fst<W> + 'static,
     B: Borrow<F> + 'static,
     FI: FactorIterator<W> + 'static,
+    Cache: FstCache<W> + 'static,
 {
     fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
         self.0.input_symbols()
@@ -133,12 +141,13 @@ where
     }
 }
 
-impl<W, F, B, FI> Debug for FactorWeightFst<W, F, B, FI>
+impl<W, F, B, FI, Cache> Debug for FactorWeightFst<W, F, B, FI, Cache>
 where
     W: WeightQuantize,
     F: Fst<W>,
     B: Borrow<F>,
     FI: FactorIterator<W>,
+    Cache: FstCache<W>,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.0.fmt(f)
@@ -150,10 +159,20 @@ where
     W: WeightQuantize,
 {
     pub fn new(fst: B, opts: FactorWeightOptions) -> Result<Self> {
+        Self::new_with_cache(fst, opts, SimpleHashMapCache::default())
+    }
+}
+
+impl<W, F: Fst<W>, B: Borrow<F>, FI: FactorIterator<W>, Cache: FstCache<W>>
+    FactorWeightFst<W, F, B, FI, Cache>
+where
+    W: WeightQuantize,
+{
+    /// Same as `new` but the computed states are stored in `fst_cache`.
+    pub fn new_with_cache(fst: B, opts: FactorWeightOptions, fst_cache: Cache) -> Result<Self> {
         let isymt = fst.borrow().input_symbols().cloned();
         let osymt = fst.borrow().output_symbols().cloned();
         let fst_op = FactorWeightOp::new(fst, opts)?;
-        let fst_cache = SimpleHashMapCache::default();
         let lazy_fst = LazyFst::from_op_and_cache(fst_op, fst_cache, isymt, osymt);
         Ok(FactorWeightFst(lazy_fst))
     }
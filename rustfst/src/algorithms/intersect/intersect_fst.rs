@@ -0,0 +1,249 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::compose::compose_filters::SequenceComposeFilterBuilder;
+use crate::algorithms::compose::matchers::GenericMatcher;
+use crate::algorithms::compose::ComposeFst;
+use crate::fst_properties::{compute_fst_properties, FstProperties};
+use crate::fst_traits::{
+    AllocableFst, CoreFst, ExpandedFst, Fst, FstIterator, MutableFst, StateIterator,
+};
+use crate::semirings::Semiring;
+use crate::{StateId, SymbolTable, TrsVec};
+
+type InnerComposeFst<W, F1, F2, B1, B2> = ComposeFst<
+    W,
+    F1,
+    F2,
+    B1,
+    B2,
+    GenericMatcher<W, F1, B1>,
+    GenericMatcher<W, F2, B2>,
+    SequenceComposeFilterBuilder<
+        W,
+        F1,
+        F2,
+        B1,
+        B2,
+        GenericMatcher<W, F1, B1>,
+        GenericMatcher<W, F2, B2>,
+    >,
+>;
+
+/// Computes the intersection (Hadamard product) of two FSAs. This version is a Delayed FST.
+///
+/// Only strings that are in both automata are retained in the result, with the product
+/// of their weights. Both arguments must be acceptors. The matchers and the composition
+/// filter are selected automatically as in [`ComposeFst::new_auto`] : the first FST must
+/// be output label sorted or the second FST input label sorted.
+///
+/// The input symbol table of the first FST and the output symbol table of the second
+/// FST are attached to the result, as for the composition.
+pub struct IntersectFst<W, F1, F2, B1, B2>(InnerComposeFst<W, F1, F2, B1, B2>)
+where
+    W: Semiring,
+    F1: ExpandedFst<W>,
+    F2: ExpandedFst<W>,
+    B1: Borrow<F1> + Debug + Clone,
+    B2: Borrow<F2> + Debug + Clone;
+
+impl<W, F1, F2, B1, B2> CoreFst<W> for IntersectFst<W, F1, F2, B1, B2>
+where
+    W: Semiring,
+    F1: ExpandedFst<W>,
+    F2: ExpandedFst<W>,
+    B1: Borrow<F1> + Debug + Clone,
+    B2: Borrow<F2> + Debug + Clone,
+{
+    type TRS = TrsVec<W>;
+
+    fn start(&self) -> Option<StateId> {
+        self.0.start()
+    }
+
+    fn final_weight(&self, state_id: StateId) -> Result<Option<W>> {
+        self.0.final_weight(state_id)
+    }
+
+    unsafe fn final_weight_unchecked(&self, state_id: StateId) -> Option<W> {
+        self.0.final_weight_unchecked(state_id)
+    }
+
+    fn num_trs(&self, s: StateId) -> Result<usize> {
+        self.0.num_trs(s)
+    }
+
+    unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
+        self.0.num_trs_unchecked(s)
+    }
+
+    fn get_trs(&self, state_id: StateId) -> Result<Self::TRS> {
+        self.0.get_trs(state_id)
+    }
+
+    unsafe fn get_trs_unchecked(&self, state_id: StateId) -> Self::TRS {
+        self.0.get_trs_unchecked(state_id)
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.0.properties()
+    }
+
+    fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_input_epsilons(state)
+    }
+
+    fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_output_epsilons(state)
+    }
+}
+
+impl<'a, W, F1, F2, B1, B2> StateIterator<'a> for IntersectFst<W, F1, F2, B1, B2>
+where
+    W: Semiring,
+    F1: ExpandedFst<W> + 'a,
+    F2: ExpandedFst<W> + 'a,
+    B1: Borrow<F1> + Debug + Clone + 'a,
+    B2: Borrow<F2> + Debug + Clone + 'a,
+{
+    type Iter = <InnerComposeFst<W, F1, F2, B1, B2> as StateIterator<'a>>::Iter;
+
+    fn states_iter(&'a self) -> Self::Iter {
+        self.0.states_iter()
+    }
+}
+
+impl<'a, W, F1, F2, B1, B2> FstIterator<'a, W> for IntersectFst<W, F1, F2, B1, B2>
+where
+    W: Semiring,
+    F1: ExpandedFst<W> + 'a,
+    F2: ExpandedFst<W> + 'a,
+    B1: Borrow<F1> + Debug + Clone + 'a,
+    B2: Borrow<F2> + Debug + Clone + 'a,
+{
+    type FstIter = <InnerComposeFst<W, F1, F2, B1, B2> as FstIterator<'a, W>>::FstIter;
+
+    fn fst_iter(&'a self) -> Self::FstIter {
+        self.0.fst_iter()
+    }
+}
+
+impl<W, F1, F2, B1, B2> Fst<W> for IntersectFst<W, F1, F2, B1, B2>
+where
+    W: Semiring,
+    F1: ExpandedFst<W> + 'static,
+    F2: ExpandedFst<W> + 'static,
+    B1: Borrow<F1> + Debug + Clone + 'static,
+    B2: Borrow<F2> + Debug + Clone + 'static,
+{
+    fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.input_symbols()
+    }
+
+    fn output_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.output_symbols()
+    }
+
+    fn set_input_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_input_symbols(symt)
+    }
+
+    fn set_output_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_output_symbols(symt)
+    }
+
+    fn take_input_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_input_symbols()
+    }
+
+    fn take_output_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_output_symbols()
+    }
+}
+
+impl<W, F1, F2, B1, B2> Debug for IntersectFst<W, F1, F2, B1, B2>
+where
+    W: Semiring,
+    F1: ExpandedFst<W>,
+    F2: ExpandedFst<W>,
+    B1: Borrow<F1> + Debug + Clone,
+    B2: Borrow<F2> + Debug + Clone,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<W, F1, F2, B1, B2> IntersectFst<W, F1, F2, B1, B2>
+where
+    W: Semiring,
+    F1: ExpandedFst<W>,
+    F2: ExpandedFst<W>,
+    B1: Borrow<F1> + Debug + Clone,
+    B2: Borrow<F2> + Debug + Clone,
+{
+    pub fn new(fst1: B1, fst2: B2) -> Result<Self> {
+        let mut known = FstProperties::empty();
+        let props1 =
+            compute_fst_properties(fst1.borrow(), FstProperties::ACCEPTOR, &mut known, true)?;
+        if !props1.contains(FstProperties::ACCEPTOR) {
+            bail!("IntersectFst: 1st argument must be an acceptor");
+        }
+        let props2 =
+            compute_fst_properties(fst2.borrow(), FstProperties::ACCEPTOR, &mut known, true)?;
+        if !props2.contains(FstProperties::ACCEPTOR) {
+            bail!("IntersectFst: 2nd argument must be an acceptor");
+        }
+        if !(fst1
+            .borrow()
+            .properties()
+            .contains(FstProperties::O_LABEL_SORTED)
+            || fst2
+                .borrow()
+                .properties()
+                .contains(FstProperties::I_LABEL_SORTED))
+        {
+            bail!("IntersectFst: 1st argument must be output label sorted or 2nd argument input label sorted (tr_sort?)");
+        }
+        Ok(IntersectFst(ComposeFst::new_auto_acceptors(fst1, fst2)?))
+    }
+
+    /// Turns the Lazy FST into a static one.
+    pub fn compute<F: MutableFst<W> + AllocableFst<W>>(&self) -> Result<F> {
+        self.0.compute()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::MutableFst;
+    use crate::semirings::{Semiring, TropicalWeight};
+    use crate::utils::acceptor;
+
+    #[test]
+    fn test_intersect_fst_sync() {
+        fn is_sync<T: Sync>() {}
+        is_sync::<IntersectFst<TropicalWeight, VectorFst<_>, VectorFst<_>, Arc<_>, Arc<_>>>();
+    }
+
+    #[test]
+    fn test_intersect_fst_acceptor_properties() -> Result<()> {
+        let mut fst1: VectorFst<TropicalWeight> = acceptor(&[1, 2], TropicalWeight::one());
+        let mut fst2: VectorFst<TropicalWeight> = acceptor(&[1, 2], TropicalWeight::one());
+        // The inputs are only known to be acceptors once their properties are computed.
+        fst1.set_properties(FstProperties::O_LABEL_SORTED);
+        fst2.set_properties(FstProperties::empty());
+
+        let intersect_fst: IntersectFst<_, VectorFst<_>, VectorFst<_>, _, _> =
+            IntersectFst::new(&fst1, &fst2)?;
+        assert!(intersect_fst.properties().contains(FstProperties::ACCEPTOR));
+        let fst: VectorFst<_> = intersect_fst.compute()?;
+        assert!(fst.properties().contains(FstProperties::ACCEPTOR));
+        Ok(())
+    }
+}
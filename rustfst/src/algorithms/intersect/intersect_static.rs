@@ -0,0 +1,171 @@
+use anyhow::Result;
+
+use crate::algorithms::intersect::IntersectFst;
+use crate::algorithms::tr_compares::ILabelCompare;
+use crate::algorithms::{connect, fst_convert_from_ref, tr_sort};
+use crate::fst_impls::VectorFst;
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{AllocableFst, ExpandedFst, MutableFst};
+use crate::semirings::Semiring;
+
+/// This operation computes the intersection (Hadamard product) of two FSAs.
+/// Only strings that are in both automata are retained in the result, their weight
+/// being the product of the weights in both automata.
+///
+/// Both arguments must be acceptors. An error is returned otherwise. If neither the first
+/// FST is output label sorted nor the second one input label sorted, a sorted copy of the
+/// second FST is used.
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use anyhow::Result;
+/// # use rustfst::utils::acceptor;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::Fst;
+/// # use rustfst::FstPath;
+/// # use rustfst::algorithms::union::union;
+/// # use rustfst::algorithms::intersect::intersect;
+/// # use std::collections::HashSet;
+/// # fn main() -> Result<()> {
+/// let mut fst_a : VectorFst<TropicalWeight> = fst![1, 2; 1.0];
+/// let fst_c : VectorFst<TropicalWeight> = fst![1, 3];
+/// union(&mut fst_a, &fst_c)?;
+/// let fst_b : VectorFst<TropicalWeight> = fst![1, 2; 2.0];
+///
+/// let fst_res : VectorFst<_> = intersect(&fst_a, &fst_b)?;
+/// let paths : HashSet<_> = fst_res.paths_iter().collect();
+///
+/// let mut paths_ref = HashSet::<FstPath<TropicalWeight>>::new();
+/// paths_ref.insert(fst_path![1, 2; 3.0]);
+///
+/// assert_eq!(paths, paths_ref);
+/// # Ok(())
+/// # }
+/// ```
+pub fn intersect<W, F1, F2, F3>(fst1: &F1, fst2: &F2) -> Result<F3>
+where
+    W: Semiring,
+    F1: ExpandedFst<W>,
+    F2: ExpandedFst<W>,
+    F3: MutableFst<W> + AllocableFst<W>,
+{
+    let sorted = fst1.properties().contains(FstProperties::O_LABEL_SORTED)
+        || fst2.properties().contains(FstProperties::I_LABEL_SORTED);
+    let mut ofst: F3 = if sorted {
+        let intersect_fst: IntersectFst<_, F1, F2, _, _> = IntersectFst::new(fst1, fst2)?;
+        intersect_fst.compute()?
+    } else {
+        let mut fst2_sorted: VectorFst<W> = fst_convert_from_ref(fst2);
+        tr_sort(&mut fst2_sorted, ILabelCompare {});
+        let intersect_fst: IntersectFst<_, F1, VectorFst<W>, _, _> =
+            IntersectFst::new(fst1, &fst2_sorted)?;
+        intersect_fst.compute()?
+    };
+    connect(&mut ofst)?;
+    Ok(ofst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    use crate::algorithms::union::union;
+    use crate::fst_traits::{CoreFst, Fst};
+    use crate::semirings::{Semiring, TropicalWeight};
+    use crate::utils::{acceptor, transducer};
+    use crate::{FstPath, SymbolTable, Tr, EPS_LABEL};
+
+    #[test]
+    fn test_intersect_weighted_acceptors() -> Result<()> {
+        let mut fst1: VectorFst<TropicalWeight> = acceptor(&[1, 2], TropicalWeight::new(1.0));
+        let fst3: VectorFst<TropicalWeight> = acceptor(&[3], TropicalWeight::new(0.5));
+        union(&mut fst1, &fst3)?;
+
+        // fst2 accepts "1 2" and "3", with an epsilon transition before "3".
+        let mut fst2 = VectorFst::<TropicalWeight>::new();
+        fst2.add_states(5);
+        fst2.set_start(0)?;
+        fst2.add_tr(0, Tr::new(1, 1, 2.0, 1))?;
+        fst2.add_tr(1, Tr::new(2, 2, 0.0, 2))?;
+        fst2.add_tr(0, Tr::new(EPS_LABEL, EPS_LABEL, 1.0, 3))?;
+        fst2.add_tr(3, Tr::new(3, 3, 1.0, 4))?;
+        fst2.add_tr(0, Tr::new(4, 4, 1.0, 4))?;
+        fst2.set_final(2, 0.0)?;
+        fst2.set_final(4, 0.0)?;
+
+        let res: VectorFst<_> = intersect(&fst1, &fst2)?;
+        let paths: HashSet<_> = res.paths_iter().collect();
+        let mut paths_ref = HashSet::new();
+        paths_ref.insert(FstPath::new(
+            vec![1, 2],
+            vec![1, 2],
+            TropicalWeight::new(3.0),
+        ));
+        paths_ref.insert(FstPath::new(vec![3], vec![3], TropicalWeight::new(2.5)));
+        assert_eq!(paths, paths_ref);
+        assert!(res.properties().contains(FstProperties::ACCEPTOR));
+        Ok(())
+    }
+
+    #[test]
+    fn test_intersect_symbol_tables() -> Result<()> {
+        let mut symt = SymbolTable::new();
+        symt.add_symbol("a");
+        symt.add_symbol("b");
+        let symt = Arc::new(symt);
+
+        let mut fst1: VectorFst<TropicalWeight> = acceptor(&[1, 2], TropicalWeight::one());
+        fst1.set_input_symbols(Arc::clone(&symt));
+        fst1.set_output_symbols(Arc::clone(&symt));
+        let mut fst2 = fst1.clone();
+        fst2.set_final(fst2.num_states() as u32 - 1, TropicalWeight::new(1.0))?;
+
+        let res: VectorFst<_> = intersect(&fst1, &fst2)?;
+        assert_eq!(res.input_symbols(), Some(&symt));
+        assert_eq!(res.output_symbols(), Some(&symt));
+        Ok(())
+    }
+
+    #[test]
+    fn test_intersect_requires_acceptors() -> Result<()> {
+        let fst1: VectorFst<TropicalWeight> = acceptor(&[1, 2], TropicalWeight::one());
+        let fst2: VectorFst<TropicalWeight> = transducer(&[1, 2], &[3, 4], TropicalWeight::one());
+
+        let res: Result<VectorFst<_>> = intersect(&fst1, &fst2);
+        assert!(res.is_err());
+        let res: Result<VectorFst<_>> = intersect(&fst2, &fst1);
+        assert!(res.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_intersect_fst_requires_sorted_input() -> Result<()> {
+        let mut fst1 = VectorFst::<TropicalWeight>::new();
+        fst1.add_states(2);
+        fst1.set_start(0)?;
+        fst1.add_tr(0, Tr::new(2, 2, TropicalWeight::one(), 1))?;
+        fst1.add_tr(0, Tr::new(1, 1, TropicalWeight::one(), 1))?;
+        fst1.set_final(1, TropicalWeight::one())?;
+        fst1.set_properties_with_mask(
+            FstProperties::NOT_I_LABEL_SORTED | FstProperties::NOT_O_LABEL_SORTED,
+            FstProperties::I_LABEL_SORTED
+                | FstProperties::NOT_I_LABEL_SORTED
+                | FstProperties::O_LABEL_SORTED
+                | FstProperties::NOT_O_LABEL_SORTED,
+        );
+
+        let res: Result<IntersectFst<_, VectorFst<_>, VectorFst<_>, _, _>> =
+            IntersectFst::new(&fst1, &fst1);
+        assert!(res.is_err());
+
+        let res: VectorFst<_> = intersect(&fst1, &fst1)?;
+        let paths: HashSet<_> = res.paths_iter().collect();
+        let paths_ref: HashSet<_> = fst1.paths_iter().collect();
+        assert_eq!(paths, paths_ref);
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+mod intersect_fst;
+mod intersect_static;
+
+pub use intersect_fst::IntersectFst;
+pub use intersect_static::intersect;
@@ -0,0 +1,368 @@
+use std::collections::HashMap;
+use std::mem::size_of;
+use std::sync::Mutex;
+
+use crate::algorithms::lazy::cache::cache_internal_types::{CacheTrs, FinalWeight, StartState};
+use crate::algorithms::lazy::{CacheStatus, FstCache};
+use crate::semirings::Semiring;
+use crate::{StateId, Tr, Trs, TrsVec, EPS_LABEL};
+
+/// Default memory limit of a `GcCache` in bytes. Same value as OpenFST's `cache_gc_limit`.
+pub const DEFAULT_GC_LIMIT: usize = 1 << 20;
+
+/// Fraction of the `gc_limit` kept in the cache after a garbage collection.
+const GC_CACHE_FRACTION: f32 = 0.666;
+
+/// Cache bounding the memory used by the states of a lazy FST.
+///
+/// When the (approximate) size of the cached transitions and final weights exceeds
+/// `gc_limit` bytes, the least recently used states are evicted until the size goes
+/// below two thirds of the limit. Evicted states are re-computed through the `FstOp`
+/// of the lazy FST the next time they are accessed. The start state and the number of
+/// known states are never evicted.
+#[derive(Debug)]
+pub struct GcCache<W: Semiring> {
+    gc_limit: usize,
+    data: Mutex<GcCacheData<W>>,
+}
+
+#[derive(Debug, Clone)]
+struct GcCacheEntry<T> {
+    data: T,
+    last_access: u64,
+}
+
+#[derive(Debug, Clone)]
+struct GcCacheData<W: Semiring> {
+    start: CacheStatus<StartState>,
+    trs: HashMap<StateId, GcCacheEntry<CacheTrs<W>>>,
+    final_weights: HashMap<StateId, GcCacheEntry<FinalWeight<W>>>,
+    num_known_states: usize,
+    cache_size: usize,
+    access_counter: u64,
+}
+
+fn trs_size<W: Semiring>(trs: &CacheTrs<W>) -> usize {
+    size_of::<StateId>() + size_of::<CacheTrs<W>>() + trs.trs.len() * size_of::<Tr<W>>()
+}
+
+fn final_weight_size<W: Semiring>() -> usize {
+    size_of::<StateId>() + size_of::<FinalWeight<W>>()
+}
+
+impl<W: Semiring> GcCacheData<W> {
+    fn new() -> Self {
+        Self {
+            start: CacheStatus::NotComputed,
+            trs: HashMap::new(),
+            final_weights: HashMap::new(),
+            num_known_states: 0,
+            cache_size: 0,
+            access_counter: 0,
+        }
+    }
+
+    fn next_access(&mut self) -> u64 {
+        self.access_counter += 1;
+        self.access_counter
+    }
+
+    fn update_num_known_states(&mut self, state: StateId) {
+        self.num_known_states = std::cmp::max(self.num_known_states, state as usize + 1);
+    }
+
+    /// Evicts the least recently used entries until the cache size goes below
+    /// `GC_CACHE_FRACTION * gc_limit`. The entries of `protected_state` are kept.
+    fn gc(&mut self, gc_limit: usize, protected_state: StateId) {
+        let target_size = (gc_limit as f32 * GC_CACHE_FRACTION) as usize;
+        let mut entries: Vec<_> = self
+            .trs
+            .iter()
+            .map(|(s, e)| (e.last_access, true, *s))
+            .chain(
+                self.final_weights
+                    .iter()
+                    .map(|(s, e)| (e.last_access, false, *s)),
+            )
+            .filter(|(_, _, s)| *s != protected_state)
+            .collect();
+        entries.sort_unstable();
+
+        for (_, is_trs, state) in entries {
+            if self.cache_size <= target_size {
+                break;
+            }
+            if is_trs {
+                if let Some(e) = self.trs.remove(&state) {
+                    self.cache_size -= trs_size(&e.data);
+                }
+            } else if self.final_weights.remove(&state).is_some() {
+                self.cache_size -= final_weight_size::<W>();
+            }
+        }
+    }
+}
+
+impl<W: Semiring> GcCache<W> {
+    /// Creates a cache using at most (approximately) `gc_limit` bytes.
+    pub fn new(gc_limit: usize) -> Self {
+        Self {
+            gc_limit,
+            data: Mutex::new(GcCacheData::new()),
+        }
+    }
+
+    pub fn gc_limit(&self) -> usize {
+        self.gc_limit
+    }
+
+    /// Approximate number of bytes used by the cached transitions and final weights.
+    pub fn cache_size(&self) -> usize {
+        self.data.lock().unwrap().cache_size
+    }
+
+    pub fn clear(&self) {
+        *self.data.lock().unwrap() = GcCacheData::new();
+    }
+}
+
+impl<W: Semiring> Clone for GcCache<W> {
+    fn clone(&self) -> Self {
+        Self {
+            gc_limit: self.gc_limit,
+            data: Mutex::new(self.data.lock().unwrap().clone()),
+        }
+    }
+}
+
+impl<W: Semiring> Default for GcCache<W> {
+    fn default() -> Self {
+        Self::new(DEFAULT_GC_LIMIT)
+    }
+}
+
+impl<W: Semiring> FstCache<W> for GcCache<W> {
+    fn get_start(&self) -> CacheStatus<StartState> {
+        self.data.lock().unwrap().start
+    }
+
+    fn insert_start(&self, id: StartState) {
+        let mut data = self.data.lock().unwrap();
+        if let Some(s) = id {
+            data.update_num_known_states(s);
+        }
+        data.start = CacheStatus::Computed(id);
+    }
+
+    fn get_trs(&self, id: StateId) -> CacheStatus<TrsVec<W>> {
+        let mut data = self.data.lock().unwrap();
+        let last_access = data.next_access();
+        match data.trs.get_mut(&id) {
+            Some(e) => {
+                e.last_access = last_access;
+                CacheStatus::Computed(e.data.trs.shallow_clone())
+            }
+            None => CacheStatus::NotComputed,
+        }
+    }
+
+    fn insert_trs(&self, id: StateId, trs: TrsVec<W>) {
+        let mut data = self.data.lock().unwrap();
+        let mut niepsilons = 0;
+        let mut noepsilons = 0;
+        for tr in trs.trs() {
+            data.update_num_known_states(tr.nextstate);
+            if tr.ilabel == EPS_LABEL {
+                niepsilons += 1;
+            }
+            if tr.olabel == EPS_LABEL {
+                noepsilons += 1;
+            }
+        }
+        let cache_trs = CacheTrs {
+            trs,
+            niepsilons,
+            noepsilons,
+        };
+        data.cache_size += trs_size(&cache_trs);
+        let last_access = data.next_access();
+        let entry = GcCacheEntry {
+            data: cache_trs,
+            last_access,
+        };
+        if let Some(old) = data.trs.insert(id, entry) {
+            data.cache_size -= trs_size(&old.data);
+        }
+        if data.cache_size > self.gc_limit {
+            data.gc(self.gc_limit, id);
+        }
+    }
+
+    fn get_final_weight(&self, id: StateId) -> CacheStatus<FinalWeight<W>> {
+        let mut data = self.data.lock().unwrap();
+        let last_access = data.next_access();
+        match data.final_weights.get_mut(&id) {
+            Some(e) => {
+                e.last_access = last_access;
+                CacheStatus::Computed(e.data.clone())
+            }
+            None => CacheStatus::NotComputed,
+        }
+    }
+
+    fn insert_final_weight(&self, id: StateId, weight: FinalWeight<W>) {
+        let mut data = self.data.lock().unwrap();
+        data.update_num_known_states(id);
+        let last_access = data.next_access();
+        let entry = GcCacheEntry {
+            data: weight,
+            last_access,
+        };
+        if data.final_weights.insert(id, entry).is_none() {
+            data.cache_size += final_weight_size::<W>();
+        }
+        if data.cache_size > self.gc_limit {
+            data.gc(self.gc_limit, id);
+        }
+    }
+
+    fn num_known_states(&self) -> usize {
+        self.data.lock().unwrap().num_known_states
+    }
+
+    fn compute_num_known_trs(&self) -> usize {
+        let data = self.data.lock().unwrap();
+        data.trs.values().map(|e| e.data.trs.len()).sum()
+    }
+
+    fn num_trs(&self, id: StateId) -> Option<usize> {
+        let data = self.data.lock().unwrap();
+        data.trs.get(&id).map(|e| e.data.trs.len())
+    }
+
+    fn num_input_epsilons(&self, id: StateId) -> Option<usize> {
+        let data = self.data.lock().unwrap();
+        data.trs.get(&id).map(|e| e.data.niepsilons)
+    }
+
+    fn num_output_epsilons(&self, id: StateId) -> Option<usize> {
+        let data = self.data.lock().unwrap();
+        data.trs.get(&id).map(|e| e.data.noepsilons)
+    }
+
+    fn len_trs(&self) -> usize {
+        self.data.lock().unwrap().trs.len()
+    }
+
+    fn len_final_weights(&self) -> usize {
+        self.data.lock().unwrap().final_weights.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::compose::compose;
+    use crate::algorithms::compose::compose_filters::SequenceComposeFilterBuilder;
+    use crate::algorithms::compose::matchers::SortedMatcher;
+    use crate::algorithms::compose::ComposeFst;
+    use crate::algorithms::rm_epsilon::RmEpsilonFst;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{CoreFst, MutableFst, StateIterator};
+    use crate::semirings::TropicalWeight;
+    use anyhow::Result;
+
+    fn trs(n: usize) -> TrsVec<TropicalWeight> {
+        let mut trs = TrsVec::default();
+        for i in 0..n {
+            trs.push(Tr::new(1, 1, TropicalWeight::one(), i as StateId));
+        }
+        trs
+    }
+
+    #[test]
+    fn test_gc_cache_evicts_least_recently_used() {
+        let trs_size = trs_size(&CacheTrs {
+            trs: trs(4),
+            niepsilons: 0,
+            noepsilons: 0,
+        });
+        let cache = GcCache::<TropicalWeight>::new(3 * trs_size);
+        cache.insert_trs(0, trs(4));
+        cache.insert_trs(1, trs(4));
+        cache.insert_trs(2, trs(4));
+        assert_eq!(cache.len_trs(), 3);
+
+        // State 0 becomes more recent than state 1.
+        assert!(cache.get_trs(0).is_computed());
+        cache.insert_trs(3, trs(4));
+
+        assert!(cache.cache_size() <= cache.gc_limit());
+        assert!(cache.get_trs(1).is_not_computed());
+        assert!(cache.get_trs(3).is_computed());
+        assert_eq!(cache.num_known_states(), 4);
+    }
+
+    #[test]
+    fn test_gc_cache_compose_fst() -> Result<()> {
+        let mut fst1 = VectorFst::<TropicalWeight>::new();
+        let mut fst2 = VectorFst::<TropicalWeight>::new();
+        fst1.add_states(20);
+        fst2.add_states(20);
+        fst1.set_start(0)?;
+        fst2.set_start(0)?;
+        for s in 0..19 {
+            fst1.emplace_tr(s, 1, 2, TropicalWeight::new(s as f32), s + 1)?;
+            fst1.emplace_tr(s, 1, 3, TropicalWeight::new(1.0), s + 1)?;
+            fst2.emplace_tr(s, 2, 4, TropicalWeight::new(0.5), s + 1)?;
+            fst2.emplace_tr(s, 3, 5, TropicalWeight::new(2.0), s + 1)?;
+        }
+        fst1.set_final(19, TropicalWeight::one())?;
+        fst2.set_final(19, TropicalWeight::one())?;
+        let ref_fst: VectorFst<_> = compose(fst1.clone(), fst2.clone())?;
+
+        let compose_fst = ComposeFst::<
+            _,
+            _,
+            _,
+            _,
+            _,
+            SortedMatcher<_, _, _>,
+            SortedMatcher<_, _, _>,
+            SequenceComposeFilterBuilder<_, _, _, _, _, _, _>,
+            GcCache<_>,
+        >::new_with_options_and_cache(
+            fst1, fst2, Default::default(), GcCache::new(256)
+        )?;
+
+        // Iterating twice forces the re-computation of the evicted states.
+        for _ in 0..2 {
+            let computed: VectorFst<_> = compose_fst.compute()?;
+            assert_eq!(computed, ref_fst);
+            for s in compose_fst.states_iter() {
+                assert_eq!(compose_fst.num_trs(s)?, ref_fst.num_trs(s)?);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_gc_cache_rm_epsilon_fst() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(30);
+        fst.set_start(0)?;
+        for s in 0..29 {
+            fst.emplace_tr(s, 0, 0, TropicalWeight::new(1.0), s + 1)?;
+            fst.emplace_tr(s, 1, 1, TropicalWeight::new(s as f32), s + 1)?;
+        }
+        fst.set_final(29, TropicalWeight::one())?;
+
+        let ref_fst: VectorFst<_> = RmEpsilonFst::new(fst.clone())?.compute()?;
+        let rm_epsilon_fst = RmEpsilonFst::new_with_cache(fst, GcCache::new(512))?;
+        for _ in 0..2 {
+            let computed: VectorFst<_> = rm_epsilon_fst.compute()?;
+            assert_eq!(computed, ref_fst);
+        }
+        Ok(())
+    }
+}
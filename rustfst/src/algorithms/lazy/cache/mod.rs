@@ -2,6 +2,8 @@ mod cache_internal_types;
 pub mod cache_status;
 pub mod first_cache;
 pub mod fst_cache;
+pub mod gc_cache;
+pub mod sharded_cache;
 pub mod simple_hash_map_cache;
 pub mod simple_vec_cache;
 mod utils_parsing;
@@ -10,6 +12,8 @@ mod utils_serialization;
 pub use self::cache_status::CacheStatus;
 pub use self::first_cache::FirstCache;
 pub use self::fst_cache::FstCache;
+pub use self::gc_cache::GcCache;
+pub use self::sharded_cache::ShardedCache;
 pub use self::simple_hash_map_cache::SimpleHashMapCache;
 pub use self::simple_vec_cache::SimpleVecCache;
 
@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::RwLock;
+
+use crate::algorithms::lazy::cache::cache_internal_types::{CacheTrs, FinalWeight, StartState};
+use crate::algorithms::lazy::{CacheStatus, FstCache};
+use crate::semirings::Semiring;
+use crate::{StateId, Trs, TrsVec, EPS_LABEL};
+
+/// Default number of shards of a `ShardedCache`.
+pub const DEFAULT_NUM_SHARDS: usize = 16;
+
+/// Cache meant to be shared between threads expanding the same lazy FST.
+///
+/// The states are distributed over several shards, each one protected by its own `RwLock`.
+/// Reading already computed states only takes a read lock on one shard so concurrent
+/// lookups don't block each other and insertions only block the states of the same shard.
+#[derive(Debug)]
+pub struct ShardedCache<W: Semiring> {
+    start: RwLock<CacheStatus<StartState>>,
+    shards: Vec<RwLock<CacheShard<W>>>,
+    num_known_states: AtomicUsize,
+}
+
+#[derive(Debug, Clone)]
+struct CacheShard<W: Semiring> {
+    trs: HashMap<StateId, CacheTrs<W>>,
+    final_weights: HashMap<StateId, FinalWeight<W>>,
+}
+
+impl<W: Semiring> Default for CacheShard<W> {
+    fn default() -> Self {
+        Self {
+            trs: HashMap::new(),
+            final_weights: HashMap::new(),
+        }
+    }
+}
+
+impl<W: Semiring> ShardedCache<W> {
+    /// Creates a cache with `num_shards` shards. Panics if `num_shards` is zero.
+    pub fn new(num_shards: usize) -> Self {
+        assert!(num_shards > 0, "A ShardedCache needs at least one shard");
+        Self {
+            start: RwLock::new(CacheStatus::NotComputed),
+            shards: (0..num_shards)
+                .map(|_| RwLock::new(CacheShard::default()))
+                .collect(),
+            num_known_states: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn num_shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    pub fn clear(&self) {
+        *self.start.write().unwrap() = CacheStatus::NotComputed;
+        for shard in self.shards.iter() {
+            *shard.write().unwrap() = CacheShard::default();
+        }
+        self.num_known_states.store(0, Ordering::SeqCst);
+    }
+
+    fn shard(&self, id: StateId) -> &RwLock<CacheShard<W>> {
+        &self.shards[id as usize % self.shards.len()]
+    }
+
+    fn update_num_known_states(&self, state: StateId) {
+        self.num_known_states
+            .fetch_max(state as usize + 1, Ordering::SeqCst);
+    }
+}
+
+impl<W: Semiring> Clone for ShardedCache<W> {
+    fn clone(&self) -> Self {
+        Self {
+            start: RwLock::new(*self.start.read().unwrap()),
+            shards: self
+                .shards
+                .iter()
+                .map(|shard| RwLock::new(shard.read().unwrap().clone()))
+                .collect(),
+            num_known_states: AtomicUsize::new(self.num_known_states.load(Ordering::SeqCst)),
+        }
+    }
+}
+
+impl<W: Semiring> Default for ShardedCache<W> {
+    fn default() -> Self {
+        Self::new(DEFAULT_NUM_SHARDS)
+    }
+}
+
+impl<W: Semiring> FstCache<W> for ShardedCache<W> {
+    fn get_start(&self) -> CacheStatus<StartState> {
+        *self.start.read().unwrap()
+    }
+
+    fn insert_start(&self, id: StartState) {
+        if let Some(s) = id {
+            self.update_num_known_states(s);
+        }
+        *self.start.write().unwrap() = CacheStatus::Computed(id);
+    }
+
+    fn get_trs(&self, id: StateId) -> CacheStatus<TrsVec<W>> {
+        match self.shard(id).read().unwrap().trs.get(&id) {
+            Some(e) => CacheStatus::Computed(e.trs.shallow_clone()),
+            None => CacheStatus::NotComputed,
+        }
+    }
+
+    fn insert_trs(&self, id: StateId, trs: TrsVec<W>) {
+        let mut niepsilons = 0;
+        let mut noepsilons = 0;
+        for tr in trs.trs() {
+            self.update_num_known_states(tr.nextstate);
+            if tr.ilabel == EPS_LABEL {
+                niepsilons += 1;
+            }
+            if tr.olabel == EPS_LABEL {
+                noepsilons += 1;
+            }
+        }
+        self.shard(id).write().unwrap().trs.insert(
+            id,
+            CacheTrs {
+                trs,
+                niepsilons,
+                noepsilons,
+            },
+        );
+    }
+
+    fn get_final_weight(&self, id: StateId) -> CacheStatus<FinalWeight<W>> {
+        match self.shard(id).read().unwrap().final_weights.get(&id) {
+            Some(e) => CacheStatus::Computed(e.clone()),
+            None => CacheStatus::NotComputed,
+        }
+    }
+
+    fn insert_final_weight(&self, id: StateId, weight: FinalWeight<W>) {
+        self.update_num_known_states(id);
+        self.shard(id)
+            .write()
+            .unwrap()
+            .final_weights
+            .insert(id, weight);
+    }
+
+    fn num_known_states(&self) -> usize {
+        self.num_known_states.load(Ordering::SeqCst)
+    }
+
+    fn compute_num_known_trs(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| {
+                let shard = shard.read().unwrap();
+                shard.trs.values().map(|e| e.trs.len()).sum::<usize>()
+            })
+            .sum()
+    }
+
+    fn num_trs(&self, id: StateId) -> Option<usize> {
+        self.shard(id)
+            .read()
+            .unwrap()
+            .trs
+            .get(&id)
+            .map(|e| e.trs.len())
+    }
+
+    fn num_input_epsilons(&self, id: StateId) -> Option<usize> {
+        self.shard(id)
+            .read()
+            .unwrap()
+            .trs
+            .get(&id)
+            .map(|e| e.niepsilons)
+    }
+
+    fn num_output_epsilons(&self, id: StateId) -> Option<usize> {
+        self.shard(id)
+            .read()
+            .unwrap()
+            .trs
+            .get(&id)
+            .map(|e| e.noepsilons)
+    }
+
+    fn len_trs(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.read().unwrap().trs.len())
+            .sum()
+    }
+
+    fn len_final_weights(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.read().unwrap().final_weights.len())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::algorithms::compose::compose_filters::SequenceComposeFilterBuilder;
+    use crate::algorithms::compose::matchers::SortedMatcher;
+    use crate::algorithms::compose::{ComposeFst, ComposeFstOp};
+    use crate::algorithms::isomorphic;
+    use crate::algorithms::lazy::LazyFst;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::MutableFst;
+    use crate::semirings::TropicalWeight;
+
+    type F = VectorFst<TropicalWeight>;
+    type M = SortedMatcher<TropicalWeight, F, Arc<F>>;
+    type Cfb = SequenceComposeFilterBuilder<TropicalWeight, F, F, Arc<F>, Arc<F>, M, M>;
+    type ShardedComposeFst =
+        ComposeFst<TropicalWeight, F, F, Arc<F>, Arc<F>, M, M, Cfb, ShardedCache<TropicalWeight>>;
+
+    #[test]
+    fn test_sharded_cache_send_sync() {
+        fn is_send_sync<T: Send + Sync>() {}
+        is_send_sync::<ShardedCache<TropicalWeight>>();
+        is_send_sync::<ShardedComposeFst>();
+        is_send_sync::<
+            LazyFst<
+                TropicalWeight,
+                ComposeFstOp<TropicalWeight, F, F, Arc<F>, Arc<F>, M, M, Cfb>,
+                ShardedCache<TropicalWeight>,
+            >,
+        >();
+    }
+
+    fn dense_fst(num_states: StateId, olabel_offset: u32) -> Result<F> {
+        let mut fst = VectorFst::new();
+        fst.add_states(num_states as usize);
+        fst.set_start(0)?;
+        for s in 0..num_states {
+            for label in 1..5 {
+                let nextstate = (s * 7 + label * 3) % num_states;
+                fst.emplace_tr(
+                    s,
+                    label,
+                    label + olabel_offset,
+                    TropicalWeight::new((s + label) as f32),
+                    nextstate,
+                )?;
+            }
+            fst.set_final(s, TropicalWeight::new(s as f32))?;
+        }
+        Ok(fst)
+    }
+
+    #[test]
+    fn test_sharded_cache_multithreaded_expansion() -> Result<()> {
+        let fst1 = Arc::new(dense_fst(50, 0)?);
+        let fst2 = Arc::new(dense_fst(40, 10)?);
+
+        let ref_fst: VectorFst<_> =
+            ShardedComposeFst::new_with_options(fst1.clone(), fst2.clone(), Default::default())?
+                .compute()?;
+
+        let compose_fst = Arc::new(ShardedComposeFst::new_with_options_and_cache(
+            fst1,
+            fst2,
+            Default::default(),
+            ShardedCache::new(4),
+        )?);
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let compose_fst = Arc::clone(&compose_fst);
+                thread::spawn(move || -> Result<Vec<F>> {
+                    (0..5).map(|_| compose_fst.compute()).collect()
+                })
+            })
+            .collect();
+
+        let mut results = vec![];
+        for handle in handles {
+            results.extend(handle.join().unwrap()?);
+        }
+        // The state ids depend on the order in which the threads discovered the states.
+        assert!(isomorphic(&results[0], &ref_fst)?);
+        for computed in results.iter() {
+            assert_eq!(computed, &results[0]);
+        }
+        Ok(())
+    }
+}
@@ -12,13 +12,15 @@ use unsafe_unwrap::UnsafeUnwrap;
 
 use crate::algorithms::lazy::cache::CacheStatus;
 use crate::algorithms::lazy::fst_op::{AccessibleOpState, FstOp, SerializableOpState};
+#[cfg(feature = "rayon")]
+use crate::algorithms::lazy::parallel;
 use crate::algorithms::lazy::{FstCache, SerializableCache};
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::{
     AllocableFst, CoreFst, Fst, FstIterData, FstIterator, MutableFst, StateIterator,
 };
 use crate::semirings::{Semiring, SerializableSemiring};
-use crate::{StateId, SymbolTable, Trs, TrsVec};
+use crate::{StateId, SymbolTable, Trs, TrsVec, EPS_LABEL};
 
 #[derive(Debug, Clone)]
 pub struct LazyFst<W: Semiring, Op: FstOp<W>, Cache> {
@@ -61,13 +63,15 @@ impl<W: Semiring, Op: FstOp<W>, Cache: FstCache<W>> CoreFst<W> for LazyFst<W, Op
     }
 
     fn num_trs(&self, s: StateId) -> Result<usize> {
-        self.cache
-            .num_trs(s)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", s))
+        match self.cache.num_trs(s) {
+            Some(n) => Ok(n),
+            // The state might have been evicted from the cache.
+            None => Ok(self.get_evicted_trs(s)?.len()),
+        }
     }
 
     unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
-        self.cache.num_trs(s).unsafe_unwrap()
+        self.num_trs(s).unsafe_unwrap()
     }
 
     fn get_trs(&self, state_id: StateId) -> Result<Self::TRS> {
@@ -90,15 +94,27 @@ impl<W: Semiring, Op: FstOp<W>, Cache: FstCache<W>> CoreFst<W> for LazyFst<W, Op
     }
 
     fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
-        self.cache
-            .num_input_epsilons(state)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", state))
+        match self.cache.num_input_epsilons(state) {
+            Some(n) => Ok(n),
+            None => Ok(self
+                .get_evicted_trs(state)?
+                .trs()
+                .iter()
+                .filter(|tr| tr.ilabel == EPS_LABEL)
+                .count()),
+        }
     }
 
     fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
-        self.cache
-            .num_output_epsilons(state)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", state))
+        match self.cache.num_output_epsilons(state) {
+            Some(n) => Ok(n),
+            None => Ok(self
+                .get_evicted_trs(state)?
+                .trs()
+                .iter()
+                .filter(|tr| tr.olabel == EPS_LABEL)
+                .count()),
+        }
     }
 }
 
@@ -208,6 +224,15 @@ where
     Op: FstOp<W>,
     Cache: FstCache<W>,
 {
+    /// Re-computes the transitions of a known state that is no longer in the cache.
+    fn get_evicted_trs(&self, state_id: StateId) -> Result<TrsVec<W>> {
+        if (state_id as usize) < self.cache.num_known_states() {
+            self.get_trs(state_id)
+        } else {
+            bail!("State {:?} doesn't exist", state_id)
+        }
+    }
+
     pub fn from_op_and_cache(
         op: Op,
         cache: Cache,
@@ -270,6 +295,33 @@ where
     }
 }
 
+#[cfg(feature = "rayon")]
+impl<W, Op, Cache> LazyFst<W, Op, Cache>
+where
+    W: Semiring + Send + Sync,
+    Op: FstOp<W> + Sync,
+    Cache: FstCache<W> + Sync,
+{
+    /// Turns the Lazy FST into a static one, expanding all the states of a breadth-first
+    /// level concurrently on the rayon thread pool.
+    ///
+    /// The states are renumbered in breadth-first order, following the order of the trs. This
+    /// doesn't depend on the states already expanded : on a fresh Lazy FST whose op creates
+    /// the states as they are discovered, this is the numbering of `compute`.
+    pub fn compute_parallel<F2: MutableFst<W> + AllocableFst<W>>(&self) -> Result<F2> {
+        let mut fst_out: F2 = parallel::compute_parallel(self.start(), self.properties(), |s| {
+            Ok((self.get_trs(s)?, self.final_weight(s)?))
+        })?;
+        if let Some(isymt) = &self.isymt {
+            fst_out.set_input_symbols(Arc::clone(isymt));
+        }
+        if let Some(osymt) = &self.osymt {
+            fst_out.set_output_symbols(Arc::clone(osymt));
+        }
+        Ok(fst_out)
+    }
+}
+
 impl<W, Op, Cache> SerializableLazyFst for LazyFst<W, Op, Cache>
 where
     W: SerializableSemiring,
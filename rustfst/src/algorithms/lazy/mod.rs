@@ -9,6 +9,8 @@ mod fst_op;
 mod fst_op_2;
 mod lazy_fst;
 mod lazy_fst_2;
+#[cfg(feature = "rayon")]
+mod parallel;
 mod state_table;
 
 pub mod cache;
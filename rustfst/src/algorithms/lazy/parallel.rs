@@ -0,0 +1,75 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use anyhow::Result;
+use rayon::prelude::*;
+
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{AllocableFst, MutableFst};
+use crate::semirings::Semiring;
+use crate::{StateId, Tr, Trs, TrsVec};
+
+/// Builds a static FST by expanding the states reachable from `start` breadth-first, all the
+/// states of a level being expanded concurrently on the rayon thread pool.
+///
+/// The states are renumbered in the order they are discovered : level by level and, within a
+/// level, following the order of the trs. The ids of the output don't depend on the order in
+/// which the expansions complete nor on the ids given by the op.
+pub(crate) fn compute_parallel<W, F, E>(
+    start: Option<StateId>,
+    properties: FstProperties,
+    expand: E,
+) -> Result<F>
+where
+    W: Semiring + Send + Sync,
+    F: MutableFst<W> + AllocableFst<W>,
+    E: Fn(StateId) -> Result<(TrsVec<W>, Option<W>)> + Sync,
+{
+    let mut fst_out = F::new();
+    let start = match start {
+        Some(s) => s,
+        None => return Ok(fst_out),
+    };
+    let mut new_ids = HashMap::new();
+    new_ids.insert(start, 0);
+    fst_out.add_states(1);
+    fst_out.set_start(0)?;
+    let mut frontier = vec![start];
+    while !frontier.is_empty() {
+        let expanded = frontier
+            .par_iter()
+            .map(|&s| expand(s))
+            .collect::<Result<Vec<_>>>()?;
+        let mut next_frontier = vec![];
+        let mut level = Vec::with_capacity(frontier.len());
+        for (s, (trs_owner, final_weight)) in frontier.iter().zip(expanded) {
+            let trs = trs_owner
+                .trs()
+                .iter()
+                .map(|tr| {
+                    let new_id = new_ids.len() as StateId;
+                    let nextstate = match new_ids.entry(tr.nextstate) {
+                        Entry::Occupied(e) => *e.get(),
+                        Entry::Vacant(e) => {
+                            next_frontier.push(tr.nextstate);
+                            *e.insert(new_id)
+                        }
+                    };
+                    Tr::new(tr.ilabel, tr.olabel, tr.weight.clone(), nextstate)
+                })
+                .collect::<Vec<_>>();
+            level.push((new_ids[s], trs, final_weight));
+        }
+        fst_out.add_states(next_frontier.len());
+        for (s, trs, final_weight) in level {
+            unsafe { fst_out.set_trs_unchecked(s, trs) };
+            if let Some(f_w) = final_weight {
+                fst_out.set_final(s, f_w)?;
+            }
+        }
+        frontier = next_frontier;
+    }
+    // Renumbering the states can break the properties depending on the ids.
+    fst_out.set_properties(properties & FstProperties::statesort_properties());
+    Ok(fst_out)
+}
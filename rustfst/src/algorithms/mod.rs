@@ -3,6 +3,9 @@ pub use self::{
     all_pairs_shortest_distance::all_pairs_shortest_distance,
     condense::condense,
     connect::connect,
+    disambiguate::{disambiguate, disambiguate_with_config, DisambiguateConfig},
+    eps_normalize::{eps_normalize, EpsNormalizeType},
+    equivalent::equivalent,
     fst_convert::{fst_convert, fst_convert_from_ref},
     inversion::invert,
     isomorphic::{isomorphic, isomorphic_with_config, IsomorphicConfig},
@@ -14,6 +17,7 @@ pub use self::{
         PushWeightsConfig,
     },
     queue::{Queue, QueueType},
+    rand_equivalent::rand_equivalent,
     relabel_pairs::relabel_pairs,
     reverse::reverse,
     reweight::{reweight, ReweightType},
@@ -43,19 +47,29 @@ mod connect;
 /// Functions to determinize FSTs.
 pub mod determinize;
 pub(crate) mod dfs_visit;
+/// Functions to compute the difference between two FSAs.
+pub mod difference;
+mod disambiguate;
 /// Functions to encode FSTs as FSAs and vice versa.
 pub mod encode;
+mod eps_normalize;
+mod equivalent;
 /// Functions to factor various weight types.
 pub mod factor_weight;
 mod fst_convert;
+/// Functions to compute the intersection of two FSAs.
+pub mod intersect;
 mod inversion;
 mod isomorphic;
 mod minimize;
 mod optimize;
 mod partition;
 mod projection;
+/// Functions to prune an FST. A static and a delayed version are available.
+pub mod prune;
 mod push;
 mod queue;
+mod rand_equivalent;
 
 /// Functions to randomly generate paths through an Fst. A static and a delayed version are available.
 pub mod randgen;
@@ -71,6 +85,8 @@ mod rm_final_epsilon;
 mod shortest_distance;
 mod shortest_path;
 mod state_sort;
+/// Functions to synchronize an FST. A static and a delayed version are available.
+pub mod synchronize;
 mod top_sort;
 mod tr_map;
 mod tr_sort;
@@ -78,6 +94,7 @@ mod tr_sum;
 pub(crate) mod tr_unique;
 /// Functions to compute the union of FSTs.
 pub mod union;
+pub(crate) mod union_find;
 mod weight_convert;
 
 /// Module providing different structures implementing the `Queue` trait.
@@ -0,0 +1,9 @@
+mod prune_fst;
+mod prune_op;
+mod prune_state;
+mod prune_static;
+
+pub use prune_fst::PruneFst;
+use prune_state::PruneState;
+pub use prune_static::prune;
+pub(crate) use prune_static::prune_with_any_semiring;
@@ -0,0 +1,247 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::lazy::{FstCache, LazyFst, SimpleHashMapCache};
+use crate::algorithms::prune::prune_op::PruneOp;
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{
+    AllocableFst, CoreFst, ExpandedFst, Fst, FstIterator, MutableFst, StateIterator,
+};
+use crate::semirings::WeaklyDivisibleSemiring;
+use crate::{StateId, SymbolTable, TrsVec};
+
+type InnerLazyFst<W, F, B, Cache> = LazyFst<W, PruneOp<W, F, B>, Cache>;
+
+/// Prune an FST, removing all the states and transitions that do not belong to a
+/// successful path whose weight is below `weight_threshold` times the weight of the
+/// shortest path w.r.t the natural semiring order. The forward and backward shortest
+/// distances are computed at construction but the pruned transitions are only
+/// computed on demand. This version is a Delayed FST.
+///
+/// The states of the input FST are kept with the same ids. The states that don't survive
+/// the pruning are simply not reachable.
+pub struct PruneFst<
+    W: WeaklyDivisibleSemiring,
+    F: ExpandedFst<W>,
+    B: Borrow<F>,
+    Cache = SimpleHashMapCache<W>,
+>(InnerLazyFst<W, F, B, Cache>);
+
+impl<W, F, B, Cache> CoreFst<W> for PruneFst<W, F, B, Cache>
+where
+    W: WeaklyDivisibleSemiring,
+    F: ExpandedFst<W>,
+    B: Borrow<F>,
+    Cache: FstCache<W>,
+{
+    type TRS = TrsVec<W>;
+
+    fn start(&self) -> Option<StateId> {
+        self.0.start()
+    }
+
+    fn final_weight(&self, state_id: StateId) -> Result<Option<W>> {
+        self.0.final_weight(state_id)
+    }
+
+    unsafe fn final_weight_unchecked(&self, state_id: StateId) -> Option<W> {
+        self.0.final_weight_unchecked(state_id)
+    }
+
+    fn num_trs(&self, s: StateId) -> Result<usize> {
+        self.0.num_trs(s)
+    }
+
+    unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
+        self.0.num_trs_unchecked(s)
+    }
+
+    fn get_trs(&self, state_id: StateId) -> Result<Self::TRS> {
+        self.0.get_trs(state_id)
+    }
+
+    unsafe fn get_trs_unchecked(&self, state_id: StateId) -> Self::TRS {
+        self.0.get_trs_unchecked(state_id)
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.0.properties()
+    }
+
+    fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_input_epsilons(state)
+    }
+
+    fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_output_epsilons(state)
+    }
+}
+
+impl<'a, W, F, B, Cache> StateIterator<'a> for PruneFst<W, F, B, Cache>
+where
+    W: WeaklyDivisibleSemiring,
+    F: ExpandedFst<W> + 'a,
+    B: Borrow<F> + 'a,
+    Cache: FstCache<W> + 'a,
+{
+    type Iter = <InnerLazyFst<W, F, B, Cache> as StateIterator<'a>>::Iter;
+
+    fn states_iter(&'a self) -> Self::Iter {
+        self.0.states_iter()
+    }
+}
+
+impl<'a, W, F, B, Cache> FstIterator<'a, W> for PruneFst<W, F, B, Cache>
+where
+    W: WeaklyDivisibleSemiring,
+    F: ExpandedFst<W> + 'a,
+    B: Borrow<F> + 'a,
+    Cache: FstCache<W> + 'a,
+{
+    type FstIter = <InnerLazyFst<W, F, B, Cache> as FstIterator<'a, W>>::FstIter;
+
+    fn fst_iter(&'a self) -> Self::FstIter {
+        self.0.fst_iter()
+    }
+}
+
+impl<W, F, B, Cache> Fst<W> for PruneFst<W, F, B, Cache>
+where
+    W: WeaklyDivisibleSemiring,
+    F: ExpandedFst<W> + 'static,
+    B: Borrow<F> + 'static,
+    Cache: FstCache<W> + 'static,
+{
+    fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.input_symbols()
+    }
+
+    fn output_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.output_symbols()
+    }
+
+    fn set_input_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_input_symbols(symt)
+    }
+
+    fn set_output_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_output_symbols(symt)
+    }
+
+    fn take_input_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_input_symbols()
+    }
+
+    fn take_output_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_output_symbols()
+    }
+}
+
+impl<W, F, B, Cache> Debug for PruneFst<W, F, B, Cache>
+where
+    W: WeaklyDivisibleSemiring,
+    F: ExpandedFst<W>,
+    B: Borrow<F>,
+    Cache: FstCache<W>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<W, F, B> PruneFst<W, F, B>
+where
+    W: WeaklyDivisibleSemiring,
+    F: ExpandedFst<W>,
+    B: Borrow<F>,
+{
+    /// Creates a lazy FST pruning `fst` with the same thresholds as `prune`. The shortest
+    /// distances needed by the pruning are computed at construction.
+    pub fn new(fst: B, weight_threshold: W, state_threshold: Option<StateId>) -> Result<Self> {
+        Self::new_with_cache(
+            fst,
+            weight_threshold,
+            state_threshold,
+            SimpleHashMapCache::default(),
+        )
+    }
+}
+
+impl<W, F, B, Cache> PruneFst<W, F, B, Cache>
+where
+    W: WeaklyDivisibleSemiring,
+    F: ExpandedFst<W>,
+    B: Borrow<F>,
+    Cache: FstCache<W>,
+{
+    /// Same as `new` but the computed states are stored in `fst_cache`.
+    pub fn new_with_cache(
+        fst: B,
+        weight_threshold: W,
+        state_threshold: Option<StateId>,
+        fst_cache: Cache,
+    ) -> Result<Self> {
+        let isymt = fst.borrow().input_symbols().cloned();
+        let osymt = fst.borrow().output_symbols().cloned();
+        let fst_op = PruneOp::new(fst, weight_threshold, state_threshold)?;
+        let lazy_fst = LazyFst::from_op_and_cache(fst_op, fst_cache, isymt, osymt);
+        Ok(PruneFst(lazy_fst))
+    }
+
+    /// Turns the Lazy FST into a static one.
+    pub fn compute<F2: MutableFst<W> + AllocableFst<W>>(&self) -> Result<F2> {
+        self.0.compute()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithms::prune::prune;
+    use crate::algorithms::{connect, isomorphic};
+    use crate::fst_impls::VectorFst;
+    use crate::semirings::{Semiring, TropicalWeight};
+    use crate::Tr;
+
+    #[test]
+    fn test_prune_fst_sync() {
+        fn is_sync<T: Sync>() {}
+        is_sync::<PruneFst<TropicalWeight, VectorFst<_>, VectorFst<_>>>();
+    }
+
+    #[test]
+    fn test_prune_fst_same_as_static() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(5);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(2, 2, 3.0, 2))?;
+        fst.add_tr(1, Tr::new(3, 3, 1.0, 3))?;
+        fst.add_tr(2, Tr::new(4, 4, 4.0, 3))?;
+        fst.add_tr(1, Tr::new(5, 5, 2.0, 4))?;
+        fst.add_tr(4, Tr::new(6, 6, 0.5, 1))?;
+        fst.set_final(3, 0.5)?;
+
+        for (weight_threshold, state_threshold) in &[
+            (3.0, None),
+            (TropicalWeight::zero().take_value(), Some(3)),
+            (5.0, Some(4)),
+        ] {
+            let weight_threshold = TropicalWeight::new(*weight_threshold);
+            let mut fst_static = fst.clone();
+            prune(&mut fst_static, weight_threshold, *state_threshold)?;
+
+            let prune_fst: PruneFst<_, VectorFst<_>, _> =
+                PruneFst::new(&fst, weight_threshold, *state_threshold)?;
+            let mut fst_lazy: VectorFst<_> = prune_fst.compute()?;
+            connect(&mut fst_lazy)?;
+            connect(&mut fst_static)?;
+
+            assert!(isomorphic(&fst_static, &fst_lazy)?);
+        }
+        Ok(())
+    }
+}
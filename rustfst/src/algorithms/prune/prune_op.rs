@@ -0,0 +1,72 @@
+use std::borrow::Borrow;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::lazy::FstOp;
+use crate::algorithms::prune::PruneState;
+use crate::fst_properties::mutable_properties::{delete_states_properties, delete_trs_properties};
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::ExpandedFst;
+use crate::semirings::WeaklyDivisibleSemiring;
+use crate::{StateId, TrsVec};
+
+pub struct PruneOp<W: WeaklyDivisibleSemiring, F: ExpandedFst<W>, B: Borrow<F>> {
+    fst: B,
+    prune_state: PruneState<W>,
+    properties: FstProperties,
+    f: PhantomData<F>,
+}
+
+impl<W: WeaklyDivisibleSemiring, F: ExpandedFst<W>, B: Borrow<F>> std::fmt::Debug
+    for PruneOp<W, F, B>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "PruneOp {{ prune_state : {:?}, fst : {:?} }}",
+            self.prune_state,
+            self.fst.borrow()
+        )
+    }
+}
+
+impl<W: WeaklyDivisibleSemiring, F: ExpandedFst<W>, B: Borrow<F>> PruneOp<W, F, B> {
+    pub fn new(fst: B, weight_threshold: W, state_threshold: Option<StateId>) -> Result<Self> {
+        let prune_state = PruneState::new(fst.borrow(), weight_threshold, state_threshold)?;
+        let properties = delete_states_properties(delete_trs_properties(fst.borrow().properties()));
+        Ok(Self {
+            fst,
+            prune_state,
+            properties,
+            f: PhantomData,
+        })
+    }
+}
+
+impl<W: WeaklyDivisibleSemiring, F: ExpandedFst<W>, B: Borrow<F>> FstOp<W> for PruneOp<W, F, B> {
+    fn compute_start(&self) -> Result<Option<StateId>> {
+        Ok(self
+            .fst
+            .borrow()
+            .start()
+            .filter(|s| self.prune_state.is_kept(*s)))
+    }
+
+    fn compute_trs(&self, state: StateId) -> Result<TrsVec<W>> {
+        let trs = self
+            .prune_state
+            .kept_trs(state, &self.fst.borrow().get_trs(state)?)?;
+        Ok(TrsVec(Arc::new(trs)))
+    }
+
+    fn compute_final_weight(&self, state: StateId) -> Result<Option<W>> {
+        self.prune_state
+            .kept_final_weight(state, self.fst.borrow().final_weight(state)?)
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.properties
+    }
+}
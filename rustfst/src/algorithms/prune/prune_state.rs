@@ -0,0 +1,182 @@
+use std::cmp::Ordering;
+
+use anyhow::Result;
+use binary_heap_plus::BinaryHeap;
+
+use crate::algorithms::queues::natural_less;
+use crate::algorithms::shortest_distance;
+use crate::fst_traits::ExpandedFst;
+use crate::semirings::{Semiring, SemiringProperties};
+use crate::{StateId, Tr, Trs};
+
+/// Result of the best-first exploration shared by the static and the delayed prune.
+///
+/// A state is kept if it has been visited by the exploration. `idistance[s]` is the
+/// shortest distance from the initial state to `s` at the time `s` was visited and
+/// `fdistance[s]` the shortest distance from `s` to the final states.
+#[derive(Debug, Clone)]
+pub(crate) struct PruneState<W: Semiring> {
+    idistance: Vec<Option<W>>,
+    fdistance: Vec<W>,
+    limit: W,
+}
+
+impl<W: Semiring> PruneState<W> {
+    pub fn new<F: ExpandedFst<W>>(
+        fst: &F,
+        weight_threshold: W,
+        state_threshold: Option<StateId>,
+    ) -> Result<Self> {
+        if !W::properties().contains(SemiringProperties::PATH) {
+            bail!("Prune: Weight needs to have the path property")
+        }
+        let fdistance = shortest_distance(fst, true)?;
+        let mut idistance = vec![None; fst.num_states()];
+
+        let start = match fst.start() {
+            Some(s) if (s as usize) < fdistance.len() => s,
+            _ => {
+                return Ok(Self {
+                    idistance,
+                    fdistance,
+                    limit: W::zero(),
+                })
+            }
+        };
+        if state_threshold == Some(0) || fdistance[start as usize].is_zero() {
+            return Ok(Self {
+                idistance,
+                fdistance,
+                limit: W::zero(),
+            });
+        }
+
+        let limit = if weight_threshold.is_zero() {
+            W::zero()
+        } else {
+            fdistance[start as usize].times(&weight_threshold)?
+        };
+
+        // Lowest weights w.r.t the natural order are popped first.
+        let mut heap = BinaryHeap::new_by(|a: &(W, StateId), b: &(W, StateId)| {
+            if natural_less(&a.0, &b.0).unwrap() {
+                Ordering::Greater
+            } else if natural_less(&b.0, &a.0).unwrap() {
+                Ordering::Less
+            } else {
+                b.1.cmp(&a.1)
+            }
+        });
+        let mut tentative = vec![W::zero(); fst.num_states()];
+        let mut enqueued = vec![false; fst.num_states()];
+        let mut num_visited = 0;
+
+        if !natural_less(&limit, &fdistance[start as usize])? {
+            tentative[start as usize] = W::one();
+            enqueued[start as usize] = true;
+            heap.push((fdistance[start as usize].clone(), start));
+            num_visited += 1;
+        }
+
+        while let Some((_, s)) = heap.pop() {
+            // A state can be pushed several times when its distance improves.
+            if idistance[s as usize].is_some() {
+                continue;
+            }
+            let d = tentative[s as usize].clone();
+            for tr in fst.get_trs(s)?.trs() {
+                let nextstate = tr.nextstate as usize;
+                let fd = fdistance.get(nextstate).cloned().unwrap_or_else(W::zero);
+                let weight = d.times(&tr.weight)?;
+                if natural_less(&limit, &weight.times(&fd)?)? {
+                    continue;
+                }
+                if natural_less(&weight, &tentative[nextstate])? {
+                    tentative[nextstate] = weight;
+                    if enqueued[nextstate] && idistance[nextstate].is_none() {
+                        heap.push((tentative[nextstate].times(&fd)?, tr.nextstate));
+                    }
+                }
+                if idistance[nextstate].is_some() {
+                    continue;
+                }
+                if let Some(state_threshold) = state_threshold {
+                    if num_visited >= state_threshold {
+                        continue;
+                    }
+                }
+                if !enqueued[nextstate] {
+                    enqueued[nextstate] = true;
+                    heap.push((tentative[nextstate].times(&fd)?, tr.nextstate));
+                    num_visited += 1;
+                }
+            }
+            idistance[s as usize] = Some(d);
+        }
+
+        Ok(Self {
+            idistance,
+            fdistance,
+            limit,
+        })
+    }
+
+    /// Returns true if the state `s` survives the pruning.
+    pub fn is_kept(&self, s: StateId) -> bool {
+        matches!(self.idistance.get(s as usize), Some(Some(_)))
+    }
+
+    /// Returns the final weight of the state `s` if it survives the pruning.
+    pub fn kept_final_weight(&self, s: StateId, final_weight: Option<W>) -> Result<Option<W>> {
+        match (&self.idistance[s as usize], final_weight) {
+            (Some(d), Some(final_weight)) => {
+                if natural_less(&self.limit, &d.times(&final_weight)?)? {
+                    Ok(None)
+                } else {
+                    Ok(Some(final_weight))
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the indices of the transitions leaving `s` that don't survive the pruning.
+    /// The indices are sorted.
+    pub fn pruned_trs<T: Trs<W>>(&self, s: StateId, trs: &T) -> Result<Vec<usize>> {
+        let mut pruned = vec![];
+        for (idx, tr) in trs.trs().iter().enumerate() {
+            if !self.keep_tr(s, tr)? {
+                pruned.push(idx);
+            }
+        }
+        Ok(pruned)
+    }
+
+    /// Returns the transitions leaving `s` that survive the pruning.
+    pub fn kept_trs<T: Trs<W>>(&self, s: StateId, trs: &T) -> Result<Vec<Tr<W>>> {
+        let mut kept = vec![];
+        for tr in trs.trs() {
+            if self.keep_tr(s, tr)? {
+                kept.push(tr.clone());
+            }
+        }
+        Ok(kept)
+    }
+
+    fn keep_tr(&self, s: StateId, tr: &Tr<W>) -> Result<bool> {
+        let d = match &self.idistance[s as usize] {
+            Some(d) => d,
+            None => return Ok(false),
+        };
+        if !self.is_kept(tr.nextstate) {
+            return Ok(false);
+        }
+        let fd = self
+            .fdistance
+            .get(tr.nextstate as usize)
+            .cloned()
+            .unwrap_or_else(W::zero);
+        let weight = d.times(&tr.weight)?.times(fd)?;
+        Ok(!natural_less(&self.limit, &weight)?)
+    }
+}
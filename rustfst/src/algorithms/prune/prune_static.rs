@@ -0,0 +1,171 @@
+use anyhow::Result;
+
+use crate::algorithms::prune::PruneState;
+use crate::fst_traits::MutableFst;
+use crate::semirings::{Semiring, WeaklyDivisibleSemiring};
+use crate::StateId;
+
+/// Prune an FST, removing all the states and transitions that do not belong to a
+/// successful path whose weight is below `weight_threshold` times the weight of the
+/// shortest path w.r.t the natural semiring order.
+///
+/// If `state_threshold` is provided, the number of states visited (and thus kept) is
+/// also limited to `state_threshold`, the states being explored by increasing weight
+/// of the best successful path going through them.
+///
+/// A `weight_threshold` equal to `W::zero()` disables the pruning on the weights.
+/// The weights need to be weakly divisible and to have the `PATH` property so that the
+/// natural order is a total order.
+///
+/// # Example
+/// ```
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::{CoreFst, ExpandedFst, MutableFst};
+/// # use rustfst::algorithms::prune::prune;
+/// # use rustfst::Tr;
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// let mut fst = VectorFst::<TropicalWeight>::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// let s2 = fst.add_state();
+/// fst.set_start(s0)?;
+/// fst.add_tr(s0, Tr::new(1, 1, 1.0, s1))?;
+/// fst.add_tr(s0, Tr::new(2, 2, 5.0, s2))?;
+/// fst.set_final(s1, 0.0)?;
+/// fst.set_final(s2, 0.0)?;
+///
+/// prune(&mut fst, TropicalWeight::new(2.0), None)?;
+///
+/// let mut fst_ref = VectorFst::<TropicalWeight>::new();
+/// let s0 = fst_ref.add_state();
+/// let s1 = fst_ref.add_state();
+/// fst_ref.set_start(s0)?;
+/// fst_ref.add_tr(s0, Tr::new(1, 1, 1.0, s1))?;
+/// fst_ref.set_final(s1, 0.0)?;
+///
+/// assert_eq!(fst, fst_ref);
+/// # Ok(())
+/// # }
+/// ```
+pub fn prune<W: WeaklyDivisibleSemiring, F: MutableFst<W>>(
+    fst: &mut F,
+    weight_threshold: W,
+    state_threshold: Option<StateId>,
+) -> Result<()> {
+    prune_with_any_semiring(fst, weight_threshold, state_threshold)
+}
+
+/// Same as `prune` but only checks the `PATH` property at runtime. Used by the algorithms
+/// generic over any semiring that prune their output, like `rm_epsilon`.
+pub(crate) fn prune_with_any_semiring<W: Semiring, F: MutableFst<W>>(
+    fst: &mut F,
+    weight_threshold: W,
+    state_threshold: Option<StateId>,
+) -> Result<()> {
+    if fst.num_states() == 0 {
+        return Ok(());
+    }
+    let prune_state = PruneState::new(fst, weight_threshold, state_threshold)?;
+
+    let mut dead = vec![];
+    for s in 0..(fst.num_states() as StateId) {
+        if !prune_state.is_kept(s) {
+            dead.push(s);
+            continue;
+        }
+        let final_weight = fst.final_weight(s)?;
+        if final_weight.is_some() && prune_state.kept_final_weight(s, final_weight)?.is_none() {
+            fst.delete_final_weight(s)?;
+        }
+        let to_del = prune_state.pruned_trs(s, &fst.get_trs(s)?)?;
+        unsafe { fst.del_trs_id_sorted_unchecked(s, &to_del) };
+    }
+
+    if dead.len() == fst.num_states() {
+        fst.del_all_states();
+    } else {
+        fst.del_states(dead)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{CoreFst, ExpandedFst};
+    use crate::semirings::{LogWeight, TropicalWeight};
+    use crate::Tr;
+
+    fn build_fst() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(2, 2, 3.0, 2))?;
+        fst.add_tr(1, Tr::new(3, 3, 1.0, 3))?;
+        fst.add_tr(2, Tr::new(4, 4, 1.0, 3))?;
+        fst.add_tr(1, Tr::new(5, 5, 6.0, 3))?;
+        fst.set_final(3, 0.5)?;
+        Ok(fst)
+    }
+
+    #[test]
+    fn test_prune_weight_threshold() -> Result<()> {
+        let mut fst = build_fst()?;
+        prune(&mut fst, TropicalWeight::new(2.5), None)?;
+
+        let mut fst_ref = VectorFst::<TropicalWeight>::new();
+        fst_ref.add_states(4);
+        fst_ref.set_start(0)?;
+        fst_ref.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+        fst_ref.add_tr(0, Tr::new(2, 2, 3.0, 2))?;
+        fst_ref.add_tr(1, Tr::new(3, 3, 1.0, 3))?;
+        fst_ref.add_tr(2, Tr::new(4, 4, 1.0, 3))?;
+        fst_ref.set_final(3, 0.5)?;
+
+        assert_eq!(fst, fst_ref);
+        Ok(())
+    }
+
+    #[test]
+    fn test_prune_no_threshold() -> Result<()> {
+        let mut fst = build_fst()?;
+        let fst_ref = fst.clone();
+        prune(&mut fst, TropicalWeight::zero(), None)?;
+        assert_eq!(fst, fst_ref);
+        Ok(())
+    }
+
+    #[test]
+    fn test_prune_state_threshold() -> Result<()> {
+        let mut fst = build_fst()?;
+        prune(&mut fst, TropicalWeight::zero(), Some(3))?;
+
+        let mut fst_ref = VectorFst::<TropicalWeight>::new();
+        fst_ref.add_states(3);
+        fst_ref.set_start(0)?;
+        fst_ref.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+        fst_ref.add_tr(0, Tr::new(2, 2, 3.0, 2))?;
+
+        assert_eq!(fst, fst_ref);
+
+        let mut fst = build_fst()?;
+        prune(&mut fst, TropicalWeight::zero(), Some(0))?;
+        assert_eq!(fst.num_states(), 0);
+        assert_eq!(fst.start(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_prune_requires_path_property() -> Result<()> {
+        let mut fst = VectorFst::<LogWeight>::new();
+        let s = fst.add_state();
+        fst.set_start(s)?;
+        fst.set_final(s, 0.0)?;
+        assert!(prune(&mut fst, LogWeight::new(1.0), None).is_err());
+        Ok(())
+    }
+}
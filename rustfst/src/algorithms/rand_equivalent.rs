@@ -0,0 +1,223 @@
+use anyhow::Result;
+use rand::Rng;
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::algorithms::compose::compose;
+use crate::algorithms::randgen::{randgen_with_config, RandGenConfig, UniformTrSelector};
+use crate::algorithms::tr_compares::{ILabelCompare, OLabelCompare};
+use crate::algorithms::{
+    connect, fst_convert_from_ref, project, shortest_distance, tr_sort, weight_convert, FinalTr,
+    MapFinalAction, ProjectType, WeightConverter,
+};
+use crate::fst_impls::VectorFst;
+use crate::fst_properties::{compute_fst_properties, FstProperties};
+use crate::fst_traits::{CoreFst, ExpandedFst};
+use crate::semirings::{Semiring, SemiringProperties, TropicalWeight};
+use crate::{Tr, KDELTA};
+
+/// Mapper replacing all the weights by `one`. The paths are sampled on such an unweighted
+/// copy as `randgen` only supports `f32` weights and the uniform sampling doesn't
+/// depend on the weights.
+struct UnweightedConverter {}
+
+impl<SI: Semiring, SO: Semiring> WeightConverter<SI, SO> for UnweightedConverter {
+    fn tr_map(&mut self, tr: &Tr<SI>) -> Result<Tr<SO>> {
+        Ok(Tr::new(tr.ilabel, tr.olabel, SO::one(), tr.nextstate))
+    }
+
+    fn final_tr_map(&mut self, final_tr: &FinalTr<SI>) -> Result<FinalTr<SO>> {
+        Ok(FinalTr {
+            ilabel: final_tr.ilabel,
+            olabel: final_tr.olabel,
+            weight: SO::one(),
+        })
+    }
+
+    fn final_action(&self) -> MapFinalAction {
+        MapFinalAction::MapNoSuperfinal
+    }
+
+    fn properties(&self, inprops: FstProperties) -> FstProperties {
+        inprops & FstProperties::weight_invariant_properties()
+    }
+}
+
+/// Sum of the weights of all the successful paths of an FST.
+fn total_weight<W: Semiring>(fst: &VectorFst<W>) -> Result<W> {
+    let distances = shortest_distance(fst, false)?;
+    let mut sum = W::zero();
+    for (state, distance) in distances.iter().enumerate() {
+        if let Some(final_weight) = fst.final_weight(state as _)? {
+            sum.plus_assign(distance.times(final_weight)?)?;
+        }
+    }
+    Ok(sum)
+}
+
+/// Weight of the input/output string pair of `path` in `fst`.
+fn path_weight<W: Semiring>(
+    ipath: &VectorFst<W>,
+    fst: &VectorFst<W>,
+    opath: &VectorFst<W>,
+) -> Result<W> {
+    let mut cfst: VectorFst<W> = compose::<W, VectorFst<W>, VectorFst<W>, _, _, _>(ipath, fst)?;
+    tr_sort(&mut cfst, OLabelCompare {});
+    let pfst: VectorFst<W> = compose::<W, VectorFst<W>, VectorFst<W>, _, _, _>(&cfst, opath)?;
+    // The total weight of epsilon cycles can't be computed in a non-idempotent semiring.
+    if !W::properties().contains(SemiringProperties::IDEMPOTENT) {
+        let mut known = FstProperties::empty();
+        let props = compute_fst_properties(&pfst, FstProperties::CYCLIC, &mut known, true)?;
+        if props.contains(FstProperties::CYCLIC) {
+            bail!("RandEquivalent: Cyclic composed FST with a non-idempotent semiring");
+        }
+    }
+    total_weight(&pfst)
+}
+
+/// Tests whether two FSTs are equivalent by randomly generating `npath` paths
+/// alternatively from each FST. For each path, the weight of its input/output
+/// string pair is computed in both FSTs and the results are compared.
+///
+/// This test is probabilistic : a return value of `false` means the FSTs are not
+/// equivalent, whereas `true` only means no counter example has been found. Contrary
+/// to `equivalent`, it is not restricted to deterministic acceptors.
+///
+/// An error is returned if the weight of a sampled string pair can't be computed, i.e if
+/// it is read through an epsilon cycle in a non-idempotent semiring.
+///
+/// # Example
+/// ```
+/// # use anyhow::Result;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::algorithms::{optimize, rand_equivalent};
+/// # use rustfst::algorithms::union::union;
+/// # use rustfst::utils::transducer;
+/// # fn main() -> Result<()> {
+/// let mut fst: VectorFst<TropicalWeight> = transducer(&[1, 2], &[3, 4], TropicalWeight::new(1.0));
+/// let fst2: VectorFst<TropicalWeight> = transducer(&[1, 3], &[5], TropicalWeight::new(2.0));
+/// union(&mut fst, &fst2)?;
+///
+/// let mut optimized_fst = fst.clone();
+/// optimize(&mut optimized_fst)?;
+///
+/// assert!(rand_equivalent(&fst, &optimized_fst, 20, 42)?);
+/// # Ok(())
+/// # }
+/// ```
+pub fn rand_equivalent<W, F1, F2>(fst1: &F1, fst2: &F2, npath: usize, seed: u64) -> Result<bool>
+where
+    W: Semiring,
+    F1: ExpandedFst<W>,
+    F2: ExpandedFst<W>,
+{
+    let mut sfst1: VectorFst<W> = fst_convert_from_ref(fst1);
+    let mut sfst2: VectorFst<W> = fst_convert_from_ref(fst2);
+    connect(&mut sfst1)?;
+    connect(&mut sfst2)?;
+    if sfst1.start().is_none() || sfst2.start().is_none() {
+        return Ok(sfst1.start().is_none() && sfst2.start().is_none());
+    }
+    tr_sort(&mut sfst1, ILabelCompare {});
+    tr_sort(&mut sfst2, ILabelCompare {});
+    let ufst1: VectorFst<TropicalWeight> = weight_convert(&sfst1, &mut UnweightedConverter {})?;
+    let ufst2: VectorFst<TropicalWeight> = weight_convert(&sfst2, &mut UnweightedConverter {})?;
+
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    for _ in 0..npath {
+        let fst = if rng.gen() { &ufst1 } else { &ufst2 };
+        let config = RandGenConfig::new(UniformTrSelector::from_seed(rng.gen()));
+        let path: VectorFst<TropicalWeight> = randgen_with_config(fst, config)?;
+        let path: VectorFst<W> = weight_convert(&path, &mut UnweightedConverter {})?;
+
+        let mut ipath = path.clone();
+        project(&mut ipath, ProjectType::ProjectInput);
+        let mut opath = path;
+        project(&mut opath, ProjectType::ProjectOutput);
+
+        let weight1 = path_weight(&ipath, &sfst1, &opath)?;
+        let weight2 = path_weight(&ipath, &sfst2, &opath)?;
+        if !weight1.approx_equal(weight2, KDELTA) {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::algorithms::union::union;
+    use crate::fst_traits::MutableFst;
+    use crate::semirings::{LogWeight, LogWeight64, TropicalWeight64};
+    use crate::utils::transducer;
+    use crate::EPS_LABEL;
+
+    #[test]
+    fn test_rand_equivalent_same_fst() -> Result<()> {
+        let mut fst: VectorFst<LogWeight> = transducer(&[1, 2], &[3], LogWeight::new(1.0));
+        union(
+            &mut fst,
+            &transducer::<_, VectorFst<_>>(&[1, 2], &[3], LogWeight::new(2.0)),
+        )?;
+        let mut fst2: VectorFst<LogWeight> = transducer(&[1, 2], &[3], LogWeight::new(2.0));
+        union(
+            &mut fst2,
+            &transducer::<_, VectorFst<_>>(&[1, 2], &[3], LogWeight::new(1.0)),
+        )?;
+        assert!(rand_equivalent(&fst, &fst2, 10, 2022)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rand_equivalent_different_weights() -> Result<()> {
+        let fst1: VectorFst<TropicalWeight> =
+            transducer(&[1, 2], &[3, 4], TropicalWeight::new(1.0));
+        let fst2: VectorFst<TropicalWeight> =
+            transducer(&[1, 2], &[3, 4], TropicalWeight::new(2.0));
+        assert!(!rand_equivalent(&fst1, &fst2, 10, 2022)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rand_equivalent_different_languages() -> Result<()> {
+        let fst1: VectorFst<TropicalWeight> = transducer(&[1, 2], &[3, 4], TropicalWeight::one());
+        let fst2: VectorFst<TropicalWeight> = transducer(&[1, 2], &[3], TropicalWeight::one());
+        assert!(!rand_equivalent(&fst1, &fst2, 10, 2022)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rand_equivalent_64_bits_weights() -> Result<()> {
+        let mut fst: VectorFst<LogWeight64> = transducer(&[1, 2], &[3], LogWeight64::new(1.0));
+        union(
+            &mut fst,
+            &transducer::<_, VectorFst<_>>(&[1, 2], &[3], LogWeight64::new(2.0)),
+        )?;
+        let fst2: VectorFst<LogWeight64> = transducer(
+            &[1, 2],
+            &[3],
+            LogWeight64::new(1.0).plus(LogWeight64::new(2.0))?,
+        );
+        assert!(rand_equivalent(&fst, &fst2, 10, 2022)?);
+
+        let fst1: VectorFst<TropicalWeight64> =
+            transducer(&[1, 2], &[3, 4], TropicalWeight64::new(1.0));
+        let fst2: VectorFst<TropicalWeight64> =
+            transducer(&[1, 2], &[3, 4], TropicalWeight64::new(2.0));
+        assert!(!rand_equivalent(&fst1, &fst2, 10, 2022)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rand_equivalent_epsilon_cycle_non_idempotent() -> Result<()> {
+        let mut fst1: VectorFst<LogWeight> = transducer(&[1], &[2], LogWeight::new(1.0));
+        fst1.add_tr(0, Tr::new(EPS_LABEL, EPS_LABEL, LogWeight::new(3.0), 0))?;
+        let fst2: VectorFst<LogWeight> = transducer(&[1], &[2], LogWeight::new(1.0));
+        assert!(rand_equivalent(&fst1, &fst2, 10, 2022).is_err());
+        Ok(())
+    }
+}
@@ -4,7 +4,7 @@ use std::sync::Arc;
 
 use anyhow::Result;
 
-use crate::algorithms::lazy::{LazyFst, SimpleHashMapCache};
+use crate::algorithms::lazy::{FstCache, LazyFst, SimpleHashMapCache};
 use crate::algorithms::replace::config::ReplaceFstOptions;
 use crate::algorithms::replace::replace_fst_op::ReplaceFstOp;
 use crate::fst_properties::FstProperties;
@@ -12,12 +12,14 @@ use crate::fst_traits::{AllocableFst, CoreFst, Fst, FstIterator, MutableFst, Sta
 use crate::semirings::Semiring;
 use crate::{Label, StateId, SymbolTable, TrsVec};
 
-type InnerLazyFst<W, F, B> = LazyFst<W, ReplaceFstOp<W, F, B>, SimpleHashMapCache<W>>;
+type InnerLazyFst<W, F, B, Cache> = LazyFst<W, ReplaceFstOp<W, F, B>, Cache>;
 
 /// ReplaceFst supports lazy replacement of trs in one FST with another FST.
 /// This replacement is recursive. ReplaceFst can be used to support a variety of
 /// delayed constructions such as recursive transition networks, union, or closure.
-pub struct ReplaceFst<W: Semiring, F: Fst<W>, B: Borrow<F>>(InnerLazyFst<W, F, B>);
+pub struct ReplaceFst<W: Semiring, F: Fst<W>, B: Borrow<F>, Cache = SimpleHashMapCache<W>>(
+    InnerLazyFst<W, F, B, Cache>,
+);
 
 impl<W, F, B> ReplaceFst<W, F, B>
 where
@@ -26,6 +28,29 @@ where
     B: Borrow<F>,
 {
     pub fn new(fst_list: Vec<(Label, B)>, root: Label, epsilon_on_replace: bool) -> Result<Self> {
+        Self::new_with_cache(
+            fst_list,
+            root,
+            epsilon_on_replace,
+            SimpleHashMapCache::default(),
+        )
+    }
+}
+
+impl<W, F, B, Cache> ReplaceFst<W, F, B, Cache>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+    Cache: FstCache<W>,
+{
+    /// Same as `new` but the computed states are stored in `fst_cache`.
+    pub fn new_with_cache(
+        fst_list: Vec<(Label, B)>,
+        root: Label,
+        epsilon_on_replace: bool,
+        fst_cache: Cache,
+    ) -> Result<Self> {
         let mut isymt = None;
         let mut osymt = None;
         if let Some(first_elt) = fst_list.first() {
@@ -34,7 +59,6 @@ where
         }
         let opts = ReplaceFstOptions::new(root, epsilon_on_replace);
         let fst_op = ReplaceFstOp::new(fst_list, opts)?;
-        let fst_cache = SimpleHashMapCache::default();
         Ok(ReplaceFst(LazyFst::from_op_and_cache(
             fst_op, fst_cache, isymt, osymt,
         )))
@@ -44,13 +68,26 @@ where
     pub fn compute<F2: MutableFst<W> + AllocableFst<W>>(&self) -> Result<F2> {
         self.0.compute()
     }
+
+    /// Same as `compute` but the states are expanded concurrently on the rayon thread pool
+    /// and renumbered in breadth-first order.
+    #[cfg(feature = "rayon")]
+    pub fn compute_parallel<F2: MutableFst<W> + AllocableFst<W>>(&self) -> Result<F2>
+    where
+        W: Send + Sync,
+        ReplaceFstOp<W, F, B>: Sync,
+        Cache: Sync,
+    {
+        self.0.compute_parallel()
+    }
 }
 
-impl<W, F, B> CoreFst<W> for ReplaceFst<W, F, B>
+impl<W, F, B, Cache> CoreFst<W> for ReplaceFst<W, F, B, Cache>
 where
     W: Semiring,
     F: Fst<W>,
     B: Borrow<F>,
+    Cache: FstCache<W>,
 {
     type TRS = TrsVec<W>;
 
@@ -95,37 +132,40 @@ where
     }
 }
 
-impl<'a, W, F, B> StateIterator<'a> for ReplaceFst<W, F, B>
+impl<'a, W, F, B, Cache> StateIterator<'a> for ReplaceFst<W, F, B, Cache>
 where
     W: Semiring,
     F: Fst<W> + 'a,
     B: Borrow<F> + 'a,
+    Cache: FstCache<W> + 'a,
 {
-    type Iter = <InnerLazyFst<W, F, B> as StateIterator<'a>>::Iter;
+    type Iter = <InnerLazyFst<W, F, B, Cache> as StateIterator<'a>>::Iter;
 
     fn states_iter(&'a self) -> Self::Iter {
         self.0.states_iter()
     }
 }
 
-impl<'a, W, F, B> FstIterator<'a, W> for ReplaceFst<W, F, B>
+impl<'a, W, F, B, Cache> FstIterator<'a, W> for ReplaceFst<W, F, B, Cache>
 where
     W: Semiring,
     F: Fst<W> + 'a,
     B: Borrow<F> + 'a,
+    Cache: FstCache<W> + 'a,
 {
-    type FstIter = <InnerLazyFst<W, F, B> as FstIterator<'a, W>>::FstIter;
+    type FstIter = <InnerLazyFst<W, F, B, Cache> as FstIterator<'a, W>>::FstIter;
 
     fn fst_iter(&'a self) -> Self::FstIter {
         self.0.fst_iter()
     }
 }
 
-impl<W, F, B> Fst<W> for ReplaceFst<W, F, B>
+impl<W, F, B, Cache> Fst<W> for ReplaceFst<W, F, B, Cache>
 where
     W: Semiring,
     F: Fst<W> + 'static,
     B: Borrow<F> + 'static,
+    Cache: FstCache<W> + 'static,
 {
     fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
         self.0.input_symbols()
@@ -152,11 +192,12 @@ where
     }
 }
 
-impl<W, F, B> Debug for ReplaceFst<W, F, B>
+impl<W, F, B, Cache> Debug for ReplaceFst<W, F, B, Cache>
 where
     W: Semiring,
     F: Fst<W>,
     B: Borrow<F>,
+    Cache: FstCache<W>,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.0.fmt(f)
@@ -175,4 +216,46 @@ mod test {
         fn is_sync<T: Sync>() {}
         is_sync::<ReplaceFst<TropicalWeight, VectorFst<_>, VectorFst<_>>>();
     }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_replace_fst_compute_parallel() -> Result<()> {
+        use crate::fst_traits::ExpandedFst;
+
+        fn build_fst(
+            trs: &[(StateId, Label, Label, StateId)],
+            final_state: StateId,
+        ) -> Result<VectorFst<TropicalWeight>> {
+            let mut fst = VectorFst::new();
+            fst.add_states(final_state as usize + 1);
+            fst.set_start(0)?;
+            for &(s, ilabel, olabel, nextstate) in trs {
+                fst.emplace_tr(
+                    s,
+                    ilabel,
+                    olabel,
+                    TropicalWeight::new(ilabel as f32),
+                    nextstate,
+                )?;
+            }
+            fst.set_final(final_state, TropicalWeight::one())?;
+            Ok(fst)
+        }
+
+        let root = build_fst(
+            &[(0, 1, 101, 1), (0, 3, 102, 2), (1, 2, 102, 2), (1, 4, 4, 2)],
+            2,
+        )?;
+        let fst_101 = build_fst(&[(0, 5, 5, 1), (0, 6, 102, 1), (1, 7, 102, 2)], 2)?;
+        let fst_102 = build_fst(&[(0, 8, 8, 1), (0, 9, 9, 2), (1, 10, 10, 2)], 2)?;
+        let fst_list = vec![(100, &root), (101, &fst_101), (102, &fst_102)];
+
+        let serial: VectorFst<_> =
+            ReplaceFst::<_, VectorFst<_>, _>::new(fst_list.clone(), 100, false)?.compute()?;
+        let parallel: VectorFst<_> =
+            ReplaceFst::<_, VectorFst<_>, _>::new(fst_list, 100, false)?.compute_parallel()?;
+        assert!(serial.num_states() > 1);
+        assert_eq!(parallel, serial);
+        Ok(())
+    }
 }
@@ -4,26 +4,34 @@ use std::sync::Arc;
 
 use anyhow::Result;
 
-use crate::algorithms::lazy::{LazyFst2, SimpleHashMapCache};
+use crate::algorithms::lazy::{FstCache, LazyFst2, SimpleHashMapCache};
 use crate::algorithms::rm_epsilon::rm_epsilon_op::RmEpsilonOp;
 use crate::fst_properties::FstProperties;
+#[cfg(feature = "rayon")]
+use crate::fst_traits::AllocableFst;
 use crate::fst_traits::{CoreFst, Fst, FstIterator, MutableFst, StateIterator};
 use crate::{Semiring, StateId, SymbolTable, TrsVec};
 
-type InnerLazyFst<W, F, B> = LazyFst2<W, RmEpsilonOp<W, F, B>, SimpleHashMapCache<W>>;
+type InnerLazyFst<W, F, B, Cache> = LazyFst2<W, RmEpsilonOp<W, F, B>, Cache>;
 
 /// The result of weight factoring is a transducer equivalent to the
 /// input whose path weights have been factored according to the FactorIterator.
 /// States and transitions will be added as necessary. The algorithm is a
 /// generalization to arbitrary weights of the second step of the input
 /// epsilon-normalization algorithm. This version is a Delayed FST.
-pub struct RmEpsilonFst<W: Semiring, F: MutableFst<W>, B: Borrow<F>>(InnerLazyFst<W, F, B>);
+pub struct RmEpsilonFst<
+    W: Semiring,
+    F: MutableFst<W>,
+    B: Borrow<F>,
+    Cache: FstCache<W> = SimpleHashMapCache<W>,
+>(InnerLazyFst<W, F, B, Cache>);
 
-impl<W, F, B> CoreFst<W> for RmEpsilonFst<W, F, B>
+impl<W, F, B, Cache> CoreFst<W> for RmEpsilonFst<W, F, B, Cache>
 where
     W: Semiring,
     F: MutableFst<W>,
     B: Borrow<F>,
+    Cache: FstCache<W>,
 {
     type TRS = TrsVec<W>;
 
@@ -68,37 +76,40 @@ where
     }
 }
 
-impl<'a, W, F, B> StateIterator<'a> for RmEpsilonFst<W, F, B>
+impl<'a, W, F, B, Cache> StateIterator<'a> for RmEpsilonFst<W, F, B, Cache>
 where
     W: Semiring,
     F: MutableFst<W> + 'a,
     B: Borrow<F> + 'a,
+    Cache: FstCache<W> + 'a,
 {
-    type Iter = <InnerLazyFst<W, F, B> as StateIterator<'a>>::Iter;
+    type Iter = <InnerLazyFst<W, F, B, Cache> as StateIterator<'a>>::Iter;
 
     fn states_iter(&'a self) -> Self::Iter {
         self.0.states_iter()
     }
 }
 
-impl<'a, W, F, B> FstIterator<'a, W> for RmEpsilonFst<W, F, B>
+impl<'a, W, F, B, Cache> FstIterator<'a, W> for RmEpsilonFst<W, F, B, Cache>
 where
     W: Semiring,
     F: MutableFst<W> + 'a,
     B: Borrow<F> + 'a,
+    Cache: FstCache<W> + 'a,
 {
-    type FstIter = <InnerLazyFst<W, F, B> as FstIterator<'a, W>>::FstIter;
+    type FstIter = <InnerLazyFst<W, F, B, Cache> as FstIterator<'a, W>>::FstIter;
 
     fn fst_iter(&'a self) -> Self::FstIter {
         self.0.fst_iter()
     }
 }
 
-impl<W, F, B> Fst<W> for RmEpsilonFst<W, F, B>
+impl<W, F, B, Cache> Fst<W> for RmEpsilonFst<W, F, B, Cache>
 where
     W: Semiring,
     F: MutableFst<W> + 'static,
     B: Borrow<F> + 'static,
+    Cache: FstCache<W> + 'static,
 {
     fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
         self.0.input_symbols()
@@ -125,11 +136,12 @@ where
     }
 }
 
-impl<W, F, B> Debug for RmEpsilonFst<W, F, B>
+impl<W, F, B, Cache> Debug for RmEpsilonFst<W, F, B, Cache>
 where
     W: Semiring,
     F: MutableFst<W>,
     B: Borrow<F>,
+    Cache: FstCache<W>,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.0.fmt(f)
@@ -143,10 +155,22 @@ where
     B: Borrow<F>,
 {
     pub fn new(fst: B) -> Result<Self> {
+        Self::new_with_cache(fst, SimpleHashMapCache::default())
+    }
+}
+
+impl<W, F, B, Cache> RmEpsilonFst<W, F, B, Cache>
+where
+    W: Semiring,
+    F: MutableFst<W>,
+    B: Borrow<F>,
+    Cache: FstCache<W>,
+{
+    /// Same as `new` but the computed states are stored in `fst_cache`.
+    pub fn new_with_cache(fst: B, fst_cache: Cache) -> Result<Self> {
         let isymt = fst.borrow().input_symbols().cloned();
         let osymt = fst.borrow().output_symbols().cloned();
         let fst_op = RmEpsilonOp::new(fst);
-        let fst_cache = SimpleHashMapCache::default();
         let lazy_fst = LazyFst2::from_op_and_cache(fst_op, fst_cache, isymt, osymt);
         Ok(RmEpsilonFst(lazy_fst))
     }
@@ -155,4 +179,76 @@ where
     pub fn compute<F2: MutableFst<W>>(&self) -> Result<F2> {
         self.0.compute()
     }
+
+    /// Same as `compute` but the states are expanded concurrently on the rayon thread pool
+    /// and renumbered in breadth-first order.
+    #[cfg(feature = "rayon")]
+    pub fn compute_parallel<F2: MutableFst<W> + AllocableFst<W>>(&self) -> Result<F2>
+    where
+        W: Send + Sync,
+        RmEpsilonOp<W, F, B>: Sync,
+        Cache: Sync,
+    {
+        self.0.compute_parallel()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fst_impls::VectorFst;
+    use crate::semirings::TropicalWeight;
+
+    #[test]
+    fn test_rmepsilon_fst_sync() {
+        fn is_sync<T: Sync>() {}
+        is_sync::<RmEpsilonFst<TropicalWeight, VectorFst<_>, VectorFst<_>>>();
+    }
+
+    #[cfg(feature = "rayon")]
+    mod parallel {
+        use super::*;
+        use crate::{Tr, Trs};
+        use proptest::prelude::any;
+        use proptest::proptest;
+        use std::collections::{HashMap, VecDeque};
+
+        // The states of `compute` keep the ids of the input FST : renumbers the accessible
+        // ones in the order they are discovered by a breadth-first search.
+        fn bfs_renumber(fst: &VectorFst<TropicalWeight>) -> VectorFst<TropicalWeight> {
+            let mut fst_out = VectorFst::new();
+            let start = match fst.start() {
+                Some(s) => s,
+                None => return fst_out,
+            };
+            let mut new_ids = HashMap::new();
+            new_ids.insert(start, fst_out.add_state());
+            fst_out.set_start(0).unwrap();
+            let mut queue = VecDeque::new();
+            queue.push_back(start);
+            while let Some(s) = queue.pop_front() {
+                for tr in fst.get_trs(s).unwrap().trs() {
+                    let nextstate = *new_ids.entry(tr.nextstate).or_insert_with(|| {
+                        queue.push_back(tr.nextstate);
+                        fst_out.add_state()
+                    });
+                    let tr = Tr::new(tr.ilabel, tr.olabel, tr.weight, nextstate);
+                    fst_out.add_tr(new_ids[&s], tr).unwrap();
+                }
+                if let Some(f_w) = fst.final_weight(s).unwrap() {
+                    fst_out.set_final(new_ids[&s], f_w).unwrap();
+                }
+            }
+            fst_out
+        }
+
+        proptest! {
+            #[test]
+            fn test_proptest_rmepsilon_fst_compute_parallel(fst in any::<VectorFst::<TropicalWeight>>()) {
+                let serial: VectorFst<_> = RmEpsilonFst::new(fst.clone()).unwrap().compute().unwrap();
+                let parallel: VectorFst<_> = RmEpsilonFst::new(fst).unwrap().compute_parallel().unwrap();
+                assert_eq!(parallel, bfs_renumber(&serial));
+            }
+        }
+    }
 }
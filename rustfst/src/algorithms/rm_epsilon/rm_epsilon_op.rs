@@ -11,24 +11,35 @@ use crate::fst_traits::MutableFst;
 use crate::semirings::Semiring;
 use crate::{StateId, TrsVec};
 use itertools::Itertools;
-use std::cell::RefCell;
 use std::marker::PhantomData;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
-#[derive(Clone)]
 pub struct RmEpsilonOp<W: Semiring, F: MutableFst<W>, B: Borrow<F>> {
-    rmeps_state: RefCell<RmEpsilonState<W, FifoQueue>>,
+    // Scratch states used by the expansions. Each expansion takes one out of the pool
+    // so that several states can be expanded concurrently.
+    rmeps_states: Mutex<Vec<RmEpsilonState<W, FifoQueue>>>,
     properties: FstProperties,
     ghost: PhantomData<F>,
     fst: B,
 }
 
+impl<W: Semiring, F: MutableFst<W>, B: Borrow<F> + Clone> Clone for RmEpsilonOp<W, F, B> {
+    fn clone(&self) -> Self {
+        Self {
+            rmeps_states: Mutex::new(self.rmeps_states.lock().unwrap().clone()),
+            properties: self.properties,
+            ghost: PhantomData,
+            fst: self.fst.clone(),
+        }
+    }
+}
+
 impl<W: Semiring, F: MutableFst<W>, B: Borrow<F>> std::fmt::Debug for RmEpsilonOp<W, F, B> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "RmEpsilonOp {{ rmeps_state : {:?}}}",
-            self.rmeps_state.borrow()
+            "RmEpsilonOp {{ rmeps_states : {:?}}}",
+            self.rmeps_states.lock().unwrap()
         )
     }
 }
@@ -42,16 +53,21 @@ impl<W: Semiring, F: MutableFst<W>, B: Borrow<F>> std::fmt::Debug for RmEpsilonO
 impl<W: Semiring, F: MutableFst<W>, B: Borrow<F>> RmEpsilonOp<W, F, B> {
     pub fn new(fst: B) -> Self {
         let properties = rmepsilon_properties(fst.borrow().properties(), true);
+        let rmeps_state = Self::new_rmeps_state(fst.borrow());
         Self {
             properties,
-            rmeps_state: RefCell::new(RmEpsilonState::new(
-                fst.borrow().num_states(),
-                RmEpsilonInternalConfig::new_with_default(FifoQueue::default()),
-            )),
+            rmeps_states: Mutex::new(vec![rmeps_state]),
             fst,
             ghost: PhantomData,
         }
     }
+
+    fn new_rmeps_state(fst: &F) -> RmEpsilonState<W, FifoQueue> {
+        RmEpsilonState::new(
+            fst.num_states(),
+            RmEpsilonInternalConfig::new_with_default(FifoQueue::default()),
+        )
+    }
 }
 
 impl<W: Semiring, F: MutableFst<W>, B: Borrow<F>> FstOp2<W> for RmEpsilonOp<W, F, B> {
@@ -60,10 +76,15 @@ impl<W: Semiring, F: MutableFst<W>, B: Borrow<F>> FstOp2<W> for RmEpsilonOp<W, F
     }
 
     fn compute_trs_and_final_weight(&self, state: StateId) -> Result<(TrsVec<W>, Option<W>)> {
-        let (trs, final_weight) = self
-            .rmeps_state
-            .borrow_mut()
-            .expand::<F, _>(state, self.fst.borrow())?;
+        let mut rmeps_state = self
+            .rmeps_states
+            .lock()
+            .unwrap()
+            .pop()
+            .unwrap_or_else(|| Self::new_rmeps_state(self.fst.borrow()));
+        let expanded = rmeps_state.expand::<F, _>(state, self.fst.borrow());
+        self.rmeps_states.lock().unwrap().push(rmeps_state);
+        let (trs, final_weight) = expanded?;
         let zero = W::zero();
 
         let trs = trs.into_iter().rev().collect_vec();
@@ -1,6 +1,7 @@
 use anyhow::Result;
 
 use crate::algorithms::dfs_visit::dfs_visit;
+use crate::algorithms::prune::prune_with_any_semiring;
 use crate::algorithms::queues::AutoQueue;
 use crate::algorithms::rm_epsilon::{RmEpsilonInternalConfig, RmEpsilonState};
 use crate::algorithms::top_sort::TopOrderVisitor;
@@ -167,7 +168,7 @@ pub(crate) fn rm_epsilon_with_internal_config<W: Semiring, F: MutableFst<W>, Q:
     fst.set_properties(rmepsilon_properties(fst.properties(), false));
 
     if weight_threshold != W::zero() || state_threshold.is_some() {
-        todo!("Implement Prune!")
+        prune_with_any_semiring(fst, weight_threshold.clone(), state_threshold)?;
     }
 
     if connect && weight_threshold == W::zero() && state_threshold.is_none() {
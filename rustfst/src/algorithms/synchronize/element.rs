@@ -0,0 +1,20 @@
+use crate::{Label, StateId};
+
+/// State of the synchronized FST : a state of the input FST (`None` once the input
+/// path is final) along with the input and output labels read but not emitted yet.
+#[derive(PartialOrd, PartialEq, Hash, Clone, Debug, Eq)]
+pub struct Element {
+    pub state: Option<StateId>,
+    pub istring: Vec<Label>,
+    pub ostring: Vec<Label>,
+}
+
+impl Element {
+    pub fn new(state: Option<StateId>, istring: Vec<Label>, ostring: Vec<Label>) -> Self {
+        Self {
+            state,
+            istring,
+            ostring,
+        }
+    }
+}
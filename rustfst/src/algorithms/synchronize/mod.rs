@@ -0,0 +1,8 @@
+mod element;
+mod synchronize_fst;
+mod synchronize_op;
+mod synchronize_static;
+
+use element::Element;
+pub use synchronize_fst::SynchronizeFst;
+pub use synchronize_static::synchronize;
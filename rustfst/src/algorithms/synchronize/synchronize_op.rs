@@ -0,0 +1,198 @@
+use std::borrow::Borrow;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::dfs_visit::dfs_visit;
+use crate::algorithms::lazy::{FstOp, StateTable};
+use crate::algorithms::synchronize::Element;
+use crate::algorithms::tr_filters::AnyTrFilter;
+use crate::algorithms::visitors::SccVisitor;
+use crate::fst_properties::mutable_properties::synchronization_properties;
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::ExpandedFst;
+use crate::semirings::Semiring;
+use crate::{Label, StateId, Tr, Trs, TrsVec, EPS_LABEL};
+
+pub struct SynchronizeOp<W: Semiring, F: ExpandedFst<W>, B: Borrow<F>> {
+    fst: B,
+    state_table: StateTable<Element>,
+    properties: FstProperties,
+    w: PhantomData<(W, F)>,
+}
+
+impl<W: Semiring, F: ExpandedFst<W>, B: Borrow<F>> std::fmt::Debug for SynchronizeOp<W, F, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "SynchronizeOp {{ state_table : {:?}, fst : {:?} }}",
+            self.state_table,
+            self.fst.borrow()
+        )
+    }
+}
+
+impl<W: Semiring, F: ExpandedFst<W>, B: Borrow<F>> SynchronizeOp<W, F, B> {
+    pub fn new(fst: B) -> Result<Self> {
+        check_bounded_delay(fst.borrow())?;
+        let properties = synchronization_properties(fst.borrow().properties());
+        Ok(Self {
+            fst,
+            state_table: StateTable::new(),
+            properties,
+            w: PhantomData,
+        })
+    }
+}
+
+/// Returns an error if the delay between the input and output labels is unbounded, i.e
+/// if a cycle reachable from the initial state doesn't read as many non-epsilon input
+/// labels as non-epsilon output labels.
+fn check_bounded_delay<W: Semiring, F: ExpandedFst<W>>(fst: &F) -> Result<()> {
+    let mut visitor = SccVisitor::new(fst, true, false);
+    dfs_visit(fst, &mut visitor, &AnyTrFilter {}, true);
+    let scc = visitor.scc.unwrap();
+
+    // In a strongly connected component, all the cycles have a null delay iff a delay can be
+    // attributed to each state such that every transition is consistent with it.
+    let mut delay: Vec<Option<i64>> = vec![None; fst.num_states()];
+    let mut stack = vec![];
+    for s in 0..fst.num_states() {
+        if scc[s] == -1 || delay[s].is_some() {
+            continue;
+        }
+        delay[s] = Some(0);
+        stack.push(s as StateId);
+        while let Some(state) = stack.pop() {
+            let d = delay[state as usize].unwrap();
+            for tr in fst.get_trs(state)?.trs() {
+                let next = tr.nextstate as usize;
+                if scc[next] != scc[state as usize] {
+                    continue;
+                }
+                let next_d = d + (tr.ilabel != EPS_LABEL) as i64 - (tr.olabel != EPS_LABEL) as i64;
+                match delay[next] {
+                    None => {
+                        delay[next] = Some(next_d);
+                        stack.push(tr.nextstate);
+                    }
+                    Some(v) if v != next_d => {
+                        bail!("Synchronize: The FST has unbounded delay")
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// First label of the concatenation of `s` and `l`.
+fn car(s: &[Label], l: Label) -> Label {
+    s.first().cloned().unwrap_or(l)
+}
+
+/// Residual string obtained by removing the first label of the concatenation of `s` and `l`.
+fn cdr(s: &[Label], l: Label) -> Vec<Label> {
+    let mut r: Vec<Label> = s.iter().skip(1).cloned().collect();
+    if l != EPS_LABEL && !s.is_empty() {
+        r.push(l);
+    }
+    r
+}
+
+/// Concatenation of `s` and `l`.
+fn concat(s: &[Label], l: Label) -> Vec<Label> {
+    let mut r = s.to_vec();
+    if l != EPS_LABEL {
+        r.push(l);
+    }
+    r
+}
+
+/// Returns true if the concatenation of `s` and `l` is empty.
+fn is_empty(s: &[Label], l: Label) -> bool {
+    s.is_empty() && l == EPS_LABEL
+}
+
+impl<W: Semiring, F: ExpandedFst<W>, B: Borrow<F>> SynchronizeOp<W, F, B> {
+    fn element_final_weight(&self, elt: &Element) -> Result<Option<W>> {
+        match elt.state {
+            None => Ok(Some(W::one())),
+            Some(s) => self.fst.borrow().final_weight(s),
+        }
+    }
+}
+
+impl<W: Semiring, F: ExpandedFst<W>, B: Borrow<F>> FstOp<W> for SynchronizeOp<W, F, B> {
+    fn compute_start(&self) -> Result<Option<StateId>> {
+        Ok(self.fst.borrow().start().map(|s| {
+            self.state_table
+                .find_id(Element::new(Some(s), vec![], vec![]))
+        }))
+    }
+
+    fn compute_trs(&self, state: StateId) -> Result<TrsVec<W>> {
+        let elt = self.state_table.find_tuple(state);
+        let mut trs = vec![];
+        if let Some(s) = elt.state {
+            for tr in self.fst.borrow().get_trs(s)?.trs() {
+                let (ilabel, olabel, next_elt) =
+                    if !is_empty(&elt.istring, tr.ilabel) && !is_empty(&elt.ostring, tr.olabel) {
+                        (
+                            car(&elt.istring, tr.ilabel),
+                            car(&elt.ostring, tr.olabel),
+                            Element::new(
+                                Some(tr.nextstate),
+                                cdr(&elt.istring, tr.ilabel),
+                                cdr(&elt.ostring, tr.olabel),
+                            ),
+                        )
+                    } else {
+                        (
+                            EPS_LABEL,
+                            EPS_LABEL,
+                            Element::new(
+                                Some(tr.nextstate),
+                                concat(&elt.istring, tr.ilabel),
+                                concat(&elt.ostring, tr.olabel),
+                            ),
+                        )
+                    };
+                let nextstate = self.state_table.find_id(next_elt);
+                trs.push(Tr::new(ilabel, olabel, tr.weight.clone(), nextstate));
+            }
+        }
+        // Flush the remaining labels once the input path is final.
+        if let Some(weight) = self.element_final_weight(&elt)? {
+            if !elt.istring.is_empty() || !elt.ostring.is_empty() {
+                let next_elt = Element::new(
+                    None,
+                    cdr(&elt.istring, EPS_LABEL),
+                    cdr(&elt.ostring, EPS_LABEL),
+                );
+                trs.push(Tr::new(
+                    car(&elt.istring, EPS_LABEL),
+                    car(&elt.ostring, EPS_LABEL),
+                    weight,
+                    self.state_table.find_id(next_elt),
+                ));
+            }
+        }
+        Ok(TrsVec(Arc::new(trs)))
+    }
+
+    fn compute_final_weight(&self, state: StateId) -> Result<Option<W>> {
+        let elt = self.state_table.find_tuple(state);
+        if elt.istring.is_empty() && elt.ostring.is_empty() {
+            self.element_final_weight(&elt)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.properties
+    }
+}
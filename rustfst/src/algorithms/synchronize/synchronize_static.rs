@@ -0,0 +1,119 @@
+use anyhow::Result;
+
+use crate::algorithms::synchronize::SynchronizeFst;
+use crate::fst_traits::{AllocableFst, ExpandedFst, MutableFst};
+use crate::semirings::Semiring;
+
+/// Synchronizes an FST. The output FST is equivalent to the input FST but the delay
+/// between the input and output labels of a successful path is minimal : the labels are
+/// emitted by pairs as soon as possible and epsilons only appear at the end of the input
+/// or output side of a path.
+///
+/// The input FST must have a bounded delay, i.e every cycle must read as many
+/// non-epsilon input labels as non-epsilon output labels. An error is returned otherwise.
+///
+/// # Example
+/// ```
+/// # use anyhow::Result;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::{CoreFst, MutableFst};
+/// # use rustfst::algorithms::synchronize::synchronize;
+/// # use rustfst::{Tr, EPS_LABEL};
+/// # fn main() -> Result<()> {
+/// let mut fst = VectorFst::<TropicalWeight>::new();
+/// fst.add_states(3);
+/// fst.set_start(0)?;
+/// fst.add_tr(0, Tr::new(1, EPS_LABEL, 1.0, 1))?;
+/// fst.add_tr(1, Tr::new(EPS_LABEL, 2, 2.0, 2))?;
+/// fst.set_final(2, 0.0)?;
+///
+/// let synchronized_fst: VectorFst<_> = synchronize(&fst)?;
+///
+/// let mut fst_ref = VectorFst::<TropicalWeight>::new();
+/// fst_ref.add_states(3);
+/// fst_ref.set_start(0)?;
+/// fst_ref.add_tr(0, Tr::new(EPS_LABEL, EPS_LABEL, 1.0, 1))?;
+/// fst_ref.add_tr(1, Tr::new(1, 2, 2.0, 2))?;
+/// fst_ref.set_final(2, 0.0)?;
+///
+/// assert_eq!(synchronized_fst, fst_ref);
+/// # Ok(())
+/// # }
+/// ```
+pub fn synchronize<W, F1, F2>(ifst: &F1) -> Result<F2>
+where
+    W: Semiring,
+    F1: ExpandedFst<W>,
+    F2: MutableFst<W> + AllocableFst<W>,
+{
+    let fst: SynchronizeFst<_, F1, _> = SynchronizeFst::new(ifst)?;
+    fst.compute()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fst_impls::VectorFst;
+    use crate::semirings::TropicalWeight;
+    use crate::{Tr, EPS_LABEL};
+
+    #[test]
+    fn test_synchronize_flush_final_labels() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 3, 1.0, 1))?;
+        fst.add_tr(1, Tr::new(2, EPS_LABEL, 2.0, 2))?;
+        fst.set_final(2, 0.5)?;
+
+        let synchronized_fst: VectorFst<_> = synchronize(&fst)?;
+
+        let mut fst_ref = VectorFst::<TropicalWeight>::new();
+        fst_ref.add_states(4);
+        fst_ref.set_start(0)?;
+        fst_ref.add_tr(0, Tr::new(1, 3, 1.0, 1))?;
+        fst_ref.add_tr(1, Tr::new(EPS_LABEL, EPS_LABEL, 2.0, 2))?;
+        fst_ref.add_tr(2, Tr::new(2, EPS_LABEL, 0.5, 3))?;
+        fst_ref.set_final(3, 0.0)?;
+
+        assert_eq!(synchronized_fst, fst_ref);
+        Ok(())
+    }
+
+    #[test]
+    fn test_synchronize_bounded_delay_cycle() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(2);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, EPS_LABEL, 1.0, 1))?;
+        fst.add_tr(1, Tr::new(EPS_LABEL, 2, 1.0, 0))?;
+        fst.set_final(0, 0.0)?;
+
+        let synchronized_fst: VectorFst<_> = synchronize(&fst)?;
+
+        let mut fst_ref = VectorFst::<TropicalWeight>::new();
+        fst_ref.add_states(2);
+        fst_ref.set_start(0)?;
+        fst_ref.add_tr(0, Tr::new(EPS_LABEL, EPS_LABEL, 1.0, 1))?;
+        fst_ref.add_tr(1, Tr::new(1, 2, 1.0, 0))?;
+        fst_ref.set_final(0, 0.0)?;
+
+        assert_eq!(synchronized_fst, fst_ref);
+        Ok(())
+    }
+
+    #[test]
+    fn test_synchronize_unbounded_delay() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(2);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, EPS_LABEL, 1.0, 0))?;
+        fst.add_tr(0, Tr::new(2, 2, 1.0, 1))?;
+        fst.set_final(1, 0.0)?;
+
+        let res: Result<VectorFst<_>> = synchronize(&fst);
+        assert!(res.is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+/// Union-find data structure over a dense set of integers, with path compression
+/// and union by rank.
+#[derive(Debug, Clone)]
+pub(crate) struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+}
+
+impl UnionFind {
+    /// Creates `size` singleton sets.
+    pub(crate) fn new(size: usize) -> Self {
+        Self {
+            parent: (0..size).collect(),
+            rank: vec![0; size],
+        }
+    }
+
+    /// Returns the representative of the set containing `item`.
+    pub(crate) fn find(&mut self, item: usize) -> usize {
+        let mut root = item;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut current = item;
+        while self.parent[current] != root {
+            let next = self.parent[current];
+            self.parent[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    /// Merges the sets containing `item1` and `item2`.
+    pub(crate) fn union(&mut self, item1: usize, item2: usize) {
+        let root1 = self.find(item1);
+        let root2 = self.find(item2);
+        if root1 == root2 {
+            return;
+        }
+        if self.rank[root1] < self.rank[root2] {
+            self.parent[root1] = root2;
+        } else {
+            self.parent[root2] = root1;
+            if self.rank[root1] == self.rank[root2] {
+                self.rank[root1] += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_find() {
+        let mut uf = UnionFind::new(5);
+        uf.union(0, 1);
+        uf.union(3, 4);
+        assert_eq!(uf.find(0), uf.find(1));
+        assert_eq!(uf.find(3), uf.find(4));
+        assert_ne!(uf.find(0), uf.find(3));
+        assert_ne!(uf.find(2), uf.find(0));
+
+        uf.union(1, 4);
+        assert_eq!(uf.find(0), uf.find(3));
+        assert_ne!(uf.find(2), uf.find(4));
+    }
+}
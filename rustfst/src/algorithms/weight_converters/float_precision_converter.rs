@@ -0,0 +1,70 @@
+use anyhow::Result;
+
+use crate::algorithms::{FinalTr, MapFinalAction, WeightConverter};
+use crate::fst_properties::FstProperties;
+use crate::semirings::{LogWeight, LogWeight64, Semiring, TropicalWeight, TropicalWeight64};
+use crate::Tr;
+
+/// Mapper converting the weights between their single and double precision versions
+/// (e.g `TropicalWeight` to `TropicalWeight64` and back).
+pub struct FloatPrecisionConverter {}
+
+macro_rules! impl_float_precision_converter {
+    ($si: ty, $so: ty, $t: ty) => {
+        impl WeightConverter<$si, $so> for FloatPrecisionConverter {
+            fn tr_map(&mut self, tr: &Tr<$si>) -> Result<Tr<$so>> {
+                Ok(Tr::new(
+                    tr.ilabel,
+                    tr.olabel,
+                    <$so>::new(*tr.weight.value() as $t),
+                    tr.nextstate,
+                ))
+            }
+
+            fn final_tr_map(&mut self, final_tr: &FinalTr<$si>) -> Result<FinalTr<$so>> {
+                Ok(FinalTr {
+                    ilabel: final_tr.ilabel,
+                    olabel: final_tr.olabel,
+                    weight: <$so>::new(*final_tr.weight.value() as $t),
+                })
+            }
+
+            fn final_action(&self) -> MapFinalAction {
+                MapFinalAction::MapNoSuperfinal
+            }
+
+            fn properties(&self, inprops: FstProperties) -> FstProperties {
+                inprops
+            }
+        }
+    };
+}
+
+impl_float_precision_converter!(TropicalWeight, TropicalWeight64, f64);
+impl_float_precision_converter!(TropicalWeight64, TropicalWeight, f32);
+impl_float_precision_converter!(LogWeight, LogWeight64, f64);
+impl_float_precision_converter!(LogWeight64, LogWeight, f32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::weight_convert;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::MutableFst;
+
+    #[test]
+    fn test_float_precision_converter() -> Result<()> {
+        let mut fst = VectorFst::<LogWeight>::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.add_tr(s0, Tr::new(1, 2, 0.5, s1))?;
+        fst.set_final(s1, LogWeight::zero())?;
+
+        let mut converter = FloatPrecisionConverter {};
+        let fst64: VectorFst<LogWeight64> = weight_convert(&fst, &mut converter)?;
+        let fst_back: VectorFst<LogWeight> = weight_convert(&fst64, &mut converter)?;
+        assert_eq!(fst_back, fst);
+        Ok(())
+    }
+}
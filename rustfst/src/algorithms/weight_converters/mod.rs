@@ -1,7 +1,9 @@
+mod float_precision_converter;
 mod from_gallic_mapper;
 mod simple_weight_converter;
 mod to_gallic_converter;
 
+pub use self::float_precision_converter::FloatPrecisionConverter;
 pub use self::from_gallic_mapper::FromGallicConverter;
 pub use self::simple_weight_converter::SimpleWeightConverter;
 pub use self::to_gallic_converter::ToGallicConverter;
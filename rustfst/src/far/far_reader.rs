@@ -0,0 +1,204 @@
+use std::marker::PhantomData;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use nom::IResult;
+
+use crate::far::FarType;
+use crate::fst_traits::SerializableFst;
+use crate::parsers::bin_fst::fst_header::{BinaryFstHeader, OpenFstString};
+use crate::parsers::nom_utils::NomCustomError;
+use crate::parsers::{parse_bin_i32, parse_bin_i64, SerializeBinary};
+use crate::semirings::SerializableSemiring;
+
+/// Reads FSTs from an archive in the binary format of OpenFST.
+///
+/// The whole archive is loaded in memory and the FSTs are parsed when accessed. An `stlist`
+/// archive has no index : its FSTs are also parsed once at load time to find where each
+/// entry starts. Entries can be iterated over in the order of their keys or retrieved by key.
+pub struct FarReader<W, F> {
+    data: Vec<u8>,
+    far_type: FarType,
+    // Keys with the position in `data` of the FST they index, sorted by key.
+    entries: Vec<(String, usize)>,
+    w: PhantomData<(W, F)>,
+}
+
+fn map_nom_error<'a, T>(
+    res: IResult<&'a [u8], T, NomCustomError<&'a [u8]>>,
+) -> Result<(&'a [u8], T)> {
+    res.map_err(|e| {
+        e.map(|e_inner| match e_inner {
+            NomCustomError::Nom(_, k) => {
+                format_err!("Error while parsing FAR entry. Error kind {:?}", k)
+            }
+            NomCustomError::SymbolTableError(e) => {
+                format_err!("Error while parsing symbolTable from FAR entry : {}", e)
+            }
+        })
+        .into()
+    })
+}
+
+/// Checks the magic number and the version of an archive and returns its type.
+fn parse_far_type(data: &[u8]) -> Result<FarType> {
+    let far_type = FarType::from_data(data)
+        .ok_or_else(|| format_err!("FarReader : unknown FAR magic number"))?;
+    let (i, _) = map_nom_error(parse_bin_i32(data))?;
+    let (_, version) = map_nom_error(parse_bin_i32(i))?;
+    if version != far_type.file_version() {
+        bail!(
+            "FarReader : unsupported {} version {}",
+            far_type.name(),
+            version
+        );
+    }
+    Ok(far_type)
+}
+
+fn parse_sttable_entries(data: &[u8]) -> Result<Vec<(String, usize)>> {
+    let n = data.len();
+    if n < 24 {
+        bail!("FarReader : truncated sttable");
+    }
+    // The index is the vector of the positions of the entries (its length followed by the
+    // positions) and the number of entries again.
+    let (_, num_entries) = map_nom_error(parse_bin_i64(&data[n - 8..]))?;
+    let index_size = (num_entries as usize)
+        .checked_add(2)
+        .and_then(|v| v.checked_mul(8))
+        .filter(|v| *v + 8 <= n)
+        .ok_or_else(|| format_err!("FarReader : corrupted sttable index"))?;
+    let (mut i, num_positions) = map_nom_error(parse_bin_i64(&data[n - index_size..n - 8]))?;
+    if num_positions != num_entries {
+        bail!("FarReader : corrupted sttable index");
+    }
+    let mut entries = Vec::with_capacity(num_entries as usize);
+    for _ in 0..num_entries {
+        let (rest, position) = map_nom_error(parse_bin_i64(i))?;
+        i = rest;
+        let position = position as usize;
+        if position < 8 || position >= n - index_size {
+            bail!("FarReader : invalid sttable entry position {}", position);
+        }
+        let (rest, key) = map_nom_error(OpenFstString::parse(&data[position..]))?;
+        entries.push((key.into(), n - rest.len()));
+    }
+    Ok(entries)
+}
+
+/// Parses the header of the first FST of an archive or returns `None` if the archive is empty.
+///
+/// All the FSTs of an archive share the same types, this allows to pick the `FarReader`
+/// able to read it.
+pub fn load_fst_header(data: &[u8]) -> Result<Option<BinaryFstHeader>> {
+    let position = match parse_far_type(data)? {
+        FarType::STTable => parse_sttable_entries(data)?.first().map(|(_, p)| *p),
+        FarType::STList if data.len() == 8 => None,
+        FarType::STList => {
+            let (rest, key) = map_nom_error(OpenFstString::parse(&data[8..]))?;
+            let key: String = key.into();
+            // OpenFST terminates the list with an empty key.
+            if key.is_empty() {
+                None
+            } else {
+                Some(data.len() - rest.len())
+            }
+        }
+    };
+    position
+        .map(|position| BinaryFstHeader::load(&data[position..]))
+        .transpose()
+}
+
+impl<W, F> FarReader<W, F>
+where
+    W: SerializableSemiring,
+    F: SerializableFst<W> + SerializeBinary,
+{
+    /// Loads an archive from a file.
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let data = std::fs::read(path.as_ref())
+            .with_context(|| format!("Can't open FAR file : {:?}", path.as_ref()))?;
+        Self::load(data)
+    }
+
+    /// Loads an archive from its binary representation.
+    pub fn load(data: Vec<u8>) -> Result<Self> {
+        let far_type = parse_far_type(&data)?;
+        let entries = match far_type {
+            FarType::STTable => parse_sttable_entries(&data)?,
+            FarType::STList => Self::parse_stlist_entries(&data)?,
+        };
+        if entries.windows(2).any(|e| e[0].0 >= e[1].0) {
+            bail!("FarReader : the keys of the archive are not sorted");
+        }
+
+        Ok(Self {
+            data,
+            far_type,
+            entries,
+            w: PhantomData,
+        })
+    }
+
+    fn parse_stlist_entries(data: &[u8]) -> Result<Vec<(String, usize)>> {
+        let n = data.len();
+        let mut i = &data[8..];
+        let mut entries = vec![];
+        while !i.is_empty() {
+            let (rest, key) = map_nom_error(OpenFstString::parse(i))?;
+            let key: String = key.into();
+            // OpenFST terminates the list with an empty key.
+            if key.is_empty() {
+                break;
+            }
+            let position = n - rest.len();
+            let (rest, _) = map_nom_error(F::parse_binary(rest))?;
+            entries.push((key, position));
+            i = rest;
+        }
+        Ok(entries)
+    }
+
+    fn parse_fst(&self, position: usize) -> Result<F> {
+        let (_, fst) = map_nom_error(F::parse_binary(&self.data[position..]))?;
+        Ok(fst)
+    }
+
+    pub fn far_type(&self) -> FarType {
+        self.far_type
+    }
+
+    /// Number of FSTs in the archive.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over the keys of the archive in sorted order.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(key, _)| key.as_str())
+    }
+
+    /// Returns the FST indexed by `key` if present in the archive.
+    pub fn find(&self, key: &str) -> Result<Option<F>> {
+        match self
+            .entries
+            .binary_search_by(|(entry_key, _)| entry_key.as_str().cmp(key))
+        {
+            Ok(idx) => self.parse_fst(self.entries[idx].1).map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Iterates over the entries of the archive in the order of their keys.
+    pub fn iter(&self) -> impl Iterator<Item = Result<(&str, F)>> {
+        self.entries
+            .iter()
+            .map(move |(key, position)| Ok((key.as_str(), self.parse_fst(*position)?)))
+    }
+}
@@ -0,0 +1,106 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::marker::PhantomData;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::far::FarType;
+use crate::fst_traits::SerializableFst;
+use crate::parsers::bin_fst::fst_header::OpenFstString;
+use crate::parsers::{write_bin_i32, write_bin_i64};
+use crate::semirings::SerializableSemiring;
+
+/// Writes FSTs to an archive, in the binary format of OpenFST.
+///
+/// The entries must be added in strictly increasing order of their keys. The archive is
+/// only complete once `finish` has been called.
+pub struct FarWriter<W, F, O = BufWriter<File>> {
+    output: O,
+    far_type: FarType,
+    position: usize,
+    positions: Vec<usize>,
+    last_key: Option<String>,
+    w: PhantomData<(W, F)>,
+}
+
+impl<W, F> FarWriter<W, F, BufWriter<File>>
+where
+    W: SerializableSemiring,
+    F: SerializableFst<W>,
+{
+    /// Creates the archive file at `path`.
+    pub fn create<P: AsRef<Path>>(path: P, far_type: FarType) -> Result<Self> {
+        let file = File::create(path.as_ref())
+            .with_context(|| format!("Cannot create FAR file : {:?}", path.as_ref()))?;
+        Self::new(BufWriter::new(file), far_type)
+    }
+}
+
+impl<W, F, O> FarWriter<W, F, O>
+where
+    W: SerializableSemiring,
+    F: SerializableFst<W>,
+    O: Write,
+{
+    /// Starts an archive of type `far_type` in `output`.
+    pub fn new(mut output: O, far_type: FarType) -> Result<Self> {
+        write_bin_i32(&mut output, far_type.magic_number())?;
+        write_bin_i32(&mut output, far_type.file_version())?;
+        Ok(Self {
+            output,
+            far_type,
+            position: 8,
+            positions: vec![],
+            last_key: None,
+            w: PhantomData,
+        })
+    }
+
+    pub fn far_type(&self) -> FarType {
+        self.far_type
+    }
+
+    /// Adds `fst` to the archive under the key `key`.
+    pub fn add(&mut self, key: &str, fst: &F) -> Result<()> {
+        if key.is_empty() {
+            bail!("FarWriter::add : key must not be empty");
+        }
+        if let Some(last_key) = &self.last_key {
+            if key <= last_key.as_str() {
+                bail!(
+                    "FarWriter::add : keys must be added in sorted order and be unique. {:?} added after {:?}",
+                    key,
+                    last_key
+                );
+            }
+        }
+
+        let mut entry = vec![];
+        OpenFstString::new(key).write(&mut entry)?;
+        fst.store(&mut entry)?;
+        self.output.write_all(&entry)?;
+
+        self.positions.push(self.position);
+        self.position += entry.len();
+        self.last_key = Some(key.to_string());
+        Ok(())
+    }
+
+    /// Writes the index (`sttable`) or the terminating empty key (`stlist`) of the archive
+    /// and returns the underlying output.
+    pub fn finish(mut self) -> Result<O> {
+        match self.far_type {
+            FarType::STTable => {
+                write_bin_i64(&mut self.output, self.positions.len() as i64)?;
+                for position in &self.positions {
+                    write_bin_i64(&mut self.output, *position as i64)?;
+                }
+                write_bin_i64(&mut self.output, self.positions.len() as i64)?;
+            }
+            FarType::STList => OpenFstString::new("").write(&mut self.output)?,
+        }
+        self.output.flush()?;
+        Ok(self.output)
+    }
+}
@@ -0,0 +1,302 @@
+//! FST archives (FAR) : containers storing a collection of FSTs indexed by a string key.
+//!
+//! Two of the OpenFST formats are supported :
+//! - `sttable` : the keys are followed by an index of the entries positions, which
+//!   allows random access by key.
+//! - `stlist` : the entries are simply stored one after the other, followed by an empty key.
+//!
+//! In both formats, the keys are stored in sorted order and each FST is serialized
+//! with the same binary format as the one used by `SerializableFst::write`.
+//!
+//! # Example
+//!
+//! ```
+//! # use anyhow::Result;
+//! # use rustfst::prelude::*;
+//! # use rustfst::far::{FarReader, FarType, FarWriter};
+//! # fn main() -> Result<()> {
+//! let mut fst = VectorFst::<TropicalWeight>::new();
+//! fst.add_states(2);
+//! fst.set_start(0)?;
+//! fst.add_tr(0, Tr::new(1, 2, 0.5, 1))?;
+//! fst.set_final(1, 0.0)?;
+//!
+//! let mut writer = FarWriter::new(vec![], FarType::STTable)?;
+//! writer.add("a", &fst)?;
+//! writer.add("b", &fst)?;
+//! let data = writer.finish()?;
+//!
+//! let reader = FarReader::<TropicalWeight, VectorFst<_>>::load(data)?;
+//! assert_eq!(reader.keys().collect::<Vec<_>>(), vec!["a", "b"]);
+//! assert_eq!(reader.find("b")?, Some(fst));
+//! assert_eq!(reader.find("c")?, None);
+//! # Ok(())
+//! # }
+//! ```
+
+mod far_reader;
+mod far_writer;
+
+pub use self::far_reader::{load_fst_header, FarReader};
+pub use self::far_writer::FarWriter;
+
+use crate::parsers::parse_bin_i32;
+
+/// Identifies stream data as a `sttable` FAR.
+pub(crate) static STTABLE_MAGIC_NUMBER: i32 = 2_125_656_924;
+pub(crate) static STTABLE_FILE_VERSION: i32 = 1;
+/// Identifies stream data as a `stlist` FAR.
+pub(crate) static STLIST_MAGIC_NUMBER: i32 = 5_656_924;
+pub(crate) static STLIST_FILE_VERSION: i32 = 1;
+
+/// Binary formats of FST archives.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FarType {
+    /// Sorted table of entries followed by an index of their positions.
+    STTable,
+    /// Sorted list of entries.
+    STList,
+}
+
+impl FarType {
+    /// Detects the type of the archive stored in `data` from its magic number.
+    pub fn from_data(data: &[u8]) -> Option<FarType> {
+        let (_, magic_number) = parse_bin_i32(data).ok()?;
+        if magic_number == STTABLE_MAGIC_NUMBER {
+            Some(FarType::STTable)
+        } else if magic_number == STLIST_MAGIC_NUMBER {
+            Some(FarType::STList)
+        } else {
+            None
+        }
+    }
+
+    /// Name of the format as used by OpenFST.
+    pub fn name(&self) -> &'static str {
+        match self {
+            FarType::STTable => "sttable",
+            FarType::STList => "stlist",
+        }
+    }
+
+    pub(crate) fn magic_number(&self) -> i32 {
+        match self {
+            FarType::STTable => STTABLE_MAGIC_NUMBER,
+            FarType::STList => STLIST_MAGIC_NUMBER,
+        }
+    }
+
+    pub(crate) fn file_version(&self) -> i32 {
+        match self {
+            FarType::STTable => STTABLE_FILE_VERSION,
+            FarType::STList => STLIST_FILE_VERSION,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+    use crate::fst_impls::{ConstFst, VectorFst};
+    use crate::fst_traits::{Fst, MutableFst, SerializableFst};
+    use crate::semirings::{LogWeight, TropicalWeight};
+    use crate::{Label, SymbolTable, Tr};
+    use std::sync::Arc;
+
+    fn build_fst(label: Label, weight: f32) -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(label, label + 1, weight, 1))?;
+        fst.add_tr(1, Tr::new(label + 2, 0, 1.5, 2))?;
+        fst.add_tr(1, Tr::new(0, label, 0.5, 0))?;
+        fst.set_final(2, weight)?;
+        Ok(fst)
+    }
+
+    fn build_archive(far_type: FarType) -> Result<(Vec<u8>, Vec<VectorFst<TropicalWeight>>)> {
+        let fsts = vec![build_fst(1, 0.5)?, build_fst(4, 2.0)?, build_fst(2, 1.0)?];
+        let mut writer = FarWriter::new(vec![], far_type)?;
+        for (key, fst) in ["fst_a", "fst_b", "fst_c"].iter().zip(fsts.iter()) {
+            writer.add(key, fst)?;
+        }
+        Ok((writer.finish()?, fsts))
+    }
+
+    #[test]
+    fn test_far_roundtrip() -> Result<()> {
+        for far_type in &[FarType::STTable, FarType::STList] {
+            let (data, fsts) = build_archive(*far_type)?;
+            assert_eq!(FarType::from_data(&data), Some(*far_type));
+
+            let reader = FarReader::<TropicalWeight, VectorFst<_>>::load(data)?;
+            assert_eq!(reader.far_type(), *far_type);
+            assert_eq!(reader.len(), 3);
+            assert_eq!(
+                reader.keys().collect::<Vec<_>>(),
+                vec!["fst_a", "fst_b", "fst_c"]
+            );
+            let entries = reader.iter().collect::<Result<Vec<_>>>()?;
+            assert_eq!(
+                entries.into_iter().map(|(_, fst)| fst).collect::<Vec<_>>(),
+                fsts
+            );
+            assert_eq!(reader.find("fst_c")?, Some(fsts[2].clone()));
+            assert_eq!(reader.find("fst_a")?, Some(fsts[0].clone()));
+            assert_eq!(reader.find("fst_0")?, None);
+            assert_eq!(reader.find("fst_d")?, None);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_far_sttable_index() -> Result<()> {
+        let (data, _) = build_archive(FarType::STTable)?;
+        let n = data.len();
+        let mut num_entries = [0u8; 8];
+        num_entries.copy_from_slice(&data[n - 8..]);
+        assert_eq!(i64::from_le_bytes(num_entries), 3);
+        // The first entry starts right after the magic number and the file version.
+        let mut first_position = [0u8; 8];
+        first_position.copy_from_slice(&data[n - 32..n - 24]);
+        assert_eq!(i64::from_le_bytes(first_position), 8);
+
+        // Positions pointing in the index are rejected.
+        let mut corrupted = data.clone();
+        let index_position = (n - 40) as i64;
+        corrupted[n - 32..n - 24].copy_from_slice(&index_position.to_le_bytes());
+        assert!(FarReader::<TropicalWeight, VectorFst<_>>::load(corrupted).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_far_keys_must_be_sorted() -> Result<()> {
+        let fst = build_fst(1, 0.5)?;
+        for far_type in &[FarType::STTable, FarType::STList] {
+            let mut writer = FarWriter::new(vec![], *far_type)?;
+            writer.add("b", &fst)?;
+            assert!(writer.add("a", &fst).is_err());
+            assert!(writer.add("b", &fst).is_err());
+            assert!(writer.add("", &fst).is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_far_empty() -> Result<()> {
+        for far_type in &[FarType::STTable, FarType::STList] {
+            let writer = FarWriter::<TropicalWeight, VectorFst<_>, _>::new(vec![], *far_type)?;
+            let reader = FarReader::<TropicalWeight, VectorFst<_>>::load(writer.finish()?)?;
+            assert!(reader.is_empty());
+            assert_eq!(reader.find("a")?, None);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_far_const_fst_with_symbols() -> Result<()> {
+        let mut fst: VectorFst<LogWeight> = VectorFst::new();
+        fst.add_states(2);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 0.3, 1))?;
+        fst.set_final(1, 0.7)?;
+        let mut symt = SymbolTable::new();
+        symt.add_symbol("a");
+        fst.set_input_symbols(Arc::new(symt));
+        let fst: ConstFst<_> = fst.into();
+
+        for far_type in &[FarType::STTable, FarType::STList] {
+            let mut writer = FarWriter::new(vec![], *far_type)?;
+            writer.add("x", &fst)?;
+            writer.add("y", &fst)?;
+            let reader = FarReader::<LogWeight, ConstFst<_>>::load(writer.finish()?)?;
+            assert_eq!(reader.find("y")?, Some(fst.clone()));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_far_load_fst_header() -> Result<()> {
+        let fst: ConstFst<LogWeight> = VectorFst::new().into();
+        for far_type in &[FarType::STTable, FarType::STList] {
+            let writer = FarWriter::<LogWeight, ConstFst<_>, _>::new(vec![], *far_type)?;
+            assert!(load_fst_header(&writer.finish()?)?.is_none());
+
+            let mut writer = FarWriter::new(vec![], *far_type)?;
+            writer.add("a", &fst)?;
+            let hdr = load_fst_header(&writer.finish()?)?.unwrap();
+            assert_eq!(hdr.fst_type, "const");
+            assert_eq!(hdr.tr_type, "log");
+        }
+        Ok(())
+    }
+
+    // Acceptor with a single tr `0 -1:1/0.5-> 1` and `1` final, in the binary format of OpenFST.
+    #[rustfmt::skip]
+    const OPENFST_FST: &[u8] = &[
+        // Magic number.
+        0xd6, 0xfd, 0xb2, 0x7e,
+        // Fst type "vector" and tr type "standard".
+        0x06, 0x00, 0x00, 0x00, 0x76, 0x65, 0x63, 0x74, 0x6f, 0x72,
+        0x08, 0x00, 0x00, 0x00, 0x73, 0x74, 0x61, 0x6e, 0x64, 0x61, 0x72, 0x64,
+        // Version, flags and properties.
+        0x02, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x03, 0x00, 0x81, 0x5a, 0x69, 0x00, 0x00, 0x00,
+        // Start state, number of states and number of trs.
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        // State 0 : final weight, number of trs and its tr.
+        0x00, 0x00, 0x80, 0x7f,
+        0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x3f, 0x01, 0x00, 0x00, 0x00,
+        // State 1 : final weight and number of trs.
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    // `stlist` archive as written by OpenFST's `farcreate` with `OPENFST_FST` under the keys
+    // "a" and "b".
+    fn openfst_stlist() -> Vec<u8> {
+        // Magic number and file version.
+        let mut data = vec![0x5c, 0x51, 0x56, 0x00, 0x01, 0x00, 0x00, 0x00];
+        for key in b"ab" {
+            data.extend_from_slice(&[0x01, 0x00, 0x00, 0x00, *key]);
+            data.extend_from_slice(OPENFST_FST);
+        }
+        // Empty key terminating the list.
+        data.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]);
+        data
+    }
+
+    #[test]
+    fn test_far_stlist_openfst_compatibility() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(2);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 0.5, 1))?;
+        fst.set_final(1, 0.0)?;
+
+        let reader = FarReader::<TropicalWeight, VectorFst<_>>::load(openfst_stlist())?;
+        assert_eq!(reader.keys().collect::<Vec<_>>(), vec!["a", "b"]);
+        assert_eq!(reader.find("b")?, Some(fst.clone()));
+
+        let mut writer = FarWriter::new(vec![], FarType::STList)?;
+        writer.add("a", &fst)?;
+        writer.add("b", &fst)?;
+        assert_eq!(writer.finish()?, openfst_stlist());
+        Ok(())
+    }
+
+    #[test]
+    fn test_far_rejects_non_archive() -> Result<()> {
+        let mut data = vec![];
+        build_fst(1, 0.5)?.store(&mut data)?;
+        assert_eq!(FarType::from_data(&data), None);
+        assert!(FarReader::<TropicalWeight, VectorFst<_>>::load(data).is_err());
+        Ok(())
+    }
+}
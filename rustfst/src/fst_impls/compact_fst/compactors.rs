@@ -0,0 +1,361 @@
+use std::fmt::Debug;
+use std::io::Write;
+use std::mem::align_of;
+
+use anyhow::Result;
+use nom::bytes::complete::take;
+use nom::IResult;
+
+use crate::fst_properties::FstProperties;
+use crate::parsers::nom_utils::NomCustomError;
+use crate::parsers::{parse_bin_i32, write_bin_i32};
+use crate::semirings::SerializableSemiring;
+use crate::{Label, StateId, Tr, NO_LABEL, NO_STATE_ID};
+
+/// Turns the trs of a `CompactFst` into compact elements and back.
+///
+/// The final weight of a state is compacted as a tr with `NO_LABEL` labels and
+/// `NO_STATE_ID` as next state, stored before the trs of the state.
+pub trait Compactor<W: SerializableSemiring>: Debug + Clone + PartialEq + 'static {
+    /// Compact representation of a tr.
+    type Element: Debug + Clone + PartialEq;
+
+    /// Name of the compactor, used in the OpenFST type of the FST (eg. `compact_acceptor`).
+    fn compactor_type() -> String;
+
+    /// Number of elements of each state if it is fixed.
+    fn size() -> Option<usize>;
+
+    /// Properties shared by all the FSTs that can be compacted.
+    fn properties() -> FstProperties;
+
+    /// Compacts a tr leaving `state`. Fails if the tr can't be represented.
+    fn compact(state: StateId, tr: &Tr<W>) -> Result<Self::Element>;
+
+    /// Expands an element of `state` back into a tr.
+    fn expand(state: StateId, element: &Self::Element) -> Tr<W>;
+
+    /// Parses an element in the binary format of OpenFST.
+    fn parse_element(i: &[u8]) -> IResult<&[u8], Self::Element, NomCustomError<&[u8]>>;
+
+    /// Writes an element in the binary format of OpenFST, including the padding of its
+    /// C struct.
+    fn write_element<O: Write>(element: &Self::Element, output: &mut O) -> Result<()>;
+}
+
+fn is_final_tr<W>(tr: &Tr<W>) -> bool {
+    tr.ilabel == NO_LABEL
+}
+
+fn final_nextstate(label: Label, nextstate: StateId) -> StateId {
+    if label == NO_LABEL {
+        NO_STATE_ID
+    } else {
+        nextstate
+    }
+}
+
+fn check_acceptor<W>(tr: &Tr<W>) -> Result<()> {
+    if tr.ilabel != tr.olabel {
+        bail!(
+            "Compactor : tr {}:{} is not an acceptor tr",
+            tr.ilabel,
+            tr.olabel
+        );
+    }
+    Ok(())
+}
+
+fn check_unweighted<W: SerializableSemiring>(tr: &Tr<W>) -> Result<()> {
+    if !tr.weight.is_one() {
+        bail!("Compactor : weight {:?} is not One", tr.weight);
+    }
+    Ok(())
+}
+
+fn check_string<W>(state: StateId, tr: &Tr<W>) -> Result<()> {
+    if !is_final_tr(tr) && tr.nextstate != state + 1 {
+        bail!(
+            "Compactor : tr from state {} to {} is not a string tr",
+            state,
+            tr.nextstate
+        );
+    }
+    Ok(())
+}
+
+// OpenFST writes the elements as they are in memory : the fields of the `std::pair`
+// holding the weight are aligned as in C and the padding bytes are written as well.
+
+/// Alignment of a weight in an element, the same as in memory.
+fn weight_align<W>() -> usize {
+    align_of::<W>()
+}
+
+/// Alignment of an element holding a label and a weight.
+fn weighted_element_align<W>() -> usize {
+    weight_align::<W>().max(align_of::<i32>())
+}
+
+/// Pads an element being written with zeros until its size is a multiple of `align`.
+fn write_padding(element: &mut Vec<u8>, align: usize) {
+    let offset = element.len() % align;
+    if offset > 0 {
+        element.resize(element.len() + align - offset, 0);
+    }
+}
+
+/// Skips the padding after the `size` bytes of an element already parsed.
+fn parse_padding(i: &[u8], size: usize, align: usize) -> IResult<&[u8], (), NomCustomError<&[u8]>> {
+    let offset = size % align;
+    if offset > 0 {
+        let (i, _) = take(align - offset)(i)?;
+        return Ok((i, ()));
+    }
+    Ok((i, ()))
+}
+
+fn parse_label(i: &[u8]) -> IResult<&[u8], Label, NomCustomError<&[u8]>> {
+    let (i, label) = parse_bin_i32(i)?;
+    Ok((i, label as Label))
+}
+
+fn parse_state(i: &[u8]) -> IResult<&[u8], StateId, NomCustomError<&[u8]>> {
+    let (i, state) = parse_bin_i32(i)?;
+    Ok((i, state as StateId))
+}
+
+/// Compacts acceptors as `(label, weight, nextstate)`. OpenFST type `acceptor`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AcceptorCompactor;
+
+impl<W: SerializableSemiring> Compactor<W> for AcceptorCompactor {
+    type Element = (Label, W, StateId);
+
+    fn compactor_type() -> String {
+        "acceptor".to_string()
+    }
+
+    fn size() -> Option<usize> {
+        None
+    }
+
+    fn properties() -> FstProperties {
+        FstProperties::ACCEPTOR
+    }
+
+    fn compact(_state: StateId, tr: &Tr<W>) -> Result<Self::Element> {
+        check_acceptor(tr)?;
+        Ok((tr.ilabel, tr.weight.clone(), tr.nextstate))
+    }
+
+    fn expand(_state: StateId, element: &Self::Element) -> Tr<W> {
+        Tr::new(element.0, element.0, element.1.clone(), element.2)
+    }
+
+    // Layout of `std::pair<std::pair<Label, Weight>, StateId>`.
+    fn parse_element(input: &[u8]) -> IResult<&[u8], Self::Element, NomCustomError<&[u8]>> {
+        let (i, label) = parse_label(input)?;
+        let (i, _) = parse_padding(i, input.len() - i.len(), weight_align::<W>())?;
+        let (i, weight) = W::parse_binary(i)?;
+        let (i, _) = parse_padding(i, input.len() - i.len(), weighted_element_align::<W>())?;
+        let (i, nextstate) = parse_state(i)?;
+        let (i, _) = parse_padding(i, input.len() - i.len(), weighted_element_align::<W>())?;
+        Ok((i, (label, weight, nextstate)))
+    }
+
+    fn write_element<O: Write>(element: &Self::Element, output: &mut O) -> Result<()> {
+        let mut data = vec![];
+        write_bin_i32(&mut data, element.0 as i32)?;
+        write_padding(&mut data, weight_align::<W>());
+        element.1.write_binary(&mut data)?;
+        write_padding(&mut data, weighted_element_align::<W>());
+        write_bin_i32(&mut data, element.2 as i32)?;
+        write_padding(&mut data, weighted_element_align::<W>());
+        output.write_all(&data)?;
+        Ok(())
+    }
+}
+
+/// Compacts unweighted acceptors as `(label, nextstate)`. OpenFST type `unweighted_acceptor`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnweightedAcceptorCompactor;
+
+impl<W: SerializableSemiring> Compactor<W> for UnweightedAcceptorCompactor {
+    type Element = (Label, StateId);
+
+    fn compactor_type() -> String {
+        "unweighted_acceptor".to_string()
+    }
+
+    fn size() -> Option<usize> {
+        None
+    }
+
+    fn properties() -> FstProperties {
+        FstProperties::ACCEPTOR | FstProperties::UNWEIGHTED
+    }
+
+    fn compact(_state: StateId, tr: &Tr<W>) -> Result<Self::Element> {
+        check_acceptor(tr)?;
+        check_unweighted(tr)?;
+        Ok((tr.ilabel, tr.nextstate))
+    }
+
+    fn expand(_state: StateId, element: &Self::Element) -> Tr<W> {
+        Tr::new(element.0, element.0, W::one(), element.1)
+    }
+
+    fn parse_element(i: &[u8]) -> IResult<&[u8], Self::Element, NomCustomError<&[u8]>> {
+        let (i, label) = parse_label(i)?;
+        let (i, nextstate) = parse_state(i)?;
+        Ok((i, (label, nextstate)))
+    }
+
+    fn write_element<O: Write>(element: &Self::Element, output: &mut O) -> Result<()> {
+        write_bin_i32(output, element.0 as i32)?;
+        write_bin_i32(output, element.1 as i32)
+    }
+}
+
+/// Compacts unweighted transducers as `(ilabel, olabel, nextstate)`. OpenFST type `unweighted`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnweightedCompactor;
+
+impl<W: SerializableSemiring> Compactor<W> for UnweightedCompactor {
+    type Element = (Label, Label, StateId);
+
+    fn compactor_type() -> String {
+        "unweighted".to_string()
+    }
+
+    fn size() -> Option<usize> {
+        None
+    }
+
+    fn properties() -> FstProperties {
+        FstProperties::UNWEIGHTED
+    }
+
+    fn compact(_state: StateId, tr: &Tr<W>) -> Result<Self::Element> {
+        check_unweighted(tr)?;
+        Ok((tr.ilabel, tr.olabel, tr.nextstate))
+    }
+
+    fn expand(_state: StateId, element: &Self::Element) -> Tr<W> {
+        Tr::new(element.0, element.1, W::one(), element.2)
+    }
+
+    fn parse_element(i: &[u8]) -> IResult<&[u8], Self::Element, NomCustomError<&[u8]>> {
+        let (i, ilabel) = parse_label(i)?;
+        let (i, olabel) = parse_label(i)?;
+        let (i, nextstate) = parse_state(i)?;
+        Ok((i, (ilabel, olabel, nextstate)))
+    }
+
+    fn write_element<O: Write>(element: &Self::Element, output: &mut O) -> Result<()> {
+        write_bin_i32(output, element.0 as i32)?;
+        write_bin_i32(output, element.1 as i32)?;
+        write_bin_i32(output, element.2 as i32)
+    }
+}
+
+/// Compacts unweighted strings, whose state `s` has either a single tr to `s + 1` or is
+/// final, as their label. OpenFST type `string`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StringCompactor;
+
+impl<W: SerializableSemiring> Compactor<W> for StringCompactor {
+    type Element = Label;
+
+    fn compactor_type() -> String {
+        "string".to_string()
+    }
+
+    fn size() -> Option<usize> {
+        Some(1)
+    }
+
+    fn properties() -> FstProperties {
+        FstProperties::ACCEPTOR | FstProperties::STRING | FstProperties::UNWEIGHTED
+    }
+
+    fn compact(state: StateId, tr: &Tr<W>) -> Result<Self::Element> {
+        check_acceptor(tr)?;
+        check_unweighted(tr)?;
+        check_string(state, tr)?;
+        Ok(tr.ilabel)
+    }
+
+    fn expand(state: StateId, element: &Self::Element) -> Tr<W> {
+        Tr::new(
+            *element,
+            *element,
+            W::one(),
+            final_nextstate(*element, state + 1),
+        )
+    }
+
+    fn parse_element(i: &[u8]) -> IResult<&[u8], Self::Element, NomCustomError<&[u8]>> {
+        parse_label(i)
+    }
+
+    fn write_element<O: Write>(element: &Self::Element, output: &mut O) -> Result<()> {
+        write_bin_i32(output, *element as i32)
+    }
+}
+
+/// Compacts weighted strings, whose state `s` has either a single tr to `s + 1` or is
+/// final, as `(label, weight)`. OpenFST type `weighted_string`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeightedStringCompactor;
+
+impl<W: SerializableSemiring> Compactor<W> for WeightedStringCompactor {
+    type Element = (Label, W);
+
+    fn compactor_type() -> String {
+        "weighted_string".to_string()
+    }
+
+    fn size() -> Option<usize> {
+        Some(1)
+    }
+
+    fn properties() -> FstProperties {
+        FstProperties::ACCEPTOR | FstProperties::STRING
+    }
+
+    fn compact(state: StateId, tr: &Tr<W>) -> Result<Self::Element> {
+        check_acceptor(tr)?;
+        check_string(state, tr)?;
+        Ok((tr.ilabel, tr.weight.clone()))
+    }
+
+    fn expand(state: StateId, element: &Self::Element) -> Tr<W> {
+        Tr::new(
+            element.0,
+            element.0,
+            element.1.clone(),
+            final_nextstate(element.0, state + 1),
+        )
+    }
+
+    // Layout of `std::pair<Label, Weight>`.
+    fn parse_element(input: &[u8]) -> IResult<&[u8], Self::Element, NomCustomError<&[u8]>> {
+        let (i, label) = parse_label(input)?;
+        let (i, _) = parse_padding(i, input.len() - i.len(), weight_align::<W>())?;
+        let (i, weight) = W::parse_binary(i)?;
+        let (i, _) = parse_padding(i, input.len() - i.len(), weighted_element_align::<W>())?;
+        Ok((i, (label, weight)))
+    }
+
+    fn write_element<O: Write>(element: &Self::Element, output: &mut O) -> Result<()> {
+        let mut data = vec![];
+        write_bin_i32(&mut data, element.0 as i32)?;
+        write_padding(&mut data, weight_align::<W>());
+        element.1.write_binary(&mut data)?;
+        write_padding(&mut data, weighted_element_align::<W>());
+        output.write_all(&data)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::lazy::GcCache;
+use crate::fst_impls::compact_fst::{CompactFst, CompactIndex, Compactor};
+use crate::fst_properties::{compute_fst_properties, FstProperties};
+use crate::fst_traits::ExpandedFst;
+use crate::semirings::SerializableSemiring;
+use crate::{Tr, Trs, NO_LABEL, NO_STATE_ID};
+
+impl<W: SerializableSemiring, C: Compactor<W>, U: CompactIndex> CompactFst<W, C, U> {
+    /// Compacts an FST. Fails if one of its trs or final weights can't be represented by
+    /// the compactor, or if there are too many trs to be indexed by `U`.
+    pub fn from_fst<F: ExpandedFst<W>>(fst: &F) -> Result<Self> {
+        let num_states = fst.num_states();
+        let fixed_size = C::size();
+        let index = |pos: usize| {
+            U::from_usize(pos).ok_or_else(|| {
+                format_err!(
+                    "CompactFst : {} elements can't be indexed on {} bits",
+                    pos,
+                    U::bits()
+                )
+            })
+        };
+
+        let mut states = Vec::with_capacity(if fixed_size.is_some() {
+            0
+        } else {
+            num_states + 1
+        });
+        let mut compacts = Vec::new();
+        let mut num_trs = 0;
+        for s in fst.states_iter() {
+            let begin = compacts.len();
+            if fixed_size.is_none() {
+                states.push(index(begin)?);
+            }
+            if let Some(final_weight) = fst.final_weight(s)? {
+                let final_tr = Tr::new(NO_LABEL, NO_LABEL, final_weight, NO_STATE_ID);
+                compacts.push(C::compact(s, &final_tr)?);
+            }
+            for tr in fst.get_trs(s)?.trs() {
+                compacts.push(C::compact(s, tr)?);
+            }
+            num_trs += fst.num_trs(s)?;
+            if let Some(size) = fixed_size {
+                if compacts.len() - begin != size {
+                    bail!(
+                        "CompactFst : state {} has {} elements, the {} compactor expects {}",
+                        s,
+                        compacts.len() - begin,
+                        C::compactor_type(),
+                        size
+                    );
+                }
+            }
+        }
+        if fixed_size.is_none() {
+            states.push(index(compacts.len())?);
+        }
+
+        let mut compact_fst = CompactFst {
+            states,
+            compacts: Arc::new(compacts),
+            num_states,
+            num_trs,
+            start: fst.start(),
+            isymt: fst.input_symbols().cloned(),
+            osymt: fst.output_symbols().cloned(),
+            properties: FstProperties::empty(),
+            cache: GcCache::default(),
+            compactor: PhantomData,
+        };
+        // The properties are computed once and for all as the FST is immutable.
+        let mut known = FstProperties::empty();
+        compact_fst.properties = compute_fst_properties(
+            &compact_fst,
+            FstProperties::all_properties(),
+            &mut known,
+            false,
+        )?;
+        Ok(compact_fst)
+    }
+}
@@ -0,0 +1,125 @@
+use std::fmt::Debug;
+use std::io::Write;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use anyhow::Result;
+use nom::number::complete::{le_u16, le_u32, le_u64, le_u8};
+use nom::IResult;
+
+use crate::algorithms::lazy::GcCache;
+use crate::fst_impls::compact_fst::Compactor;
+use crate::fst_properties::properties::EXPANDED;
+use crate::fst_properties::FstProperties;
+use crate::parsers::nom_utils::NomCustomError;
+use crate::semirings::SerializableSemiring;
+use crate::{StateId, SymbolTable};
+
+/// Immutable FST storing its trs as compact elements produced by a `Compactor`.
+///
+/// All the elements are stored in a single array. When the compactor has no fixed size,
+/// the position of the first element of each state is stored as an unsigned integer of
+/// type `U`, which allows to shrink the FST further for small graphs (`compact8_*`).
+///
+/// The binary format is the same as the one of the OpenFST `CompactFst`.
+#[derive(Debug, Clone)]
+pub struct CompactFst<W: SerializableSemiring, C: Compactor<W>, U: CompactIndex = u32> {
+    /// Position of the elements of each state, followed by the total number of elements.
+    /// Empty if the compactor has a fixed size.
+    pub(crate) states: Vec<U>,
+    pub(crate) compacts: Arc<Vec<C::Element>>,
+    pub(crate) num_states: usize,
+    pub(crate) num_trs: usize,
+    pub(crate) start: Option<StateId>,
+    pub(crate) isymt: Option<Arc<SymbolTable>>,
+    pub(crate) osymt: Option<Arc<SymbolTable>>,
+    pub(crate) properties: FstProperties,
+    /// Trs expanded by `get_trs`, the least recently used ones are evicted as in OpenFST.
+    pub(crate) cache: GcCache<W>,
+    pub(crate) compactor: PhantomData<C>,
+}
+
+/// Compacts acceptors : each tr is stored as `(label, weight, nextstate)`.
+pub type CompactAcceptorFst<W, U = u32> = CompactFst<W, super::AcceptorCompactor, U>;
+/// Compacts unweighted strings : each tr is stored as its label.
+pub type CompactStringFst<W, U = u32> = CompactFst<W, super::StringCompactor, U>;
+/// Compacts weighted strings : each tr is stored as `(label, weight)`.
+pub type CompactWeightedStringFst<W, U = u32> = CompactFst<W, super::WeightedStringCompactor, U>;
+/// Compacts unweighted acceptors : each tr is stored as `(label, nextstate)`.
+pub type CompactUnweightedAcceptorFst<W, U = u32> =
+    CompactFst<W, super::UnweightedAcceptorCompactor, U>;
+/// Compacts unweighted transducers : each tr is stored as `(ilabel, olabel, nextstate)`.
+pub type CompactUnweightedFst<W, U = u32> = CompactFst<W, super::UnweightedCompactor, U>;
+
+impl<W: SerializableSemiring, C: Compactor<W>, U: CompactIndex> CompactFst<W, C, U> {
+    pub(crate) fn static_properties() -> u64 {
+        EXPANDED
+    }
+
+    /// Range of the elements of a state in `compacts`. The state must exist.
+    pub(crate) fn elements_range(&self, state: usize) -> (usize, usize) {
+        match C::size() {
+            Some(size) => (state * size, (state + 1) * size),
+            None => (
+                self.states[state].to_usize(),
+                self.states[state + 1].to_usize(),
+            ),
+        }
+    }
+}
+
+impl<W: SerializableSemiring, C: Compactor<W>, U: CompactIndex> PartialEq for CompactFst<W, C, U> {
+    fn eq(&self, other: &Self) -> bool {
+        // Intended: Doesn't check symt and properties
+        self.num_states == other.num_states
+            && self.states == other.states
+            && self.compacts == other.compacts
+            && self.start == other.start
+    }
+}
+
+/// Unsigned integer used to index the elements of a `CompactFst`.
+pub trait CompactIndex: Copy + Debug + PartialEq + Send + Sync + 'static {
+    /// Number of bits of the integer. Part of the type of the FST if different from 32.
+    fn bits() -> usize;
+
+    fn to_usize(self) -> usize;
+
+    /// Returns `None` if the value doesn't fit in the integer.
+    fn from_usize(v: usize) -> Option<Self>;
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>>;
+
+    fn write_binary<O: Write>(self, output: &mut O) -> Result<()>;
+}
+
+macro_rules! impl_compact_index {
+    ($t: ty, $parser: ident) => {
+        impl CompactIndex for $t {
+            fn bits() -> usize {
+                std::mem::size_of::<$t>() * 8
+            }
+
+            fn to_usize(self) -> usize {
+                self as usize
+            }
+
+            fn from_usize(v: usize) -> Option<Self> {
+                std::convert::TryFrom::try_from(v).ok()
+            }
+
+            fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+                $parser(i)
+            }
+
+            fn write_binary<O: Write>(self, output: &mut O) -> Result<()> {
+                output.write_all(&self.to_le_bytes()).map_err(|e| e.into())
+            }
+        }
+    };
+}
+
+impl_compact_index!(u8, le_u8);
+impl_compact_index!(u16, le_u16);
+impl_compact_index!(u32, le_u32);
+impl_compact_index!(u64, le_u64);
@@ -0,0 +1,14 @@
+use crate::fst_impls::compact_fst::{CompactFst, CompactIndex, Compactor};
+use crate::fst_traits::ExpandedFst;
+use crate::semirings::SerializableSemiring;
+
+impl<W, C, U> ExpandedFst<W> for CompactFst<W, C, U>
+where
+    W: SerializableSemiring,
+    C: Compactor<W>,
+    U: CompactIndex,
+{
+    fn num_states(&self) -> usize {
+        self.num_states
+    }
+}
@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use anyhow::{format_err, Result};
+
+use crate::algorithms::lazy::{CacheStatus, FstCache};
+use crate::fst_impls::compact_fst::{CompactFst, CompactIndex, Compactor};
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{CoreFst, Fst};
+use crate::semirings::SerializableSemiring;
+use crate::{StateId, SymbolTable, Tr, Trs, TrsVec, EPS_LABEL, NO_LABEL};
+
+impl<W: SerializableSemiring, C: Compactor<W>, U: CompactIndex> CompactFst<W, C, U> {
+    fn check_state(&self, state: StateId) -> Result<usize> {
+        let s = state as usize;
+        if s < self.num_states {
+            Ok(s)
+        } else {
+            Err(format_err!("State {:?} doesn't exist", state))
+        }
+    }
+
+    /// Final weight of a state followed by its trs. The state must exist.
+    pub(crate) fn expand_state(&self, state: usize) -> (Option<W>, Vec<Tr<W>>) {
+        let (begin, end) = self.elements_range(state);
+        let mut final_weight = None;
+        let mut trs = Vec::with_capacity(end - begin);
+        for element in &self.compacts[begin..end] {
+            let tr = C::expand(state as StateId, element);
+            if tr.ilabel == NO_LABEL {
+                final_weight = Some(tr.weight);
+            } else {
+                trs.push(tr);
+            }
+        }
+        (final_weight, trs)
+    }
+
+    fn has_final_element(&self, state: usize) -> bool {
+        let (begin, end) = self.elements_range(state);
+        begin < end && C::expand(state as StateId, &self.compacts[begin]).ilabel == NO_LABEL
+    }
+
+    fn count_trs<P: Fn(&Tr<W>) -> bool>(&self, state: usize, predicate: P) -> usize {
+        let (begin, end) = self.elements_range(state);
+        self.compacts[begin..end]
+            .iter()
+            .map(|e| C::expand(state as StateId, e))
+            .filter(|tr| tr.ilabel != NO_LABEL && predicate(tr))
+            .count()
+    }
+}
+
+impl<W: SerializableSemiring, C: Compactor<W>, U: CompactIndex> Fst<W> for CompactFst<W, C, U> {
+    fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.isymt.as_ref()
+    }
+
+    fn output_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.osymt.as_ref()
+    }
+
+    fn set_input_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.isymt = Some(symt)
+    }
+
+    fn set_output_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.osymt = Some(symt);
+    }
+
+    fn take_input_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.isymt.take()
+    }
+
+    fn take_output_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.osymt.take()
+    }
+}
+
+impl<W: SerializableSemiring, C: Compactor<W>, U: CompactIndex> CoreFst<W> for CompactFst<W, C, U> {
+    type TRS = TrsVec<W>;
+
+    fn start(&self) -> Option<StateId> {
+        self.start
+    }
+
+    fn final_weight(&self, state_id: StateId) -> Result<Option<W>> {
+        let s = self.check_state(state_id)?;
+        Ok(unsafe { self.final_weight_unchecked(s as StateId) })
+    }
+
+    unsafe fn final_weight_unchecked(&self, state_id: StateId) -> Option<W> {
+        let state = state_id as usize;
+        let (begin, end) = self.elements_range(state);
+        if begin == end {
+            return None;
+        }
+        let tr = C::expand(state_id, self.compacts.get_unchecked(begin));
+        if tr.ilabel == NO_LABEL {
+            Some(tr.weight)
+        } else {
+            None
+        }
+    }
+
+    fn num_trs(&self, s: StateId) -> Result<usize> {
+        let s = self.check_state(s)?;
+        Ok(unsafe { self.num_trs_unchecked(s as StateId) })
+    }
+
+    unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
+        let state = s as usize;
+        let (begin, end) = self.elements_range(state);
+        if self.has_final_element(state) {
+            end - begin - 1
+        } else {
+            end - begin
+        }
+    }
+
+    fn get_trs(&self, state_id: StateId) -> Result<Self::TRS> {
+        let s = self.check_state(state_id)?;
+        Ok(unsafe { self.get_trs_unchecked(s as StateId) })
+    }
+
+    unsafe fn get_trs_unchecked(&self, state_id: StateId) -> Self::TRS {
+        if let CacheStatus::Computed(trs) = self.cache.get_trs(state_id) {
+            return trs;
+        }
+        let (_, trs) = self.expand_state(state_id as usize);
+        let trs = TrsVec(Arc::new(trs));
+        self.cache.insert_trs(state_id, trs.shallow_clone());
+        trs
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.properties
+    }
+
+    fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
+        let s = self.check_state(state)?;
+        Ok(self.count_trs(s, |tr| tr.ilabel == EPS_LABEL))
+    }
+
+    fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
+        let s = self.check_state(state)?;
+        Ok(self.count_trs(s, |tr| tr.olabel == EPS_LABEL))
+    }
+}
@@ -0,0 +1,64 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::fst_impls::compact_fst::{CompactFst, CompactIndex, Compactor};
+use crate::fst_traits::{FstIntoIterator, FstIterData, FstIterator, StateIterator};
+use crate::semirings::SerializableSemiring;
+use crate::{StateId, Tr, TrsVec};
+
+impl<'a, W, C, U> StateIterator<'a> for CompactFst<W, C, U>
+where
+    W: SerializableSemiring,
+    C: Compactor<W>,
+    U: CompactIndex,
+{
+    type Iter = Range<StateId>;
+    fn states_iter(&'a self) -> Self::Iter {
+        0..(self.num_states as StateId)
+    }
+}
+
+impl<'a, W, C, U> FstIterator<'a, W> for CompactFst<W, C, U>
+where
+    W: SerializableSemiring,
+    C: Compactor<W>,
+    U: CompactIndex,
+{
+    type FstIter = Box<dyn Iterator<Item = FstIterData<W, Self::TRS>> + 'a>;
+    fn fst_iter(&'a self) -> Self::FstIter {
+        Box::new((0..self.num_states).map(move |s| {
+            let (final_weight, trs) = self.expand_state(s);
+            FstIterData {
+                state_id: s as StateId,
+                final_weight,
+                num_trs: trs.len(),
+                trs: TrsVec(Arc::new(trs)),
+            }
+        }))
+    }
+}
+
+impl<W, C, U> FstIntoIterator<W> for CompactFst<W, C, U>
+where
+    W: SerializableSemiring,
+    C: Compactor<W>,
+    U: CompactIndex,
+{
+    type TrsIter = std::vec::IntoIter<Tr<W>>;
+    // TODO: Change this to impl once the feature has been stabilized
+    // #![feature(type_alias_impl_trait)]
+    // https://github.com/rust-lang/rust/issues/63063)
+    type FstIter = Box<dyn Iterator<Item = FstIterData<W, Self::TrsIter>>>;
+
+    fn fst_into_iter(self) -> Self::FstIter {
+        Box::new((0..self.num_states).map(move |s| {
+            let (final_weight, trs) = self.expand_state(s);
+            FstIterData {
+                state_id: s as StateId,
+                final_weight,
+                num_trs: trs.len(),
+                trs: trs.into_iter(),
+            }
+        }))
+    }
+}
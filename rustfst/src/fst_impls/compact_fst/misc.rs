@@ -0,0 +1,20 @@
+use std::fmt;
+
+use unsafe_unwrap::UnsafeUnwrap;
+
+use crate::fst_impls::compact_fst::{CompactFst, CompactIndex, Compactor};
+use crate::fst_traits::{CoreFst, Fst, StateIterator};
+use crate::semirings::SerializableSemiring;
+use crate::Trs;
+
+impl<W, C, U> fmt::Display for CompactFst<W, C, U>
+where
+    W: SerializableSemiring,
+    C: Compactor<W>,
+    U: CompactIndex,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_fst!(self, f, true, true);
+        Ok(())
+    }
+}
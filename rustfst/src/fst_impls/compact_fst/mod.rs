@@ -0,0 +1,271 @@
+pub use self::compactors::{
+    AcceptorCompactor, Compactor, StringCompactor, UnweightedAcceptorCompactor,
+    UnweightedCompactor, WeightedStringCompactor,
+};
+pub use self::data_structure::{
+    CompactAcceptorFst, CompactFst, CompactIndex, CompactStringFst, CompactUnweightedAcceptorFst,
+    CompactUnweightedFst, CompactWeightedStringFst,
+};
+
+mod compactors;
+mod converters;
+mod data_structure;
+mod expanded_fst;
+mod fst;
+mod iterators;
+mod misc;
+mod serializable_fst;
+
+pub(super) static COMPACT_MIN_FILE_VERSION: i32 = 1;
+pub(super) static COMPACT_ALIGNED_FILE_VERSION: i32 = 1;
+pub(super) static COMPACT_FILE_VERSION: i32 = 2;
+pub(super) static COMPACT_ARCH_ALIGNMENT: usize = 16;
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::algorithms::fst_convert_from_ref;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{CoreFst, ExpandedFst, MutableFst, SerializableFst};
+    use crate::parsers::SerializeBinary;
+    use crate::semirings::{
+        LogWeight, LogWeight64, Semiring, SerializableSemiring, TropicalWeight,
+    };
+    use crate::utils::acceptor;
+    use crate::{StateId, Tr};
+
+    fn weighted_acceptor() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 0.5, 1))?;
+        fst.add_tr(0, Tr::new(0, 0, 1.5, 2))?;
+        fst.add_tr(1, Tr::new(3, 3, 2.0, 2))?;
+        fst.add_tr(2, Tr::new(2, 2, 0.0, 0))?;
+        fst.set_final(1, 0.3)?;
+        fst.set_final(2, 0.0)?;
+        Ok(fst)
+    }
+
+    fn unweighted_transducer() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 2, TropicalWeight::one(), 1))?;
+        fst.add_tr(0, Tr::new(0, 3, TropicalWeight::one(), 2))?;
+        fst.add_tr(1, Tr::new(3, 0, TropicalWeight::one(), 2))?;
+        fst.set_final(2, TropicalWeight::one())?;
+        Ok(fst)
+    }
+
+    fn check_compact<W, C, U>(fst: &VectorFst<W>) -> Result<()>
+    where
+        W: SerializableSemiring,
+        C: Compactor<W>,
+        U: CompactIndex,
+    {
+        let compact_fst = CompactFst::<W, C, U>::from_fst(fst)?;
+        assert_eq!(compact_fst.num_states(), fst.num_states());
+        assert_eq!(
+            compact_fst.properties() & fst.properties(),
+            fst.properties()
+        );
+        for s in 0..fst.num_states() as StateId {
+            assert_eq!(compact_fst.num_trs(s)?, fst.num_trs(s)?);
+            assert_eq!(
+                compact_fst.num_input_epsilons(s)?,
+                fst.num_input_epsilons(s)?
+            );
+        }
+        let expanded: VectorFst<W> = fst_convert_from_ref(&compact_fst);
+        assert_eq!(&expanded, fst);
+        // The expanded trs are cached.
+        if let Some(start) = compact_fst.start() {
+            let trs = compact_fst.get_trs(start)?;
+            assert!(Arc::ptr_eq(&trs.0, &compact_fst.get_trs(start)?.0));
+        }
+
+        let mut data = vec![];
+        compact_fst.store(&mut data)?;
+        let loaded = CompactFst::<W, C, U>::load(&data)?;
+        assert_eq!(loaded, compact_fst);
+        assert_eq!(loaded.properties(), compact_fst.properties());
+        let (rest, parsed) = CompactFst::<W, C, U>::parse_binary(&data)
+            .map_err(|_| format_err!("Can't parse CompactFst"))?;
+        assert!(rest.is_empty());
+        assert_eq!(parsed, compact_fst);
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_acceptor_fst() -> Result<()> {
+        let fst = weighted_acceptor()?;
+        check_compact::<_, AcceptorCompactor, u32>(&fst)?;
+        check_compact::<_, AcceptorCompactor, u8>(&fst)?;
+        assert!(CompactAcceptorFst::<_>::from_fst(&unweighted_transducer()?).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_unweighted_fst() -> Result<()> {
+        let fst = unweighted_transducer()?;
+        check_compact::<_, UnweightedCompactor, u32>(&fst)?;
+        check_compact::<_, UnweightedCompactor, u16>(&fst)?;
+        assert!(CompactUnweightedFst::<_>::from_fst(&weighted_acceptor()?).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_unweighted_acceptor_fst() -> Result<()> {
+        let mut fst = weighted_acceptor()?;
+        assert!(CompactUnweightedAcceptorFst::<_>::from_fst(&fst).is_err());
+        for s in 0..fst.num_states() as StateId {
+            let mut trs = fst.tr_iter_mut(s)?;
+            for idx in 0..trs.len() {
+                trs.set_weight(idx, TropicalWeight::one())?;
+            }
+            if fst.is_final(s)? {
+                fst.set_final(s, TropicalWeight::one())?;
+            }
+        }
+        check_compact::<_, UnweightedAcceptorCompactor, u32>(&fst)?;
+        check_compact::<_, UnweightedAcceptorCompactor, u64>(&fst)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_string_fst() -> Result<()> {
+        let fst: VectorFst<LogWeight> = acceptor(&[3, 1, 2], LogWeight::one());
+        check_compact::<_, StringCompactor, u32>(&fst)?;
+
+        let weighted_fst: VectorFst<LogWeight> = acceptor(&[3, 1, 2], LogWeight::new(0.7));
+        assert!(CompactStringFst::<_>::from_fst(&weighted_fst).is_err());
+        check_compact::<_, WeightedStringCompactor, u32>(&weighted_fst)?;
+
+        // A string can't have two trs leaving the same state.
+        let mut fst = fst;
+        fst.add_tr(0, Tr::new(4, 4, LogWeight::one(), 1))?;
+        assert!(CompactStringFst::<_>::from_fst(&fst).is_err());
+        assert!(CompactWeightedStringFst::<_>::from_fst(&fst).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_fst_type() {
+        assert_eq!(
+            CompactAcceptorFst::<TropicalWeight>::fst_type(),
+            "compact_acceptor"
+        );
+        assert_eq!(
+            CompactStringFst::<TropicalWeight, u8>::fst_type(),
+            "compact8_string"
+        );
+        assert_eq!(
+            CompactUnweightedFst::<LogWeight, u16>::fst_type(),
+            "compact16_unweighted"
+        );
+        assert_eq!(
+            CompactWeightedStringFst::<LogWeight, u64>::fst_type(),
+            "compact64_weighted_string"
+        );
+    }
+
+    #[test]
+    fn test_compact_fst_index_overflow() -> Result<()> {
+        let labels = (1..300).collect::<Vec<_>>();
+        let fst: VectorFst<TropicalWeight> = acceptor(&labels, TropicalWeight::one());
+        assert!(CompactUnweightedAcceptorFst::<_, u8>::from_fst(&fst).is_err());
+        check_compact::<_, UnweightedAcceptorCompactor, u16>(&fst)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_fst_binary_layout() -> Result<()> {
+        let fst: VectorFst<TropicalWeight> = acceptor(&[5, 6], TropicalWeight::one());
+        let compact_fst = CompactStringFst::<_>::from_fst(&fst)?;
+        let mut data = vec![];
+        compact_fst.store(&mut data)?;
+        // Fixed size compactor : no states index, one label per state, -1 for the final state.
+        let mut elements = vec![];
+        for label in &[5i32, 6, -1] {
+            elements.extend_from_slice(&label.to_le_bytes());
+        }
+        assert!(data.ends_with(&elements));
+        assert!(CompactAcceptorFst::<TropicalWeight>::load(&data).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_fst_load_aligned() -> Result<()> {
+        let fst = unweighted_transducer()?;
+        let compact_fst = CompactUnweightedFst::<_>::from_fst(&fst)?;
+        let mut data = vec![];
+        compact_fst.store(&mut data)?;
+
+        // Rebuild the file in the aligned format (version 1) : the states index and the
+        // elements both start at an offset multiple of 16 bytes.
+        let states_len = 4 * (compact_fst.num_states() + 1);
+        let header_len = data.len() - states_len - 12 * compact_fst.compacts.len();
+        let version_pos = 4 + 4 + "compact_unweighted".len() + 4 + "standard".len();
+        let mut aligned = data[..header_len].to_vec();
+        aligned[version_pos..version_pos + 4].copy_from_slice(&1i32.to_le_bytes());
+        aligned.resize(header_len + 15 - (header_len + 15) % 16, 0);
+        aligned.extend_from_slice(&data[header_len..header_len + states_len]);
+        let padded_len = aligned.len() + 15 - (aligned.len() + 15) % 16;
+        aligned.resize(padded_len, 0);
+        aligned.extend_from_slice(&data[header_len + states_len..]);
+
+        assert_eq!(CompactUnweightedFst::<_>::load(&aligned)?, compact_fst);
+        Ok(())
+    }
+
+    // `compact_acceptor` FST with `log64` weights as written by OpenFST's `fstconvert`. The
+    // elements are `std::pair<std::pair<int, double>, int>` structs of 24 bytes.
+    #[rustfmt::skip]
+    const OPENFST_COMPACT_ACCEPTOR_LOG64: &[u8] = &[
+        // Header : magic number, "compact_acceptor", "log64", version, flags, properties,
+        // start state, number of states and number of trs.
+        0xd6, 0xfd, 0xb2, 0x7e,
+        0x10, 0x00, 0x00, 0x00, 0x63, 0x6f, 0x6d, 0x70, 0x61, 0x63, 0x74, 0x5f,
+        0x61, 0x63, 0x63, 0x65, 0x70, 0x74, 0x6f, 0x72,
+        0x05, 0x00, 0x00, 0x00, 0x6c, 0x6f, 0x67, 0x36, 0x34,
+        0x02, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x01, 0x00, 0x95, 0x5a, 0x69, 0x95, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        // Position of the elements of each state.
+        0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00,
+        // State 0 : tr 1 with weight 0.5 to state 1, with the padding after the label and
+        // the next state.
+        0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xe0, 0x3f,
+        0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        // State 1 : final weight 1.5.
+        0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xf8, 0x3f,
+        0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    #[test]
+    fn test_compact_fst_openfst_padding() -> Result<()> {
+        let mut fst = VectorFst::<LogWeight64>::new();
+        fst.add_states(2);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, LogWeight64::new(0.5), 1))?;
+        fst.set_final(1, LogWeight64::new(1.5))?;
+        let compact_fst = CompactAcceptorFst::<_>::from_fst(&fst)?;
+
+        let loaded = CompactAcceptorFst::<LogWeight64>::load(OPENFST_COMPACT_ACCEPTOR_LOG64)?;
+        assert_eq!(loaded, compact_fst);
+        let mut data = vec![];
+        loaded.store(&mut data)?;
+        assert_eq!(data, OPENFST_COMPACT_ACCEPTOR_LOG64);
+        Ok(())
+    }
+}
@@ -0,0 +1,180 @@
+use std::io::Write;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use anyhow::Result;
+use nom::bytes::complete::take;
+use nom::error::ErrorKind;
+use nom::multi::count;
+use nom::IResult;
+
+use crate::algorithms::lazy::GcCache;
+use crate::fst_impls::compact_fst::{
+    CompactFst, CompactIndex, Compactor, COMPACT_ALIGNED_FILE_VERSION, COMPACT_ARCH_ALIGNMENT,
+    COMPACT_FILE_VERSION, COMPACT_MIN_FILE_VERSION,
+};
+use crate::fst_impls::VectorFst;
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{Fst, SerializableFst};
+use crate::parsers::bin_fst::fst_header::{FstFlags, FstHeader, OpenFstString, FST_MAGIC_NUMBER};
+use crate::parsers::bin_fst::utils_parsing::parse_start_state;
+use crate::parsers::nom_utils::NomCustomError;
+use crate::parsers::text_fst::ParsedTextFst;
+use crate::parsers::SerializeBinary;
+use crate::semirings::SerializableSemiring;
+use crate::Tr;
+
+impl<W, C, U> SerializableFst<W> for CompactFst<W, C, U>
+where
+    W: SerializableSemiring,
+    C: Compactor<W>,
+    U: CompactIndex,
+{
+    fn fst_type() -> String {
+        let bits = U::bits();
+        if bits == 32 {
+            format!("compact_{}", C::compactor_type())
+        } else {
+            format!("compact{}_{}", bits, C::compactor_type())
+        }
+    }
+
+    fn load(data: &[u8]) -> Result<Self> {
+        let (_, parsed_fst) = parse_compact_fst(data)
+            .map_err(|_| format_err!("Error while parsing binary {}Fst", Self::fst_type()))?;
+
+        Ok(parsed_fst)
+    }
+
+    fn store<O: Write>(&self, mut output: O) -> Result<()> {
+        let mut flags = FstFlags::empty();
+        if self.input_symbols().is_some() {
+            flags |= FstFlags::HAS_ISYMBOLS;
+        }
+        if self.output_symbols().is_some() {
+            flags |= FstFlags::HAS_OSYMBOLS;
+        }
+
+        let hdr = FstHeader {
+            magic_number: FST_MAGIC_NUMBER,
+            fst_type: OpenFstString::new(Self::fst_type()),
+            tr_type: OpenFstString::new(Tr::<W>::tr_type()),
+            version: COMPACT_FILE_VERSION,
+            flags,
+            properties: self.properties.bits() | Self::static_properties(),
+            start: self.start.map(|v| v as i64).unwrap_or(-1),
+            num_states: self.num_states as i64,
+            num_trs: self.num_trs as i64,
+            isymt: self.input_symbols().cloned(),
+            osymt: self.output_symbols().cloned(),
+        };
+        hdr.write(&mut output)?;
+
+        for pos in &self.states {
+            pos.write_binary(&mut output)?;
+        }
+        for element in self.compacts.iter() {
+            C::write_element(element, &mut output)?;
+        }
+
+        output.flush()?;
+        Ok(())
+    }
+
+    fn from_parsed_fst_text(parsed_fst_text: ParsedTextFst<W>) -> Result<Self> {
+        let fst = VectorFst::from_parsed_fst_text(parsed_fst_text)?;
+        Self::from_fst(&fst)
+    }
+}
+
+impl<W, C, U> SerializeBinary for CompactFst<W, C, U>
+where
+    W: SerializableSemiring,
+    C: Compactor<W>,
+    U: CompactIndex,
+{
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+        parse_compact_fst(i)
+    }
+
+    fn write_binary<WB: Write>(&self, writer: &mut WB) -> Result<()> {
+        self.store(writer)
+    }
+}
+
+fn align_input(i: &[u8], stream_len: usize) -> IResult<&[u8], (), NomCustomError<&[u8]>> {
+    let offset = (stream_len - i.len()) % COMPACT_ARCH_ALIGNMENT;
+    if offset > 0 {
+        let (i, _) = take(COMPACT_ARCH_ALIGNMENT - offset)(i)?;
+        return Ok((i, ()));
+    }
+    Ok((i, ()))
+}
+
+type ParsedCompactFst<'a, W, C, U> =
+    IResult<&'a [u8], CompactFst<W, C, U>, NomCustomError<&'a [u8]>>;
+
+fn parse_compact_fst<W, C, U>(i: &[u8]) -> ParsedCompactFst<'_, W, C, U>
+where
+    W: SerializableSemiring,
+    C: Compactor<W>,
+    U: CompactIndex,
+{
+    let stream_len = i.len();
+    let invalid = |i| nom::Err::Error(NomCustomError::Nom(i, ErrorKind::Verify));
+
+    let (mut i, hdr) = FstHeader::parse(
+        i,
+        COMPACT_MIN_FILE_VERSION,
+        CompactFst::<W, C, U>::fst_type(),
+        Tr::<W>::tr_type(),
+    )?;
+    let aligned =
+        hdr.version == COMPACT_ALIGNED_FILE_VERSION || hdr.flags.contains(FstFlags::IS_ALIGNED);
+    if hdr.num_states < 0 || hdr.num_trs < 0 {
+        return Err(invalid(i));
+    }
+    let num_states = hdr.num_states as usize;
+
+    let mut states = vec![];
+    let num_compacts = match C::size() {
+        Some(size) => num_states * size,
+        None => {
+            if aligned {
+                i = align_input(i, stream_len)?.0;
+            }
+            let (rest, parsed_states) = count(U::parse_binary, num_states + 1)(i)?;
+            // The elements of each state must be located after the ones of the previous state.
+            if parsed_states
+                .windows(2)
+                .any(|w| w[0].to_usize() > w[1].to_usize())
+            {
+                return Err(invalid(i));
+            }
+            i = rest;
+            states = parsed_states;
+            states[num_states].to_usize()
+        }
+    };
+
+    if aligned {
+        i = align_input(i, stream_len)?.0;
+    }
+    let (i, compacts) = count(C::parse_element, num_compacts)(i)?;
+
+    Ok((
+        i,
+        CompactFst {
+            states,
+            compacts: Arc::new(compacts),
+            num_states,
+            num_trs: hdr.num_trs as usize,
+            start: parse_start_state(hdr.start),
+            isymt: hdr.isymt,
+            osymt: hdr.osymt,
+            properties: FstProperties::from_bits_truncate(hdr.properties),
+            cache: GcCache::default(),
+            compactor: PhantomData,
+        },
+    ))
+}
@@ -0,0 +1,503 @@
+use std::fmt;
+use std::fs::File;
+use std::marker::PhantomData;
+use std::mem::{align_of, size_of};
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{format_err, Context, Result};
+use memmap2::Mmap;
+
+use crate::fst_impls::const_fst::{
+    CONST_ALIGNED_FILE_VERSION, CONST_ARCH_ALIGNMENT, CONST_MIN_FILE_VERSION,
+};
+use crate::fst_impls::ConstFst;
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{
+    CoreFst, ExpandedFst, Fst, FstIntoIterator, FstIterData, FstIterator, SerializableFst,
+    StateIterator,
+};
+use crate::parsers::bin_fst::fst_header::{FstFlags, FstHeader};
+use crate::parsers::bin_fst::utils_parsing::{parse_final_weight, parse_start_state};
+use crate::semirings::{LogWeight, SerializableSemiring, TropicalWeight};
+use crate::{Label, StateId, SymbolTable, Tr, Trs, TrsVec};
+
+/// Weights whose in-memory representation is exactly their binary serialization.
+///
+/// The trs of a memory-mapped `ConstFst` are read in place for such weights.
+///
+/// # Safety
+///
+/// The type must have no invalid bit patterns and its layout must be the one written by
+/// `SerializableSemiring::write_binary` on a little-endian platform.
+pub unsafe trait ZeroCopyWeight: SerializableSemiring + Copy {}
+
+unsafe impl ZeroCopyWeight for TropicalWeight {}
+unsafe impl ZeroCopyWeight for LogWeight {}
+
+// Binary layout of a state : final weight, pos, ntrs, niepsilons, noepsilons.
+const STATE_FIELDS_SIZE: usize = 4 * size_of::<i32>();
+
+/// Immutable FST backed by a memory-mapped `ConstFst` file.
+///
+/// The states and the trs are read directly from the mapped file without being copied,
+/// so several processes loading the same file share the page cache. The file must have
+/// been written with the trs aligned in memory, which is guaranteed by
+/// `ConstFst::write_aligned` (or `fstconvert --fst_align` on the OpenFST side).
+pub struct MmapConstFst<W> {
+    data: Arc<Mmap>,
+    start: Option<StateId>,
+    num_states: usize,
+    states_offset: usize,
+    num_trs: usize,
+    trs_offset: usize,
+    isymt: Option<Arc<SymbolTable>>,
+    osymt: Option<Arc<SymbolTable>>,
+    properties: FstProperties,
+    w: PhantomData<W>,
+}
+
+/// Trs of a state of a `MmapConstFst`, pointing into the memory-mapped file.
+pub struct TrsMmap<W> {
+    data: Arc<Mmap>,
+    offset: usize,
+    n: usize,
+    w: PhantomData<W>,
+}
+
+impl<W: ZeroCopyWeight> ConstFst<W> {
+    /// Memory-maps a `ConstFst` binary file instead of parsing it.
+    ///
+    /// Fails if the trs stored in the file are not aligned for the current platform.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified while the returned FST is alive.
+    pub unsafe fn mmap<P: AsRef<Path>>(path_bin_fst: P) -> Result<MmapConstFst<W>> {
+        let file = File::open(path_bin_fst.as_ref()).with_context(|| {
+            format!(
+                "Can't open ConstFst binary file : {:?}",
+                path_bin_fst.as_ref()
+            )
+        })?;
+        let mmap = Mmap::map(&file)?;
+        MmapConstFst::new(mmap)
+    }
+}
+
+impl<W: ZeroCopyWeight> MmapConstFst<W> {
+    fn new(mmap: Mmap) -> Result<Self> {
+        if cfg!(target_endian = "big")
+            || size_of::<Label>() != size_of::<i32>()
+            || size_of::<StateId>() != size_of::<i32>()
+            || size_of::<Tr<W>>() != 3 * size_of::<i32>() + size_of::<W>()
+        {
+            bail!("MmapConstFst : the binary format of the trs can't be used in place on this platform");
+        }
+        let data = &mmap[..];
+
+        let (i, hdr) = FstHeader::parse(
+            data,
+            CONST_MIN_FILE_VERSION,
+            ConstFst::<W>::fst_type(),
+            Tr::<W>::tr_type(),
+        )
+        .map_err(|_| format_err!("Error while parsing the header of a binary ConstFst"))?;
+        let aligned =
+            hdr.version == CONST_ALIGNED_FILE_VERSION || hdr.flags.contains(FstFlags::IS_ALIGNED);
+        let max_size = data.len() as i64;
+        if hdr.num_states < 0
+            || hdr.num_states > max_size
+            || hdr.num_trs < 0
+            || hdr.num_trs > max_size
+        {
+            bail!("MmapConstFst : invalid number of states or trs");
+        }
+        let num_states = hdr.num_states as usize;
+        let num_trs = hdr.num_trs as usize;
+
+        let align = |pos: usize| {
+            let offset = pos % CONST_ARCH_ALIGNMENT;
+            if aligned && offset > 0 {
+                pos + CONST_ARCH_ALIGNMENT - offset
+            } else {
+                pos
+            }
+        };
+        let states_offset = align(data.len() - i.len());
+        let trs_offset = align(states_offset + num_states * Self::state_size());
+        if data.len() < trs_offset + num_trs * size_of::<Tr<W>>() {
+            bail!("MmapConstFst : truncated file");
+        }
+        let misalignment = (data.as_ptr() as usize + trs_offset) % align_of::<Tr<W>>();
+        if misalignment > 0 {
+            bail!("MmapConstFst : the trs are not aligned in the file. It must be written with `ConstFst::write_aligned`");
+        }
+
+        let fst = Self {
+            start: parse_start_state(hdr.start),
+            num_states,
+            states_offset,
+            num_trs,
+            trs_offset,
+            isymt: hdr.isymt,
+            osymt: hdr.osymt,
+            properties: FstProperties::from_bits_truncate(hdr.properties),
+            data: Arc::new(mmap),
+            w: PhantomData,
+        };
+
+        // The trs slices are built from the states fields : check them once and for all.
+        for s in 0..num_states {
+            let pos = fst.state_field(s, 0);
+            let ntrs = fst.state_field(s, 1);
+            if pos < 0 || ntrs < 0 || pos as usize + ntrs as usize > num_trs {
+                bail!("MmapConstFst : invalid trs of state {}", s);
+            }
+        }
+        if let Some(start) = fst.start {
+            if start as usize >= num_states {
+                bail!("MmapConstFst : invalid start state {}", start);
+            }
+        }
+        Ok(fst)
+    }
+
+    fn states_bytes(&self) -> &[u8] {
+        &self.data[self.states_offset..self.states_offset + self.num_states * Self::state_size()]
+    }
+
+    fn state_size() -> usize {
+        size_of::<W>() + STATE_FIELDS_SIZE
+    }
+
+    /// Reads the i-th integer field of the state `s` which must exist.
+    fn state_field(&self, s: usize, field: usize) -> i32 {
+        let offset = self.states_offset + s * Self::state_size() + size_of::<W>() + 4 * field;
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(&self.data[offset..offset + 4]);
+        i32::from_le_bytes(bytes)
+    }
+
+    fn check_state(&self, s: StateId) -> Result<usize> {
+        let s = s as usize;
+        if s < self.num_states {
+            Ok(s)
+        } else {
+            Err(format_err!("State {:?} doesn't exist", s))
+        }
+    }
+
+    unsafe fn final_weight_unchecked_usize(&self, s: usize) -> Option<W> {
+        let offset = self.states_offset + s * Self::state_size();
+        let weight = std::ptr::read_unaligned(self.data.as_ptr().add(offset) as *const W);
+        parse_final_weight(weight)
+    }
+
+    fn trs_of_state(&self, s: usize) -> TrsMmap<W> {
+        TrsMmap {
+            data: Arc::clone(&self.data),
+            offset: self.trs_offset + self.state_field(s, 0) as usize * size_of::<Tr<W>>(),
+            n: self.state_field(s, 1) as usize,
+            w: PhantomData,
+        }
+    }
+}
+
+impl<W: ZeroCopyWeight> Trs<W> for TrsMmap<W> {
+    fn trs(&self) -> &[Tr<W>] {
+        // Safety : the offset and the number of trs have been checked against the size of
+        // the mapped file and its alignment when loading the FST.
+        unsafe {
+            std::slice::from_raw_parts(self.data.as_ptr().add(self.offset) as *const Tr<W>, self.n)
+        }
+    }
+
+    fn to_trs_vec(&self) -> TrsVec<W> {
+        TrsVec(Arc::new(self.trs().to_vec()))
+    }
+
+    fn shallow_clone(&self) -> Self {
+        Self {
+            data: Arc::clone(&self.data),
+            offset: self.offset,
+            n: self.n,
+            w: PhantomData,
+        }
+    }
+}
+
+impl<W: ZeroCopyWeight> std::ops::Deref for TrsMmap<W> {
+    type Target = [Tr<W>];
+    fn deref(&self) -> &Self::Target {
+        self.trs()
+    }
+}
+
+impl<W: ZeroCopyWeight> fmt::Debug for TrsMmap<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.trs()).finish()
+    }
+}
+
+impl<W: ZeroCopyWeight> CoreFst<W> for MmapConstFst<W> {
+    type TRS = TrsMmap<W>;
+
+    fn start(&self) -> Option<StateId> {
+        self.start
+    }
+
+    fn final_weight(&self, state_id: StateId) -> Result<Option<W>> {
+        let s = self.check_state(state_id)?;
+        Ok(unsafe { self.final_weight_unchecked_usize(s) })
+    }
+
+    unsafe fn final_weight_unchecked(&self, state_id: StateId) -> Option<W> {
+        self.final_weight_unchecked_usize(state_id as usize)
+    }
+
+    fn num_trs(&self, s: StateId) -> Result<usize> {
+        let s = self.check_state(s)?;
+        Ok(self.state_field(s, 1) as usize)
+    }
+
+    unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
+        self.state_field(s as usize, 1) as usize
+    }
+
+    fn get_trs(&self, state_id: StateId) -> Result<Self::TRS> {
+        let s = self.check_state(state_id)?;
+        Ok(self.trs_of_state(s))
+    }
+
+    unsafe fn get_trs_unchecked(&self, state_id: StateId) -> Self::TRS {
+        self.trs_of_state(state_id as usize)
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.properties
+    }
+
+    fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
+        let s = self.check_state(state)?;
+        Ok(self.state_field(s, 2) as usize)
+    }
+
+    fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
+        let s = self.check_state(state)?;
+        Ok(self.state_field(s, 3) as usize)
+    }
+}
+
+impl<'a, W: ZeroCopyWeight> StateIterator<'a> for MmapConstFst<W> {
+    type Iter = Range<StateId>;
+    fn states_iter(&'a self) -> Self::Iter {
+        0..(self.num_states as StateId)
+    }
+}
+
+impl<'a, W: ZeroCopyWeight> FstIterator<'a, W> for MmapConstFst<W> {
+    type FstIter = Box<dyn Iterator<Item = FstIterData<W, Self::TRS>> + 'a>;
+    fn fst_iter(&'a self) -> Self::FstIter {
+        Box::new((0..self.num_states).map(move |s| {
+            let trs = self.trs_of_state(s);
+            FstIterData {
+                state_id: s as StateId,
+                final_weight: unsafe { self.final_weight_unchecked_usize(s) },
+                num_trs: trs.n,
+                trs,
+            }
+        }))
+    }
+}
+
+impl<W: ZeroCopyWeight + 'static> FstIntoIterator<W> for MmapConstFst<W> {
+    type TrsIter = std::vec::IntoIter<Tr<W>>;
+    type FstIter = Box<dyn Iterator<Item = FstIterData<W, Self::TrsIter>>>;
+
+    fn fst_into_iter(self) -> Self::FstIter {
+        Box::new((0..self.num_states).map(move |s| {
+            let trs: Vec<_> = self.trs_of_state(s).to_vec();
+            FstIterData {
+                state_id: s as StateId,
+                final_weight: unsafe { self.final_weight_unchecked_usize(s) },
+                num_trs: trs.len(),
+                trs: trs.into_iter(),
+            }
+        }))
+    }
+}
+
+impl<W: ZeroCopyWeight> Fst<W> for MmapConstFst<W> {
+    fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.isymt.as_ref()
+    }
+
+    fn output_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.osymt.as_ref()
+    }
+
+    fn set_input_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.isymt = Some(symt)
+    }
+
+    fn set_output_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.osymt = Some(symt);
+    }
+
+    fn take_input_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.isymt.take()
+    }
+
+    fn take_output_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.osymt.take()
+    }
+}
+
+impl<W: ZeroCopyWeight + 'static> ExpandedFst<W> for MmapConstFst<W> {
+    fn num_states(&self) -> usize {
+        self.num_states
+    }
+}
+
+// Doesn't copy the mapped data.
+impl<W> Clone for MmapConstFst<W> {
+    fn clone(&self) -> Self {
+        Self {
+            data: Arc::clone(&self.data),
+            start: self.start,
+            num_states: self.num_states,
+            states_offset: self.states_offset,
+            num_trs: self.num_trs,
+            trs_offset: self.trs_offset,
+            isymt: self.isymt.clone(),
+            osymt: self.osymt.clone(),
+            properties: self.properties,
+            w: PhantomData,
+        }
+    }
+}
+
+impl<W: ZeroCopyWeight> PartialEq for MmapConstFst<W> {
+    fn eq(&self, other: &Self) -> bool {
+        // Indended: Doesn't check symt and properties
+        self.start == other.start
+            && self.states_bytes() == other.states_bytes()
+            && (0..self.num_states)
+                .all(|s| self.trs_of_state(s).trs() == other.trs_of_state(s).trs())
+    }
+}
+
+impl<W: ZeroCopyWeight> fmt::Debug for MmapConstFst<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MmapConstFst")
+            .field("start", &self.start)
+            .field("num_states", &self.num_states)
+            .field("num_trs", &self.num_trs)
+            .field("isymt", &self.isymt)
+            .field("osymt", &self.osymt)
+            .field("properties", &self.properties)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{fst_convert_from_ref, shortest_path};
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::MutableFst;
+    use crate::parsers::SerializeBinary;
+    use crate::semirings::Semiring;
+
+    fn build_fst() -> Result<ConstFst<TropicalWeight>> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 2, 1.5, 1))?;
+        fst.add_tr(0, Tr::new(0, 3, 0.5, 2))?;
+        fst.add_tr(1, Tr::new(4, 0, 2.0, 3))?;
+        fst.add_tr(2, Tr::new(5, 5, 4.0, 3))?;
+        fst.add_tr(2, Tr::new(6, 6, 0.25, 1))?;
+        fst.set_final(3, 0.75)?;
+        let mut symt = SymbolTable::new();
+        symt.add_symbol("a");
+        fst.set_input_symbols(Arc::new(symt));
+        Ok(fst.into())
+    }
+
+    #[test]
+    fn test_mmap_const_fst() -> Result<()> {
+        let fst = build_fst()?;
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("aligned.fst");
+        fst.write_aligned(&path)?;
+
+        let mmap_fst = unsafe { ConstFst::<TropicalWeight>::mmap(&path)? };
+        assert_eq!(mmap_fst.start(), fst.start());
+        assert_eq!(mmap_fst.num_states(), fst.num_states());
+        assert_eq!(mmap_fst.input_symbols(), fst.input_symbols());
+        for s in fst.states_iter() {
+            assert_eq!(mmap_fst.final_weight(s)?, fst.final_weight(s)?);
+            assert_eq!(mmap_fst.get_trs(s)?.trs(), fst.get_trs(s)?.trs());
+            assert_eq!(mmap_fst.num_input_epsilons(s)?, fst.num_input_epsilons(s)?);
+            assert_eq!(
+                mmap_fst.num_output_epsilons(s)?,
+                fst.num_output_epsilons(s)?
+            );
+        }
+        assert!(mmap_fst.get_trs(4).is_err());
+
+        let vfst: VectorFst<_> = fst_convert_from_ref(&mmap_fst);
+        let vfst_ref: VectorFst<_> = fst_convert_from_ref(&fst);
+        assert_eq!(vfst, vfst_ref);
+
+        let path: VectorFst<TropicalWeight> = shortest_path(&mmap_fst)?;
+        let path_ref: VectorFst<TropicalWeight> = shortest_path(&fst)?;
+        assert_eq!(path, path_ref);
+        Ok(())
+    }
+
+    #[test]
+    fn test_aligned_const_fst_can_be_parsed() -> Result<()> {
+        let fst = build_fst()?;
+        let mut data = vec![];
+        fst.store_aligned(&mut data)?;
+        assert_eq!(ConstFst::<TropicalWeight>::load(&data)?, fst);
+
+        // The empty sections are padded as well.
+        let mut fst_no_trs = VectorFst::<TropicalWeight>::new();
+        fst_no_trs.add_state();
+        fst_no_trs.set_start(0)?;
+        fst_no_trs.set_final(0, 0.5)?;
+        for fst in [VectorFst::new(), fst_no_trs] {
+            let fst: ConstFst<TropicalWeight> = fst.into();
+            let mut data = vec![];
+            fst.store_aligned(&mut data)?;
+            assert_eq!(data.len() % CONST_ARCH_ALIGNMENT, 0);
+            let (rest, parsed) = ConstFst::<TropicalWeight>::parse_binary(&data)
+                .map_err(|_| format_err!("Error while parsing the aligned ConstFst"))?;
+            assert!(rest.is_empty());
+            assert_eq!(parsed, fst);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_mmap_const_fst_unaligned() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(2);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, TropicalWeight::one(), 1))?;
+        fst.set_final(1, TropicalWeight::one())?;
+        let fst: ConstFst<_> = fst.into();
+
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("unaligned.fst");
+        // Without symbol tables, the header of an unaligned file is 65 bytes long.
+        fst.write(&path)?;
+        assert!(unsafe { ConstFst::<TropicalWeight>::mmap(&path) }.is_err());
+        Ok(())
+    }
+}
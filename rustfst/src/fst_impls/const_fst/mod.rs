@@ -1,4 +1,5 @@
 pub use self::data_structure::ConstFst;
+pub use self::mmap_const_fst::{MmapConstFst, TrsMmap, ZeroCopyWeight};
 
 mod converters;
 mod data_structure;
@@ -6,6 +7,7 @@ mod expanded_fst;
 mod fst;
 mod iterators;
 mod misc;
+mod mmap_const_fst;
 mod serializable_fst;
 
 pub(super) static CONST_MIN_FILE_VERSION: i32 = 1;
@@ -1,7 +1,9 @@
-use std::io::Write;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
 use std::sync::Arc;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use itertools::Itertools;
 use nom::bytes::complete::take;
 use nom::multi::count;
@@ -21,7 +23,7 @@ use crate::parsers::bin_fst::utils_parsing::{
 use crate::parsers::nom_utils::NomCustomError;
 use crate::parsers::parse_bin_i32;
 use crate::parsers::text_fst::ParsedTextFst;
-use crate::parsers::write_bin_i32;
+use crate::parsers::{write_bin_i32, SerializeBinary};
 use crate::semirings::SerializableSemiring;
 use crate::{Tr, EPS_LABEL};
 
@@ -37,50 +39,8 @@ impl<W: SerializableSemiring> SerializableFst<W> for ConstFst<W> {
         Ok(parsed_fst)
     }
 
-    fn store<O: Write>(&self, mut output: O) -> Result<()> {
-        let mut flags = FstFlags::empty();
-        if self.input_symbols().is_some() {
-            flags |= FstFlags::HAS_ISYMBOLS;
-        }
-        if self.output_symbols().is_some() {
-            flags |= FstFlags::HAS_OSYMBOLS;
-        }
-
-        let hdr = FstHeader {
-            magic_number: FST_MAGIC_NUMBER,
-            fst_type: OpenFstString::new(Self::fst_type()),
-            tr_type: OpenFstString::new(Tr::<W>::tr_type()),
-            version: CONST_FILE_VERSION,
-            // TODO: Set flags if the content is aligned
-            flags,
-            properties: self.properties.bits() | ConstFst::<W>::static_properties(),
-            start: self.start.map(|v| v as i64).unwrap_or(-1),
-            num_states: self.num_states() as i64,
-            num_trs: self.trs.len() as i64,
-            isymt: self.input_symbols().cloned(),
-            osymt: self.output_symbols().cloned(),
-        };
-        hdr.write(&mut output)?;
-
-        let zero = W::zero();
-        for const_state in &self.states {
-            let f_weight = const_state.final_weight.as_ref().unwrap_or(&zero);
-            f_weight.write_binary(&mut output)?;
-
-            write_bin_i32(&mut output, const_state.pos as i32)?;
-            write_bin_i32(&mut output, const_state.ntrs as i32)?;
-            write_bin_i32(&mut output, const_state.niepsilons as i32)?;
-            write_bin_i32(&mut output, const_state.noepsilons as i32)?;
-        }
-
-        for tr in &*self.trs {
-            write_bin_i32(&mut output, tr.ilabel as i32)?;
-            write_bin_i32(&mut output, tr.olabel as i32)?;
-            tr.weight.write_binary(&mut output)?;
-            write_bin_i32(&mut output, tr.nextstate as i32)?;
-        }
-
-        Ok(())
+    fn store<O: Write>(&self, output: O) -> Result<()> {
+        self.store_with_alignment(output, false)
     }
 
     fn from_parsed_fst_text(mut parsed_fst_text: ParsedTextFst<W>) -> Result<Self> {
@@ -196,6 +156,130 @@ fn parse_const_state<W: SerializableSemiring>(
     ))
 }
 
+/// Keeps track of the number of bytes written to be able to align the sections of the file.
+struct PositionWriter<O> {
+    output: O,
+    position: usize,
+}
+
+impl<O: Write> Write for PositionWriter<O> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.output.write(buf)?;
+        self.position += n;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.output.flush()
+    }
+}
+
+impl<O: Write> PositionWriter<O> {
+    fn align(&mut self) -> Result<()> {
+        let offset = self.position % CONST_ARCH_ALIGNMENT;
+        if offset > 0 {
+            self.write_all(&vec![0u8; CONST_ARCH_ALIGNMENT - offset])?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: SerializableSemiring> ConstFst<W> {
+    /// Store the FST in binary format to a `Write`, padding the states and the trs so that
+    /// they start at offsets multiple of 16 bytes (`FstFlags::IS_ALIGNED`).
+    ///
+    /// This is the format expected by `MmapConstFst`.
+    pub fn store_aligned<O: Write>(&self, output: O) -> Result<()> {
+        self.store_with_alignment(output, true)
+    }
+
+    /// Writes the FST to a file in aligned binary format. See `store_aligned`.
+    pub fn write_aligned<P: AsRef<Path>>(&self, path_bin_fst: P) -> Result<()> {
+        let output = File::create(path_bin_fst.as_ref()).with_context(|| {
+            format!(
+                "Cannot create {}Fst binary file : {:?}",
+                Self::fst_type(),
+                path_bin_fst.as_ref(),
+            )
+        })?;
+        self.store_aligned(BufWriter::new(output))
+    }
+
+    fn store_with_alignment<O: Write>(&self, output: O, aligned: bool) -> Result<()> {
+        let mut output = PositionWriter {
+            output,
+            position: 0,
+        };
+        let mut flags = FstFlags::empty();
+        if self.input_symbols().is_some() {
+            flags |= FstFlags::HAS_ISYMBOLS;
+        }
+        if self.output_symbols().is_some() {
+            flags |= FstFlags::HAS_OSYMBOLS;
+        }
+        if aligned {
+            flags |= FstFlags::IS_ALIGNED;
+        }
+
+        let hdr = FstHeader {
+            magic_number: FST_MAGIC_NUMBER,
+            fst_type: OpenFstString::new(Self::fst_type()),
+            tr_type: OpenFstString::new(Tr::<W>::tr_type()),
+            version: if aligned {
+                CONST_ALIGNED_FILE_VERSION
+            } else {
+                CONST_FILE_VERSION
+            },
+            flags,
+            properties: self.properties.bits() | ConstFst::<W>::static_properties(),
+            start: self.start.map(|v| v as i64).unwrap_or(-1),
+            num_states: self.num_states() as i64,
+            num_trs: self.trs.len() as i64,
+            isymt: self.input_symbols().cloned(),
+            osymt: self.output_symbols().cloned(),
+        };
+        hdr.write(&mut output)?;
+        // As OpenFST, the sections are aligned even when they are empty.
+        if aligned {
+            output.align()?;
+        }
+
+        let zero = W::zero();
+        for const_state in &self.states {
+            let f_weight = const_state.final_weight.as_ref().unwrap_or(&zero);
+            f_weight.write_binary(&mut output)?;
+
+            write_bin_i32(&mut output, const_state.pos as i32)?;
+            write_bin_i32(&mut output, const_state.ntrs as i32)?;
+            write_bin_i32(&mut output, const_state.niepsilons as i32)?;
+            write_bin_i32(&mut output, const_state.noepsilons as i32)?;
+        }
+
+        if aligned {
+            output.align()?;
+        }
+        for tr in &*self.trs {
+            write_bin_i32(&mut output, tr.ilabel as i32)?;
+            write_bin_i32(&mut output, tr.olabel as i32)?;
+            tr.weight.write_binary(&mut output)?;
+            write_bin_i32(&mut output, tr.nextstate as i32)?;
+        }
+
+        output.flush()?;
+        Ok(())
+    }
+}
+
+impl<W: SerializableSemiring> SerializeBinary for ConstFst<W> {
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+        parse_const_fst(i)
+    }
+
+    fn write_binary<WB: Write>(&self, writer: &mut WB) -> Result<()> {
+        self.store(writer)
+    }
+}
+
 fn parse_const_fst<W: SerializableSemiring>(
     i: &[u8],
 ) -> IResult<&[u8], ConstFst<W>, NomCustomError<&[u8]>> {
@@ -207,18 +291,19 @@ fn parse_const_fst<W: SerializableSemiring>(
         ConstFst::<W>::fst_type(),
         Tr::<W>::tr_type(),
     )?;
-    let aligned = hdr.version == CONST_ALIGNED_FILE_VERSION;
+    let aligned =
+        hdr.version == CONST_ALIGNED_FILE_VERSION || hdr.flags.contains(FstFlags::IS_ALIGNED);
     let pos = stream_len - i.len();
 
     // Align input
-    if aligned && hdr.num_states > 0 && pos % CONST_ARCH_ALIGNMENT > 0 {
+    if aligned && pos % CONST_ARCH_ALIGNMENT > 0 {
         i = take(CONST_ARCH_ALIGNMENT - (pos % CONST_ARCH_ALIGNMENT))(i)?.0;
     }
     let (mut i, const_states) = count(parse_const_state, hdr.num_states as usize)(i)?;
     let pos = stream_len - i.len();
 
     // Align input
-    if aligned && hdr.num_trs > 0 && pos % CONST_ARCH_ALIGNMENT > 0 {
+    if aligned && pos % CONST_ARCH_ALIGNMENT > 0 {
         i = take(CONST_ARCH_ALIGNMENT - (pos % CONST_ARCH_ALIGNMENT))(i)?.0;
     }
     let (i, const_trs) = count(parse_bin_fst_tr, hdr.num_trs as usize)(i)?;
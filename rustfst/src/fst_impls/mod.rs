@@ -1,6 +1,12 @@
 mod arc;
+pub(crate) mod compact_fst;
 pub(crate) mod const_fst;
 pub(crate) mod vector_fst;
 
-pub use self::const_fst::ConstFst;
+pub use self::compact_fst::{
+    AcceptorCompactor, CompactAcceptorFst, CompactFst, CompactIndex, CompactStringFst,
+    CompactUnweightedAcceptorFst, CompactUnweightedFst, CompactWeightedStringFst, Compactor,
+    StringCompactor, UnweightedAcceptorCompactor, UnweightedCompactor, WeightedStringCompactor,
+};
+pub use self::const_fst::{ConstFst, MmapConstFst, TrsMmap, ZeroCopyWeight};
 pub use self::vector_fst::VectorFst;
@@ -17,7 +17,7 @@ use crate::parsers::bin_fst::utils_parsing::{
 use crate::parsers::bin_fst::utils_serialization::write_bin_fst_tr;
 use crate::parsers::nom_utils::NomCustomError;
 use crate::parsers::text_fst::ParsedTextFst;
-use crate::parsers::write_bin_i64;
+use crate::parsers::{write_bin_i64, SerializeBinary};
 use crate::semirings::SerializableSemiring;
 use crate::{StateId, Tr, Trs, TrsVec, EPS_LABEL};
 
@@ -124,6 +124,16 @@ impl<W: SerializableSemiring> SerializableFst<W> for VectorFst<W> {
     }
 }
 
+impl<W: SerializableSemiring> SerializeBinary for VectorFst<W> {
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+        parse_vector_fst(i)
+    }
+
+    fn write_binary<WB: Write>(&self, writer: &mut WB) -> Result<()> {
+        self.store(writer)
+    }
+}
+
 static VECTOR_MIN_FILE_VERSION: i32 = 2;
 
 fn parse_vector_fst_state<W: SerializableSemiring>(
@@ -144,8 +144,26 @@ pub fn closure_properties(inprops: FstProperties, delayed: bool) -> FstPropertie
     outprops
 }
 
-pub fn complement_properties(_inprops: FstProperties) -> FstProperties {
-    unimplemented!()
+pub fn complement_properties(inprops: FstProperties) -> FstProperties {
+    let mut outprops = FstProperties::ACCEPTOR
+        | FstProperties::UNWEIGHTED
+        | FstProperties::UNWEIGHTED_CYCLES
+        | FstProperties::NO_EPSILONS
+        | FstProperties::NO_I_EPSILONS
+        | FstProperties::NO_O_EPSILONS
+        | FstProperties::I_DETERMINISTIC
+        | FstProperties::O_DETERMINISTIC
+        | FstProperties::ACCESSIBLE;
+    outprops |= (FstProperties::I_LABEL_SORTED
+        | FstProperties::O_LABEL_SORTED
+        | FstProperties::INITIAL_CYCLIC)
+        & inprops;
+    if inprops.contains(FstProperties::ACCESSIBLE) {
+        outprops |= FstProperties::NOT_I_LABEL_SORTED
+            | FstProperties::NOT_O_LABEL_SORTED
+            | FstProperties::CYCLIC;
+    }
+    outprops
 }
 
 pub fn compose_properties(inprops1: FstProperties, inprops2: FstProperties) -> FstProperties {
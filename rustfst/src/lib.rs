@@ -256,6 +256,8 @@ pub mod fst_traits;
 pub mod fst_impls;
 /// Provides a trait that shall be implemented for all weights stored inside a wFST.
 pub mod semirings;
+/// Reading and writing of FST archives (FAR).
+pub mod far;
 
 mod drawing_config;
 /// Implementation of a successful path inside a wFST.
@@ -263,6 +265,7 @@ mod fst_path;
 mod parsers;
 mod string_path;
 
+pub use crate::parsers::bin_fst::fst_header::{BinaryFstHeader, FstFlags};
 pub use crate::parsers::nom_utils::NomCustomError;
 
 /// A representable float near .001. (Used in Quantize)
@@ -1,19 +1,27 @@
 use std::io::Write;
+use std::path::Path;
 
-use anyhow::Result;
+use anyhow::{format_err, Context, Result};
 use nom::bytes::complete::take;
 use nom::combinator::{map_res, verify};
 use nom::IResult;
 
 use bitflags::bitflags;
 
-use crate::parsers::bin_symt::nom_parser::{parse_symbol_table_bin, write_bin_symt};
+use crate::fst_properties::FstProperties;
+use crate::parsers::bin_fst::utils_parsing::parse_start_state;
+use crate::parsers::bin_symt::nom_parser::{
+    parse_named_symbol_table_bin, parse_symbol_table_bin, write_bin_symt,
+};
 use crate::parsers::nom_utils::NomCustomError;
 use crate::parsers::{parse_bin_i32, parse_bin_i64, parse_bin_u32, parse_bin_u64};
 use crate::parsers::{write_bin_i32, write_bin_i64, write_bin_u32, write_bin_u64};
-use crate::SymbolTable;
+use crate::{StateId, SymbolTable};
 use std::sync::Arc;
 
+/// Symbol table stored in a binary FST header along with its name.
+type NamedSymbolTable = (String, Arc<SymbolTable>);
+
 // Identifies stream data as an FST (and its endianity).
 pub(crate) static FST_MAGIC_NUMBER: i32 = 2_125_659_606;
 
@@ -167,3 +175,141 @@ impl From<OpenFstString> for String {
         os.s
     }
 }
+
+/// Header of a binary FST file, as written by rustfst and OpenFST.
+///
+/// It can be read without knowing the type of the FST nor the type of its weights,
+/// for instance to pick the right ones before loading the whole FST.
+#[derive(Debug, Clone)]
+pub struct BinaryFstHeader {
+    /// Type of the FST (e.g. `vector`, `const`, `compact_acceptor`).
+    pub fst_type: String,
+    /// Type of the transitions : `standard` for the tropical semiring,
+    /// `SerializableSemiring::weight_type` otherwise.
+    pub tr_type: String,
+    pub version: i32,
+    pub flags: FstFlags,
+    /// Properties stored in the file. They may not all be known.
+    pub properties: FstProperties,
+    pub start: Option<StateId>,
+    pub num_states: i64,
+    pub num_trs: i64,
+    /// Input symbol table along with its name.
+    pub isymt: Option<NamedSymbolTable>,
+    /// Output symbol table along with its name.
+    pub osymt: Option<NamedSymbolTable>,
+}
+
+fn optionally_parse_named_symt(
+    i: &[u8],
+    parse_symt: bool,
+) -> IResult<&[u8], Option<NamedSymbolTable>, NomCustomError<&[u8]>> {
+    if parse_symt {
+        let (i, (name, symt)) = parse_named_symbol_table_bin(i)?;
+        Ok((i, Some((name, Arc::new(symt)))))
+    } else {
+        Ok((i, None))
+    }
+}
+
+fn parse_binary_fst_header(i: &[u8]) -> IResult<&[u8], BinaryFstHeader, NomCustomError<&[u8]>> {
+    let (i, _magic_number) = verify(parse_bin_i32, |v: &i32| *v == FST_MAGIC_NUMBER)(i)?;
+    let (i, fst_type) = OpenFstString::parse(i)?;
+    let (i, tr_type) = OpenFstString::parse(i)?;
+    let (i, version) = parse_bin_i32(i)?;
+    let (i, flags) = map_res(parse_bin_u32, |v: u32| {
+        FstFlags::from_bits(v).ok_or("Could not parse Fst Flags")
+    })(i)?;
+    let (i, properties) = parse_bin_u64(i)?;
+    let (i, start) = parse_bin_i64(i)?;
+    let (i, num_states) = parse_bin_i64(i)?;
+    let (i, num_trs) = parse_bin_i64(i)?;
+    let (i, isymt) = optionally_parse_named_symt(i, flags.contains(FstFlags::HAS_ISYMBOLS))?;
+    let (i, osymt) = optionally_parse_named_symt(i, flags.contains(FstFlags::HAS_OSYMBOLS))?;
+
+    Ok((
+        i,
+        BinaryFstHeader {
+            fst_type: fst_type.into(),
+            tr_type: tr_type.into(),
+            version,
+            flags,
+            properties: FstProperties::from_bits_truncate(properties),
+            start: parse_start_state(start),
+            num_states,
+            num_trs,
+            isymt,
+            osymt,
+        },
+    ))
+}
+
+impl BinaryFstHeader {
+    /// Parses the header at the beginning of a binary FST.
+    pub fn load(data: &[u8]) -> Result<Self> {
+        let (_, hdr) = parse_binary_fst_header(data).map_err(|e| {
+            e.map(|e_inner| match e_inner {
+                NomCustomError::Nom(_, k) => {
+                    format_err!("Error while parsing binary FST header. Error kind {:?}", k)
+                }
+                NomCustomError::SymbolTableError(e) => format_err!(
+                    "Error while parsing symbolTable from binary FST header : {}",
+                    e
+                ),
+            })
+        })?;
+        Ok(hdr)
+    }
+
+    /// Reads the header of a binary FST file.
+    pub fn read<P: AsRef<Path>>(path_bin_fst: P) -> Result<Self> {
+        let data = std::fs::read(path_bin_fst.as_ref())
+            .with_context(|| format!("Can't open Fst binary file : {:?}", path_bin_fst.as_ref()))?;
+        Self::load(&data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fst_impls::{ConstFst, VectorFst};
+    use crate::fst_traits::{Fst, MutableFst, SerializableFst};
+    use crate::semirings::{LogWeight, Semiring};
+    use crate::Tr;
+
+    #[test]
+    fn test_binary_fst_header() -> Result<()> {
+        let mut fst = VectorFst::<LogWeight>::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.set_final(s1, LogWeight::one())?;
+        fst.add_tr(s0, Tr::new(1, 2, LogWeight::new(0.5), s1))?;
+        fst.set_input_symbols(Arc::new(SymbolTable::new()));
+
+        let mut data = vec![];
+        fst.store(&mut data)?;
+        let hdr = BinaryFstHeader::load(&data)?;
+        assert_eq!(hdr.fst_type, "vector");
+        assert_eq!(hdr.tr_type, "log");
+        assert_eq!(hdr.start, Some(s0));
+        assert_eq!(hdr.num_states, 2);
+        assert_eq!(hdr.num_trs, 1);
+        assert_eq!(
+            hdr.isymt.map(|(name, symt)| (name, symt.len())),
+            Some(("rustfst_symboltable".to_string(), 1))
+        );
+        assert!(hdr.osymt.is_none());
+
+        let const_fst: ConstFst<_> = fst.into();
+        let mut data = vec![];
+        const_fst.store(&mut data)?;
+        let hdr = BinaryFstHeader::load(&data)?;
+        assert_eq!(hdr.fst_type, "const");
+        assert_eq!(hdr.tr_type, "log");
+
+        assert!(BinaryFstHeader::load(&[0, 1, 2, 3]).is_err());
+        Ok(())
+    }
+}
@@ -22,8 +22,16 @@ fn parse_row_symt(i: &[u8]) -> IResult<&[u8], (i64, OpenFstString), NomCustomErr
 pub(crate) fn parse_symbol_table_bin(
     i: &[u8],
 ) -> IResult<&[u8], SymbolTable, NomCustomError<&[u8]>> {
+    let (i, (_name, symt)) = parse_named_symbol_table_bin(i)?;
+    Ok((i, symt))
+}
+
+/// Same as `parse_symbol_table_bin` but also returns the name of the table.
+pub(crate) fn parse_named_symbol_table_bin(
+    i: &[u8],
+) -> IResult<&[u8], (String, SymbolTable), NomCustomError<&[u8]>> {
     let (i, _magic_number) = verify(parse_bin_i32, |v| *v == SYMBOL_TABLE_MAGIC_NUMBER)(i)?;
-    let (i, _name) = OpenFstString::parse(i)?;
+    let (i, name) = OpenFstString::parse(i)?;
     let (i, _available_key) = parse_bin_i64(i)?;
     let (i, num_symbols) = parse_bin_i64(i)?;
     let (i, pairs_idx_symbols) = count(parse_row_symt, num_symbols as usize)(i)?;
@@ -38,7 +46,7 @@ pub(crate) fn parse_symbol_table_bin(
         }
     }
 
-    Ok((i, symt))
+    Ok((i, (name.into(), symt)))
 }
 
 pub(crate) fn write_bin_symt<W: Write, H: BuildHasher>(
@@ -1,4 +1,4 @@
-use nom::number::complete::{le_f32, le_i32, le_i64, le_u32, le_u64, le_u8};
+use nom::number::complete::{le_f32, le_f64, le_i32, le_i64, le_u32, le_u64, le_u8};
 use nom::IResult;
 
 use crate::parsers::nom_utils::NomCustomError;
@@ -28,6 +28,11 @@ pub fn parse_bin_f32(i: &[u8]) -> IResult<&[u8], f32, NomCustomError<&[u8]>> {
     le_f32(i)
 }
 
+#[inline]
+pub fn parse_bin_f64(i: &[u8]) -> IResult<&[u8], f64, NomCustomError<&[u8]>> {
+    le_f64(i)
+}
+
 #[inline]
 pub fn parse_bin_u8(i: &[u8]) -> IResult<&[u8], u8, NomCustomError<&[u8]>> {
     le_u8(i)
@@ -1,7 +1,19 @@
-use anyhow::Result;
-
-use crate::semirings::{CompleteSemiring, ReverseBack, Semiring, SemiringProperties, StarSemiring};
 use std::borrow::Borrow;
+use std::io::Write;
+
+use anyhow::{bail, Result};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::combinator::map;
+use nom::IResult;
+
+use crate::parsers::{parse_bin_u8, write_bin_u8};
+use crate::semirings::{
+    CompleteSemiring, DivideType, ReverseBack, Semiring, SemiringProperties, SerializableSemiring,
+    StarSemiring, WeaklyDivisibleSemiring, WeightQuantize,
+};
+use crate::NomCustomError;
+
 /// Boolean semiring: (&, |, false, true).
 #[derive(Clone, Debug, PartialEq, PartialOrd, Default, Eq, Copy, Hash)]
 pub struct BooleanWeight {
@@ -77,6 +89,49 @@ impl StarSemiring for BooleanWeight {
     }
 }
 
+impl WeaklyDivisibleSemiring for BooleanWeight {
+    fn divide_assign(&mut self, rhs: &Self, _divide_type: DivideType) -> Result<()> {
+        if !rhs.value {
+            bail!("BooleanWeight : division by zero");
+        }
+        Ok(())
+    }
+}
+
+impl WeightQuantize for BooleanWeight {
+    fn quantize_assign(&mut self, _delta: f32) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl SerializableSemiring for BooleanWeight {
+    fn weight_type() -> String {
+        "boolean".to_string()
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+        let (i, weight) = parse_bin_u8(i)?;
+        Ok((i, Self::new(weight != 0)))
+    }
+
+    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        write_bin_u8(file, self.value as u8)
+    }
+
+    fn parse_text(i: &str) -> IResult<&str, Self> {
+        alt((
+            map(tag("true"), |_| Self::new(true)),
+            map(tag("false"), |_| Self::new(false)),
+        ))(i)
+    }
+}
+
+test_semiring_serializable!(
+    tests_boolean_weight_serializable,
+    BooleanWeight,
+    BooleanWeight::one() BooleanWeight::zero()
+);
+
 impl From<bool> for BooleanWeight {
     fn from(b: bool) -> Self {
         Self::new(b)
@@ -103,6 +158,11 @@ mod tests {
         assert_eq!(b_true.times(b_false)?, b_false);
         assert_eq!(b_false.times(b_true)?, b_false);
         assert_eq!(b_false.times(b_false)?, b_false);
+
+        // Test divide
+        assert_eq!(b_true.divide(&b_true, DivideType::DivideAny)?, b_true);
+        assert_eq!(b_false.divide(&b_true, DivideType::DivideAny)?, b_false);
+        assert!(b_true.divide(&b_false, DivideType::DivideAny).is_err());
         Ok(())
     }
 }
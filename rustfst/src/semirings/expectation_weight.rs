@@ -0,0 +1,287 @@
+use std::borrow::Borrow;
+use std::fmt;
+use std::fmt::Debug;
+use std::io::Write;
+
+use anyhow::Result;
+use nom::IResult;
+
+use crate::parsers::nom_utils::NomCustomError;
+#[cfg(test)]
+use crate::semirings::LogWeight;
+use crate::semirings::{
+    DivideType, ReverseBack, Semiring, SemiringProperties, SerializableSemiring, SignedLogWeight,
+    WeaklyDivisibleSemiring, WeightQuantize,
+};
+
+/// Expectation semiring: (W1 x W2) with `(a, b) * (c, d) = (a * c, a * d + b * c)`.
+///
+/// Used to compute expectations over the paths of an FST : a path of probability `p` and
+/// value `v` is weighted by `(p, p * v)`, hence the sum over all the paths is the pair
+/// `(P, E)` with `E / P` the expected value. `W2` must be a module over `W1`, the product of
+/// a `W1` by a `W2` being computed in `W2` after conversion. Typically `W1` is `LogWeight`
+/// and `W2` is `SignedLogWeight`.
+#[derive(Debug, Eq, PartialOrd, PartialEq, Clone, Default, Hash)]
+pub struct ExpectationWeight<W1, W2>
+where
+    W1: Semiring,
+    W2: Semiring,
+{
+    pub(crate) weight: (W1, W2),
+}
+
+impl<W1, W2> AsRef<Self> for ExpectationWeight<W1, W2>
+where
+    W1: Semiring,
+    W2: Semiring,
+{
+    fn as_ref(&self) -> &ExpectationWeight<W1, W2> {
+        self
+    }
+}
+
+// The reverse of both weights must be themselves (which is the case of commutative semirings)
+// to avoid requiring the conversion from `W1` to `W2` on an unbounded chain of reverse weights.
+impl<W1, W2> Semiring for ExpectationWeight<W1, W2>
+where
+    W1: Semiring<ReverseWeight = W1>,
+    W2: Semiring<ReverseWeight = W2> + From<W1>,
+{
+    type Type = (W1, W2);
+    type ReverseWeight = ExpectationWeight<W1, W2>;
+
+    fn zero() -> Self {
+        Self {
+            weight: (W1::zero(), W2::zero()),
+        }
+    }
+
+    fn one() -> Self {
+        Self {
+            weight: (W1::one(), W2::zero()),
+        }
+    }
+
+    fn new(weight: <Self as Semiring>::Type) -> Self {
+        Self { weight }
+    }
+
+    fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        self.weight.0.plus_assign(&rhs.borrow().weight.0)?;
+        self.weight.1.plus_assign(&rhs.borrow().weight.1)?;
+        Ok(())
+    }
+
+    fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        let (c, d) = &rhs.borrow().weight;
+        let mut value2 = W2::from(self.weight.0.clone()).times(d)?;
+        value2.plus_assign(self.weight.1.times(W2::from(c.clone()))?)?;
+        self.weight.0.times_assign(c)?;
+        self.weight.1 = value2;
+        Ok(())
+    }
+
+    fn approx_equal<P: Borrow<Self>>(&self, rhs: P, delta: f32) -> bool {
+        self.value1().approx_equal(rhs.borrow().value1(), delta)
+            && self.value2().approx_equal(rhs.borrow().value2(), delta)
+    }
+
+    fn value(&self) -> &<Self as Semiring>::Type {
+        &self.weight
+    }
+
+    fn take_value(self) -> <Self as Semiring>::Type {
+        self.weight
+    }
+
+    fn set_value(&mut self, value: <Self as Semiring>::Type) {
+        self.set_value1(value.0);
+        self.set_value2(value.1);
+    }
+
+    fn reverse(&self) -> Result<Self::ReverseWeight> {
+        Ok((self.value1().reverse()?, self.value2().reverse()?).into())
+    }
+
+    fn properties() -> SemiringProperties {
+        W1::properties()
+            & W2::properties()
+            & (SemiringProperties::LEFT_SEMIRING
+                | SemiringProperties::RIGHT_SEMIRING
+                | SemiringProperties::COMMUTATIVE)
+    }
+}
+
+impl<W1, W2> ReverseBack<ExpectationWeight<W1, W2>> for ExpectationWeight<W1, W2>
+where
+    W1: Semiring<ReverseWeight = W1>,
+    W2: Semiring<ReverseWeight = W2> + From<W1>,
+{
+    fn reverse_back(&self) -> Result<ExpectationWeight<W1, W2>> {
+        Ok((self.value1().reverse()?, self.value2().reverse()?).into())
+    }
+}
+
+impl<W1, W2> ExpectationWeight<W1, W2>
+where
+    W1: Semiring,
+    W2: Semiring,
+{
+    pub fn value1(&self) -> &W1 {
+        &self.weight.0
+    }
+
+    pub fn value2(&self) -> &W2 {
+        &self.weight.1
+    }
+
+    pub fn set_value1(&mut self, new_weight: W1) {
+        self.weight.0 = new_weight;
+    }
+
+    pub fn set_value2(&mut self, new_weight: W2) {
+        self.weight.1 = new_weight;
+    }
+}
+
+impl<W1, W2> From<(W1, W2)> for ExpectationWeight<W1, W2>
+where
+    W1: Semiring,
+    W2: Semiring,
+{
+    fn from(t: (W1, W2)) -> Self {
+        Self { weight: t }
+    }
+}
+
+impl<W1> WeaklyDivisibleSemiring for ExpectationWeight<W1, SignedLogWeight>
+where
+    W1: WeaklyDivisibleSemiring<ReverseWeight = W1>,
+    SignedLogWeight: From<W1>,
+{
+    /// `(a, b) / (c, d) = (a / c, (b - d * a / c) / c)`.
+    fn divide_assign(&mut self, rhs: &Self, divide_type: DivideType) -> Result<()> {
+        let (c, d) = &rhs.weight;
+        self.weight.0.divide_assign(c, divide_type)?;
+        let x = SignedLogWeight::from(self.weight.0.clone());
+        self.weight.1.plus_assign(d.times(x)?.negate())?;
+        self.weight
+            .1
+            .divide_assign(&SignedLogWeight::from(c.clone()), divide_type)?;
+        Ok(())
+    }
+}
+
+impl<W1, W2> WeightQuantize for ExpectationWeight<W1, W2>
+where
+    W1: WeightQuantize<ReverseWeight = W1>,
+    W2: WeightQuantize<ReverseWeight = W2> + From<W1>,
+{
+    fn quantize_assign(&mut self, delta: f32) -> Result<()> {
+        self.set_value1(self.value1().quantize(delta)?);
+        self.set_value2(self.value2().quantize(delta)?);
+        Ok(())
+    }
+}
+
+impl<W1, W2> fmt::Display for ExpectationWeight<W1, W2>
+where
+    W1: SerializableSemiring,
+    W2: SerializableSemiring,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.value1(), self.value2())?;
+        Ok(())
+    }
+}
+
+impl<W1, W2> SerializableSemiring for ExpectationWeight<W1, W2>
+where
+    W1: SerializableSemiring<ReverseWeight = W1>,
+    W2: SerializableSemiring<ReverseWeight = W2> + From<W1>,
+{
+    fn weight_type() -> String {
+        format!("expectation_{}_{}", W1::weight_type(), W2::weight_type())
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+        let (i, weight_1) = W1::parse_binary(i)?;
+        let (i, weight_2) = W2::parse_binary(i)?;
+        Ok((i, Self::new((weight_1, weight_2))))
+    }
+
+    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        self.value1().write_binary(file)?;
+        self.value2().write_binary(file)?;
+        Ok(())
+    }
+
+    fn parse_text(i: &str) -> IResult<&str, Self> {
+        let (i, weight_1) = W1::parse_text(i)?;
+        let (i, _) = nom::bytes::complete::tag(",")(i)?;
+        let (i, weight_2) = W2::parse_text(i)?;
+        Ok((i, Self::new((weight_1, weight_2))))
+    }
+}
+
+test_semiring_serializable!(
+    tests_expectation_weight_serializable,
+    ExpectationWeight::<LogWeight, SignedLogWeight>,
+    ExpectationWeight::new((LogWeight::new(0.2), SignedLogWeight::one()))
+    ExpectationWeight::<LogWeight, SignedLogWeight>::zero()
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::shortest_distance;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::MutableFst;
+    use crate::semirings::TropicalWeight;
+    use crate::Tr;
+
+    type ExpWeight = ExpectationWeight<LogWeight, SignedLogWeight>;
+
+    fn slw(x: f32) -> SignedLogWeight {
+        let sign = TropicalWeight::new(if x >= 0.0 { 1.0 } else { -1.0 });
+        SignedLogWeight::new((sign, LogWeight::new(-x.abs().ln())))
+    }
+
+    // Weight of an event of probability `p` and value `v`.
+    fn ew(p: f32, v: f32) -> ExpWeight {
+        ExpectationWeight::new((LogWeight::new(-p.ln()), slw(p * v)))
+    }
+
+    #[test]
+    fn test_expectation_weight_times_divide() -> Result<()> {
+        let w = ew(0.5, 2.0).times(ew(0.25, -3.0))?;
+        // Probability 0.125 and value 2 - 3.
+        assert!(w.approx_equal(ew(0.125, -1.0), 1e-5));
+        assert!(w.times(ExpWeight::one())?.approx_equal(&w, 1e-5));
+        assert!(w.times(ExpWeight::zero())?.is_zero());
+        assert!(w
+            .divide(&ew(0.25, -3.0), DivideType::DivideAny)?
+            .approx_equal(ew(0.5, 2.0), 1e-5));
+        Ok(())
+    }
+
+    #[test]
+    fn test_expectation_weight_shortest_distance() -> Result<()> {
+        // Three paths : probabilities 0.3, 0.2 and 0.5 with values 1, 2 and 3.
+        let mut fst: VectorFst<ExpWeight> = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, ew(0.5, 1.0), 1))?;
+        fst.add_tr(1, Tr::new(2, 2, ew(0.6, 0.0), 2))?;
+        fst.add_tr(1, Tr::new(3, 3, ew(0.4, 1.0), 2))?;
+        fst.add_tr(0, Tr::new(4, 4, ew(0.5, 3.0), 2))?;
+        fst.set_final(2, ExpWeight::one())?;
+
+        let distance = shortest_distance(&fst, true)?;
+        let total = &distance[0];
+        assert!(total.value1().approx_equal(LogWeight::one(), 1e-5));
+        let expectation = 0.3 * 1.0 + 0.2 * 2.0 + 0.5 * 3.0;
+        assert!(total.value2().approx_equal(slw(expectation), 1e-5));
+        Ok(())
+    }
+}
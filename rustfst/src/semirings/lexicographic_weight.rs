@@ -0,0 +1,305 @@
+use std::borrow::Borrow;
+use std::fmt;
+use std::fmt::Debug;
+use std::io::Write;
+
+use anyhow::Result;
+use nom::IResult;
+
+use crate::parsers::nom_utils::NomCustomError;
+use crate::semirings::{
+    DivideType, ReverseBack, Semiring, SemiringProperties, SerializableSemiring,
+    WeaklyDivisibleSemiring, WeightQuantize,
+};
+#[cfg(test)]
+use crate::semirings::{LogWeight, TropicalWeight};
+
+/// Lexicographic semiring: W1 x W2.
+///
+/// `Plus` returns the lesser of the two weights according to the natural order of `W1`
+/// and, in case of a tie, the natural order of `W2`. `Times` is computed component-wise.
+/// Both `W1` and `W2` must have the `PATH` property for this to be a semiring.
+#[derive(Debug, Eq, PartialOrd, PartialEq, Clone, Default, Hash)]
+pub struct LexicographicWeight<W1, W2>
+where
+    W1: Semiring,
+    W2: Semiring,
+{
+    pub(crate) weight: (W1, W2),
+}
+
+/// Natural order of a semiring with the `PATH` property : `w1 < w2` iff `w1 + w2 = w1 != w2`.
+fn natural_less<W: Semiring>(w1: &W, w2: &W) -> Result<bool> {
+    Ok(w1 != w2 && &w1.plus(w2)? == w1)
+}
+
+impl<W1, W2> AsRef<Self> for LexicographicWeight<W1, W2>
+where
+    W1: Semiring,
+    W2: Semiring,
+{
+    fn as_ref(&self) -> &LexicographicWeight<W1, W2> {
+        self
+    }
+}
+
+impl<W1, W2> Semiring for LexicographicWeight<W1, W2>
+where
+    W1: Semiring,
+    W2: Semiring,
+{
+    type Type = (W1, W2);
+    type ReverseWeight = LexicographicWeight<W1::ReverseWeight, W2::ReverseWeight>;
+
+    fn zero() -> Self {
+        Self {
+            weight: (W1::zero(), W2::zero()),
+        }
+    }
+
+    fn one() -> Self {
+        Self {
+            weight: (W1::one(), W2::one()),
+        }
+    }
+
+    fn new(weight: <Self as Semiring>::Type) -> Self {
+        Self { weight }
+    }
+
+    fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        let rhs = rhs.borrow();
+        let rhs_is_less = if natural_less(rhs.value1(), self.value1())? {
+            true
+        } else if natural_less(self.value1(), rhs.value1())? {
+            false
+        } else {
+            natural_less(rhs.value2(), self.value2())?
+        };
+        if rhs_is_less {
+            self.weight = rhs.weight.clone();
+        }
+        Ok(())
+    }
+
+    fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        self.weight.0.times_assign(&rhs.borrow().weight.0)?;
+        self.weight.1.times_assign(&rhs.borrow().weight.1)?;
+        Ok(())
+    }
+
+    fn approx_equal<P: Borrow<Self>>(&self, rhs: P, delta: f32) -> bool {
+        self.value1().approx_equal(rhs.borrow().value1(), delta)
+            && self.value2().approx_equal(rhs.borrow().value2(), delta)
+    }
+
+    fn value(&self) -> &<Self as Semiring>::Type {
+        &self.weight
+    }
+
+    fn take_value(self) -> <Self as Semiring>::Type {
+        self.weight
+    }
+
+    fn set_value(&mut self, value: <Self as Semiring>::Type) {
+        self.set_value1(value.0);
+        self.set_value2(value.1);
+    }
+
+    fn reverse(&self) -> Result<Self::ReverseWeight> {
+        Ok((self.value1().reverse()?, self.value2().reverse()?).into())
+    }
+
+    fn properties() -> SemiringProperties {
+        W1::properties()
+            & W2::properties()
+            & (SemiringProperties::LEFT_SEMIRING
+                | SemiringProperties::RIGHT_SEMIRING
+                | SemiringProperties::PATH
+                | SemiringProperties::IDEMPOTENT
+                | SemiringProperties::COMMUTATIVE)
+    }
+}
+
+impl<W1: Semiring, W2: Semiring> ReverseBack<LexicographicWeight<W1, W2>>
+    for <LexicographicWeight<W1, W2> as Semiring>::ReverseWeight
+{
+    fn reverse_back(&self) -> Result<LexicographicWeight<W1, W2>> {
+        Ok((self.value1().reverse_back()?, self.value2().reverse_back()?).into())
+    }
+}
+
+impl<W1, W2> LexicographicWeight<W1, W2>
+where
+    W1: Semiring,
+    W2: Semiring,
+{
+    pub fn value1(&self) -> &W1 {
+        &self.weight.0
+    }
+
+    pub fn value2(&self) -> &W2 {
+        &self.weight.1
+    }
+
+    pub fn set_value1(&mut self, new_weight: W1) {
+        self.weight.0 = new_weight;
+    }
+
+    pub fn set_value2(&mut self, new_weight: W2) {
+        self.weight.1 = new_weight;
+    }
+}
+
+impl<W1, W2> From<(W1, W2)> for LexicographicWeight<W1, W2>
+where
+    W1: Semiring,
+    W2: Semiring,
+{
+    fn from(t: (W1, W2)) -> Self {
+        Self::new(t)
+    }
+}
+
+impl<W1, W2> WeaklyDivisibleSemiring for LexicographicWeight<W1, W2>
+where
+    W1: WeaklyDivisibleSemiring,
+    W2: WeaklyDivisibleSemiring,
+{
+    fn divide_assign(&mut self, rhs: &Self, divide_type: DivideType) -> Result<()> {
+        self.weight.0.divide_assign(&rhs.weight.0, divide_type)?;
+        self.weight.1.divide_assign(&rhs.weight.1, divide_type)?;
+        Ok(())
+    }
+}
+
+impl<W1, W2> WeightQuantize for LexicographicWeight<W1, W2>
+where
+    W1: WeightQuantize,
+    W2: WeightQuantize,
+{
+    fn quantize_assign(&mut self, delta: f32) -> Result<()> {
+        self.set_value1(self.value1().quantize(delta)?);
+        self.set_value2(self.value2().quantize(delta)?);
+        Ok(())
+    }
+}
+
+impl<W1, W2> fmt::Display for LexicographicWeight<W1, W2>
+where
+    W1: SerializableSemiring,
+    W2: SerializableSemiring,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.value1(), self.value2())?;
+        Ok(())
+    }
+}
+
+impl<W1, W2> SerializableSemiring for LexicographicWeight<W1, W2>
+where
+    W1: SerializableSemiring,
+    W2: SerializableSemiring,
+{
+    fn weight_type() -> String {
+        format!("{}_LT_{}", W1::weight_type(), W2::weight_type())
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+        let (i, weight_1) = W1::parse_binary(i)?;
+        let (i, weight_2) = W2::parse_binary(i)?;
+        Ok((i, Self::new((weight_1, weight_2))))
+    }
+
+    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        self.value1().write_binary(file)?;
+        self.value2().write_binary(file)?;
+        Ok(())
+    }
+
+    fn parse_text(i: &str) -> IResult<&str, Self> {
+        let (i, weight_1) = W1::parse_text(i)?;
+        let (i, _) = nom::bytes::complete::tag(",")(i)?;
+        let (i, weight_2) = W2::parse_text(i)?;
+        Ok((i, Self::new((weight_1, weight_2))))
+    }
+}
+
+test_semiring_serializable!(
+    tests_lexicographic_weight_serializable,
+    LexicographicWeight::<TropicalWeight, LogWeight>,
+    LexicographicWeight::new((TropicalWeight::new(0.2), LogWeight::new(1.7)))
+    LexicographicWeight::new((TropicalWeight::zero(), LogWeight::one()))
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::determinize::determinize;
+    use crate::algorithms::{minimize, shortest_path};
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{CoreFst, ExpandedFst, Fst, MutableFst};
+    use crate::{Tr, Trs};
+
+    type LexWeight = LexicographicWeight<TropicalWeight, TropicalWeight>;
+
+    fn lw(errors: f32, cost: f32) -> LexWeight {
+        LexicographicWeight::new((TropicalWeight::new(errors), TropicalWeight::new(cost)))
+    }
+
+    #[test]
+    fn test_lexicographic_weight_plus() -> Result<()> {
+        assert_eq!(lw(1.0, 5.0).plus(lw(2.0, 0.5))?, lw(1.0, 5.0));
+        assert_eq!(lw(2.0, 0.5).plus(lw(1.0, 5.0))?, lw(1.0, 5.0));
+        assert_eq!(lw(1.0, 5.0).plus(lw(1.0, 0.5))?, lw(1.0, 0.5));
+        assert_eq!(lw(1.0, 5.0).plus(LexWeight::zero())?, lw(1.0, 5.0));
+        assert!(LexWeight::properties().contains(SemiringProperties::PATH));
+        Ok(())
+    }
+
+    #[test]
+    fn test_lexicographic_weight_times_divide() -> Result<()> {
+        let w = lw(1.0, 5.0).times(lw(2.0, 0.5))?;
+        assert_eq!(w, lw(3.0, 5.5));
+        assert_eq!(
+            w.divide(&lw(2.0, 0.5), DivideType::DivideAny)?,
+            lw(1.0, 5.0)
+        );
+        assert!(LexWeight::zero().times(lw(1.0, 1.0))?.is_zero());
+        Ok(())
+    }
+
+    #[test]
+    fn test_lexicographic_weight_type() {
+        assert_eq!(
+            LexicographicWeight::<TropicalWeight, LogWeight>::weight_type(),
+            "tropical_LT_log"
+        );
+    }
+
+    #[test]
+    fn test_lexicographic_weight_algorithms() -> Result<()> {
+        // Two paths accepting "1 2" : one with fewer errors, one with a lower cost.
+        let mut fst: VectorFst<LexWeight> = VectorFst::new();
+        fst.add_states(5);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, lw(0.0, 3.0), 1))?;
+        fst.add_tr(0, Tr::new(1, 1, lw(1.0, 0.5), 2))?;
+        fst.add_tr(1, Tr::new(2, 2, lw(1.0, 2.0), 3))?;
+        fst.add_tr(2, Tr::new(2, 2, lw(1.0, 0.0), 4))?;
+        fst.set_final(3, LexWeight::one())?;
+        fst.set_final(4, LexWeight::one())?;
+
+        let best: VectorFst<_> = shortest_path(&fst)?;
+        let start = best.start().unwrap();
+        assert_eq!(best.get_trs(start)?.trs()[0].weight, lw(0.0, 3.0));
+
+        let mut det: VectorFst<_> = determinize(&fst)?;
+        minimize(&mut det)?;
+        assert_eq!(det.num_states(), 3);
+        let paths = det.paths_iter().collect::<Vec<_>>();
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].weight, lw(1.0, 5.0));
+        Ok(())
+    }
+}
@@ -23,6 +23,7 @@ use crate::KDELTA;
 
 /// Log semiring: (log(e^-x + e^-y), +, inf, 0).
 #[derive(Clone, Debug, PartialOrd, Default, Copy, Eq)]
+#[repr(transparent)]
 pub struct LogWeight {
     value: OrderedFloat<f32>,
 }
@@ -0,0 +1,194 @@
+use std::borrow::Borrow;
+use std::f64;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+
+use anyhow::Result;
+use nom::branch::alt;
+use nom::bytes::complete::tag_no_case;
+use nom::combinator::map;
+use nom::number::complete::double;
+use nom::IResult;
+use ordered_float::OrderedFloat;
+
+use crate::parsers::nom_utils::NomCustomError;
+use crate::parsers::parse_bin_f64;
+use crate::parsers::write_bin_f64;
+use crate::semirings::utils_float::float64_approx_equal;
+use crate::semirings::{
+    CompleteSemiring, DivideType, ReverseBack, Semiring, SemiringProperties, SerializableSemiring,
+    StarSemiring, WeaklyDivisibleSemiring, WeightQuantize,
+};
+use crate::KDELTA;
+
+/// Log semiring with double precision: (log(e^-x + e^-y), +, inf, 0).
+#[derive(Clone, Debug, PartialOrd, Default, Copy, Eq)]
+#[repr(transparent)]
+pub struct LogWeight64 {
+    value: OrderedFloat<f64>,
+}
+
+fn ln_pos_exp(x: f64) -> f64 {
+    ((-x).exp()).ln_1p()
+}
+
+impl Semiring for LogWeight64 {
+    type Type = f64;
+    type ReverseWeight = LogWeight64;
+
+    fn zero() -> Self {
+        Self {
+            value: OrderedFloat(f64::INFINITY),
+        }
+    }
+    fn one() -> Self {
+        Self {
+            value: OrderedFloat(0.0),
+        }
+    }
+
+    fn new(value: <Self as Semiring>::Type) -> Self {
+        LogWeight64 {
+            value: OrderedFloat(value),
+        }
+    }
+
+    fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        let f1 = self.value();
+        let f2 = rhs.borrow().value();
+        self.value.0 = if f1.eq(&f64::INFINITY) {
+            *f2
+        } else if f2.eq(&f64::INFINITY) {
+            *f1
+        } else if f1 > f2 {
+            f2 - ln_pos_exp(f1 - f2)
+        } else {
+            f1 - ln_pos_exp(f2 - f1)
+        };
+        Ok(())
+    }
+
+    fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        let f1 = self.value();
+        let f2 = rhs.borrow().value();
+        if f1.eq(&f64::INFINITY) {
+        } else if f2.eq(&f64::INFINITY) {
+            self.value.0 = *f2;
+        } else {
+            self.value.0 += f2;
+        }
+        Ok(())
+    }
+
+    fn approx_equal<P: Borrow<Self>>(&self, rhs: P, delta: f32) -> bool {
+        float64_approx_equal(self.value.0, rhs.borrow().value.0, delta)
+    }
+
+    fn value(&self) -> &Self::Type {
+        self.value.as_ref()
+    }
+
+    fn take_value(self) -> Self::Type {
+        self.value.into_inner()
+    }
+
+    fn set_value(&mut self, value: <Self as Semiring>::Type) {
+        self.value.0 = value
+    }
+
+    fn reverse(&self) -> Result<Self::ReverseWeight> {
+        Ok(*self)
+    }
+
+    fn properties() -> SemiringProperties {
+        SemiringProperties::LEFT_SEMIRING
+            | SemiringProperties::RIGHT_SEMIRING
+            | SemiringProperties::COMMUTATIVE
+    }
+}
+
+impl ReverseBack<LogWeight64> for LogWeight64 {
+    fn reverse_back(&self) -> Result<LogWeight64> {
+        Ok(*self)
+    }
+}
+
+impl AsRef<LogWeight64> for LogWeight64 {
+    fn as_ref(&self) -> &LogWeight64 {
+        self
+    }
+}
+
+display_semiring!(LogWeight64);
+
+impl CompleteSemiring for LogWeight64 {}
+
+impl StarSemiring for LogWeight64 {
+    fn closure(&self) -> Self {
+        if self.value.0 >= 0.0 && self.value.0 < 1.0 {
+            Self::new((1.0 - self.value.0).ln())
+        } else {
+            Self::new(f64::NEG_INFINITY)
+        }
+    }
+}
+
+impl WeaklyDivisibleSemiring for LogWeight64 {
+    fn divide_assign(&mut self, rhs: &Self, _divide_type: DivideType) -> Result<()> {
+        self.value.0 -= rhs.value.0;
+        Ok(())
+    }
+}
+
+impl_quantize_f64!(LogWeight64);
+
+partial_eq_and_hash_f64!(LogWeight64);
+
+impl SerializableSemiring for LogWeight64 {
+    fn weight_type() -> String {
+        "log64".to_string()
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+        let (i, weight) = parse_bin_f64(i)?;
+        Ok((i, Self::new(weight)))
+    }
+
+    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        write_bin_f64(file, *self.value())
+    }
+
+    fn parse_text(i: &str) -> IResult<&str, Self> {
+        // Same workaround as for `LogWeight` : "infinity" is parsed manually.
+        let (i, f) = alt((map(tag_no_case("infinity"), |_| f64::INFINITY), double))(i)?;
+        Ok((i, Self::new(f)))
+    }
+}
+
+test_semiring_serializable!(
+    tests_log_weight64_serializable,
+    LogWeight64,
+    LogWeight64::new(0.3) LogWeight64::new(0.5) LogWeight64::new(0.0) LogWeight64::new(-1.2)
+);
+
+impl From<f64> for LogWeight64 {
+    fn from(f: f64) -> Self {
+        LogWeight64::new(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_weight64_precision() -> Result<()> {
+        // Not representable with a f32.
+        let w = LogWeight64::new(1e8).times(LogWeight64::new(1e-3))?;
+        assert_eq!(*w.value(), 1e8 + 1e-3);
+
+        let w = LogWeight64::new(1.0).plus(LogWeight64::new(1.0))?;
+        assert!(w.approx_equal(LogWeight64::new(1.0 - 2f64.ln()), 1e-12));
+        Ok(())
+    }
+}
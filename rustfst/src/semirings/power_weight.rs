@@ -1,21 +1,31 @@
-#![allow(unused)]
-
+use std::borrow::Borrow;
 use std::cmp::Ordering;
+use std::convert::TryFrom;
 use std::fmt;
-use std::fmt::Debug;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::io::Write;
 
 use anyhow::Result;
-
+use generic_array::sequence::GenericSequence;
 use generic_array::ArrayLength;
 use generic_array::GenericArray;
+use nom::bytes::complete::tag;
+use nom::multi::count;
+use nom::IResult;
 
+use crate::parsers::nom_utils::NomCustomError;
 use crate::semirings::{
-    DivideType, Semiring, SemiringProperties, WeaklyDivisibleSemiring, WeightQuantize,
+    DivideType, ReverseBack, Semiring, SemiringProperties, SerializableSemiring,
+    WeaklyDivisibleSemiring, WeightQuantize,
 };
+#[cfg(test)]
+use crate::semirings::{LogWeight, TropicalWeight};
 
 /// Cartesian power semiring: W ^ n.
+///
+/// Operations are computed component-wise. `N` is a `typenum` unsigned integer,
+/// eg. `PowerWeight<TropicalWeight, typenum::U3>`.
 pub struct PowerWeight<W, N>
 where
     W: Semiring,
@@ -26,11 +36,17 @@ where
 
 impl<W, N> fmt::Display for PowerWeight<W, N>
 where
-    W: Semiring,
+    W: SerializableSemiring,
     N: ArrayLength<W>,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.weights.as_slice().fmt(f)
+        for (idx, w) in self.weights.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", w)?;
+        }
+        Ok(())
     }
 }
 
@@ -103,92 +119,271 @@ where
 {
 }
 
-//impl<W, N> Semiring for PowerWeight<W, N>
-//where
-//    W: Semiring,
-//    N: ArrayLength<W>,
-//{
-//    type Type = GenericArray<W, N>;
-//    type ReverseSemiring<P> = PowerWeight<W::ReverseSemiring, P>;
-//
-//    fn zero() -> Self {
-//        Self {
-//            weights: GenericArray::clone_from_slice(&[W::zero()]),
-//        }
-//    }
-//
-//    fn one() -> Self {
-//        Self {
-//            weights: GenericArray::clone_from_slice(&[W::one()]),
-//        }
-//    }
-//
-//    fn new(value: <Self as Semiring>::Type) -> Self {
-//        Self { weights: value }
-//    }
-//
-//    fn plus_assign<P: AsRef<Self>>(&mut self, rhs: P) -> Result<()> {
-//        for i in 0..self.weights.len() {
-//            self.weights[i].plus_assign(&rhs.as_ref().weights[i])?;
-//        }
-//        Ok(())
-//    }
-//
-//    fn times_assign<P: AsRef<Self>>(&mut self, rhs: P) -> Result<()> {
-//        for i in 0..self.weights.len() {
-//            self.weights[i].times_assign(&rhs.as_ref().weights[i])?;
-//        }
-//        Ok(())
-//    }
-//
-//    fn value(&self) -> <Self as Semiring>::Type {
-//        self.weights.clone()
-//    }
-//
-//    fn set_value(&mut self, value: <Self as Semiring>::Type) {
-//        self.weights = value;
-//    }
-//
-//    fn reverse(&self) -> Self::ReverseSemiring {
-//        let mut rw = Vec::with_capacity(self.weights.len());
-//        for i in 0..self.weights.len() {
-//            rw.push(self.weights[i].reverse());
-//        }
-//        PowerWeight::new(GenericArray::clone_from(rw))
-//    }
-//
-//    fn properties() -> SemiringProperties {
-//        W::properties()
-//            & (SemiringProperties::LEFT_SEMIRING
-//                | SemiringProperties::RIGHT_SEMIRING
-//                | SemiringProperties::COMMUTATIVE
-//                | SemiringProperties::IDEMPOTENT)
-//    }
-//}
-//
-//impl<W, N> WeaklyDivisibleSemiring for PowerWeight<W, N>
-//where
-//    W: WeaklyDivisibleSemiring,
-//    N: ArrayLength<W>,
-//{
-//    fn divide(&self, rhs: &Self, divide_type: DivideType) -> Result<Self> {
-//        let mut mul = self.clone();
-//        for i in 0..self.weights.len() {
-//            mul.weights[i] = self.weights[i].divide(&rhs.weights[i], divide_type)?;
-//        }
-//        Ok(mul)
-//    }
-//}
-//
-//impl<W, N> WeightQuantize for PowerWeight<W, N>
-//where
-//    W: WeightQuantize,
-//    N: ArrayLength<W>,
-//{
-//    fn quantize_assign(&mut self, delta: f32) -> Result<()> {
-//        for i in 0..self.weights.len() {
-//            unsafe { self.weights.get_unchecked_mut(i).quantize_assign(delta)? };
-//        }
-//        Ok(())
-//    }
-//}
+impl<W, N> PowerWeight<W, N>
+where
+    W: Semiring,
+    N: ArrayLength<W>,
+{
+    /// Weight of the component `idx`.
+    pub fn value_at(&self, idx: usize) -> Option<&W> {
+        self.weights.get(idx)
+    }
+
+    pub fn set_value_at(&mut self, idx: usize, weight: W) -> Result<()> {
+        let w = self.weights.get_mut(idx).ok_or_else(|| {
+            format_err!(
+                "Index {} out of bounds for PowerWeight of size {}",
+                idx,
+                N::to_usize()
+            )
+        })?;
+        *w = weight;
+        Ok(())
+    }
+}
+
+impl<W, N> TryFrom<Vec<W>> for PowerWeight<W, N>
+where
+    W: Semiring,
+    N: ArrayLength<W>,
+{
+    type Error = anyhow::Error;
+
+    /// Fails if the length of the vector is different from `N`.
+    fn try_from(weights: Vec<W>) -> Result<Self> {
+        if weights.len() != N::to_usize() {
+            bail!(
+                "Can't build a PowerWeight of size {} from {} weights",
+                N::to_usize(),
+                weights.len()
+            );
+        }
+        Ok(Self {
+            weights: GenericArray::clone_from_slice(&weights),
+        })
+    }
+}
+
+// `N` must be able to store both the weights and their reverse. The reverse of the reverse
+// weight is expected to be the weight itself for the bounds on `ReverseWeight` to be satisfied.
+impl<W, N> Semiring for PowerWeight<W, N>
+where
+    W: Semiring,
+    W::ReverseWeight: Semiring<ReverseWeight = W>,
+    N: ArrayLength<W> + ArrayLength<W::ReverseWeight> + 'static,
+{
+    type Type = GenericArray<W, N>;
+    type ReverseWeight = PowerWeight<W::ReverseWeight, N>;
+
+    fn zero() -> Self {
+        Self {
+            weights: GenericArray::generate(|_| W::zero()),
+        }
+    }
+
+    fn one() -> Self {
+        Self {
+            weights: GenericArray::generate(|_| W::one()),
+        }
+    }
+
+    fn new(value: <Self as Semiring>::Type) -> Self {
+        Self { weights: value }
+    }
+
+    fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        for (w, w_rhs) in self.weights.iter_mut().zip(rhs.borrow().weights.iter()) {
+            w.plus_assign(w_rhs)?;
+        }
+        Ok(())
+    }
+
+    fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        for (w, w_rhs) in self.weights.iter_mut().zip(rhs.borrow().weights.iter()) {
+            w.times_assign(w_rhs)?;
+        }
+        Ok(())
+    }
+
+    fn approx_equal<P: Borrow<Self>>(&self, rhs: P, delta: f32) -> bool {
+        self.weights
+            .iter()
+            .zip(rhs.borrow().weights.iter())
+            .all(|(w, w_rhs)| w.approx_equal(w_rhs, delta))
+    }
+
+    fn value(&self) -> &<Self as Semiring>::Type {
+        &self.weights
+    }
+
+    fn take_value(self) -> <Self as Semiring>::Type {
+        self.weights
+    }
+
+    fn set_value(&mut self, value: <Self as Semiring>::Type) {
+        self.weights = value;
+    }
+
+    fn reverse(&self) -> Result<Self::ReverseWeight> {
+        let weights = self
+            .weights
+            .iter()
+            .map(|w| w.reverse())
+            .collect::<Result<Vec<_>>>()?;
+        Ok(PowerWeight {
+            weights: GenericArray::clone_from_slice(&weights),
+        })
+    }
+
+    fn properties() -> SemiringProperties {
+        W::properties()
+            & (SemiringProperties::LEFT_SEMIRING
+                | SemiringProperties::RIGHT_SEMIRING
+                | SemiringProperties::COMMUTATIVE
+                | SemiringProperties::IDEMPOTENT)
+    }
+}
+
+impl<W, N> ReverseBack<PowerWeight<W, N>> for PowerWeight<W::ReverseWeight, N>
+where
+    W: Semiring,
+    W::ReverseWeight: Semiring<ReverseWeight = W>,
+    N: ArrayLength<W> + ArrayLength<W::ReverseWeight> + 'static,
+{
+    fn reverse_back(&self) -> Result<PowerWeight<W, N>> {
+        let weights = self
+            .weights
+            .iter()
+            .map(|w| w.reverse_back())
+            .collect::<Result<Vec<_>>>()?;
+        Ok(PowerWeight {
+            weights: GenericArray::clone_from_slice(&weights),
+        })
+    }
+}
+
+impl<W, N> WeaklyDivisibleSemiring for PowerWeight<W, N>
+where
+    W: WeaklyDivisibleSemiring,
+    W::ReverseWeight: Semiring<ReverseWeight = W>,
+    N: ArrayLength<W> + ArrayLength<W::ReverseWeight> + 'static,
+{
+    fn divide_assign(&mut self, rhs: &Self, divide_type: DivideType) -> Result<()> {
+        for (w, w_rhs) in self.weights.iter_mut().zip(rhs.weights.iter()) {
+            w.divide_assign(w_rhs, divide_type)?;
+        }
+        Ok(())
+    }
+}
+
+impl<W, N> WeightQuantize for PowerWeight<W, N>
+where
+    W: WeightQuantize,
+    W::ReverseWeight: Semiring<ReverseWeight = W>,
+    N: ArrayLength<W> + ArrayLength<W::ReverseWeight> + 'static,
+{
+    fn quantize_assign(&mut self, delta: f32) -> Result<()> {
+        for w in self.weights.iter_mut() {
+            w.quantize_assign(delta)?;
+        }
+        Ok(())
+    }
+}
+
+impl<W, N> SerializableSemiring for PowerWeight<W, N>
+where
+    W: SerializableSemiring,
+    W::ReverseWeight: Semiring<ReverseWeight = W>,
+    N: ArrayLength<W> + ArrayLength<W::ReverseWeight> + 'static,
+{
+    fn weight_type() -> String {
+        format!("{}_^{}", W::weight_type(), N::to_usize())
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+        let (i, weights) = count(W::parse_binary, N::to_usize())(i)?;
+        Ok((
+            i,
+            Self {
+                weights: GenericArray::clone_from_slice(&weights),
+            },
+        ))
+    }
+
+    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        for w in self.weights.iter() {
+            w.write_binary(file)?;
+        }
+        Ok(())
+    }
+
+    fn parse_text(i: &str) -> IResult<&str, Self> {
+        let mut weights = Vec::with_capacity(N::to_usize());
+        let mut i = i;
+        for idx in 0..N::to_usize() {
+            if idx > 0 {
+                i = tag(",")(i)?.0;
+            }
+            let (rest, w) = W::parse_text(i)?;
+            weights.push(w);
+            i = rest;
+        }
+        Ok((
+            i,
+            Self {
+                weights: GenericArray::clone_from_slice(&weights),
+            },
+        ))
+    }
+}
+
+test_semiring_serializable!(
+    tests_power_weight_serializable,
+    PowerWeight::<TropicalWeight, typenum::U3>,
+    PowerWeight::<TropicalWeight, typenum::U3>::try_from(vec![
+        TropicalWeight::new(0.5), TropicalWeight::new(1.2), TropicalWeight::zero()
+    ]).unwrap()
+    PowerWeight::<TropicalWeight, typenum::U3>::one()
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type LogPowerWeight = PowerWeight<LogWeight, typenum::U2>;
+
+    #[test]
+    fn test_power_weight_operations() -> Result<()> {
+        let w1 = LogPowerWeight::try_from(vec![LogWeight::new(1.0), LogWeight::new(2.0)])?;
+        let w2 = LogPowerWeight::try_from(vec![LogWeight::new(0.5), LogWeight::zero()])?;
+        let product = w1.times(&w2)?;
+        assert_eq!(product.value_at(0), Some(&LogWeight::new(1.5)));
+        assert_eq!(product.value_at(1), Some(&LogWeight::zero()));
+        assert_eq!(w1.plus(LogPowerWeight::zero())?, w1);
+        assert_eq!(w1.times(LogPowerWeight::one())?, w1);
+        assert_eq!(
+            w1.divide(&w1, DivideType::DivideAny)?,
+            LogPowerWeight::one()
+        );
+        assert_eq!(LogPowerWeight::weight_type(), "log_^2");
+        Ok(())
+    }
+
+    #[test]
+    fn test_power_weight_binary_layout() -> Result<()> {
+        let w = LogPowerWeight::try_from(vec![LogWeight::new(1.0), LogWeight::new(2.0)])?;
+        let mut data = vec![];
+        w.write_binary(&mut data)?;
+        let mut expected = 1f32.to_le_bytes().to_vec();
+        expected.extend_from_slice(&2f32.to_le_bytes());
+        assert_eq!(data, expected);
+        assert_eq!(format!("{}", w), "1,2");
+        Ok(())
+    }
+
+    #[test]
+    fn test_power_weight_try_from_wrong_size() {
+        assert!(LogPowerWeight::try_from(vec![LogWeight::one()]).is_err());
+        assert!(LogPowerWeight::try_from(vec![LogWeight::one(); 3]).is_err());
+    }
+}
@@ -144,6 +144,22 @@ macro_rules! impl_quantize_f32 {
     };
 }
 
+macro_rules! impl_quantize_f64 {
+    ($semiring: ident) => {
+        impl WeightQuantize for $semiring {
+            fn quantize_assign(&mut self, delta: f32) -> Result<()> {
+                let v = *self.value();
+                if v.is_infinite() {
+                    return Ok(());
+                }
+                let delta = delta as f64;
+                self.set_value(((v / delta) + 0.5).floor() * delta);
+                Ok(())
+            }
+        }
+    };
+}
+
 macro_rules! display_semiring {
     ($semiring:tt) => {
         use std::fmt;
@@ -175,6 +191,24 @@ macro_rules! partial_eq_and_hash_f32 {
     };
 }
 
+macro_rules! partial_eq_and_hash_f64 {
+    ($semiring:tt) => {
+        impl PartialEq for $semiring {
+            fn eq(&self, other: &Self) -> bool {
+                let w1 = *self.value();
+                let w2 = *other.value();
+                w1 <= (w2 + KDELTA as f64) && w2 <= (w1 + KDELTA as f64)
+            }
+        }
+
+        impl Hash for $semiring {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.value.hash(state)
+            }
+        }
+    };
+}
+
 pub trait SerializableSemiring: Semiring + Display {
     fn weight_type() -> String;
     fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>>;
@@ -0,0 +1,296 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::f32;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+
+use anyhow::Result;
+use nom::IResult;
+
+use crate::parsers::nom_utils::NomCustomError;
+use crate::semirings::{
+    DivideType, LogWeight, ReverseBack, Semiring, SemiringProperties, SerializableSemiring,
+    TropicalWeight, WeaklyDivisibleSemiring, WeightQuantize,
+};
+
+/// Signed log semiring: extends the log semiring with negative numbers.
+///
+/// A weight is a pair `(sign, value)` representing the real number `sign * e^-value` where
+/// `sign` is either `1` or `-1`. All the zeros are equal whatever their sign.
+#[derive(Clone, Debug, Default, Copy)]
+pub struct SignedLogWeight {
+    weight: (TropicalWeight, LogWeight),
+}
+
+fn ln_pos_exp(x: f32) -> f32 {
+    ((-x).exp()).ln_1p()
+}
+
+fn ln_neg_exp(x: f32) -> f32 {
+    (-(-x).exp()).ln_1p()
+}
+
+fn sign(positive: bool) -> TropicalWeight {
+    TropicalWeight::new(if positive { 1.0 } else { -1.0 })
+}
+
+impl SignedLogWeight {
+    pub fn value1(&self) -> &TropicalWeight {
+        &self.weight.0
+    }
+
+    pub fn value2(&self) -> &LogWeight {
+        &self.weight.1
+    }
+
+    pub fn set_value1(&mut self, new_weight: TropicalWeight) {
+        self.weight.0 = new_weight;
+    }
+
+    pub fn set_value2(&mut self, new_weight: LogWeight) {
+        self.weight.1 = new_weight;
+    }
+
+    /// Returns true if the sign of the weight is positive.
+    pub fn is_positive(&self) -> bool {
+        *self.weight.0.value() > 0.0
+    }
+
+    /// Returns the opposite of the weight : `-(sign * e^-value)`.
+    pub fn negate(&self) -> Self {
+        Self::new((sign(!self.is_positive()), self.weight.1))
+    }
+
+    // Representation in which all the zeros are positive, used for comparisons.
+    fn canonical(&self) -> (bool, LogWeight) {
+        (self.is_positive() || self.weight.1.is_zero(), self.weight.1)
+    }
+}
+
+impl Semiring for SignedLogWeight {
+    type Type = (TropicalWeight, LogWeight);
+    type ReverseWeight = SignedLogWeight;
+
+    fn zero() -> Self {
+        Self::new((TropicalWeight::new(1.0), LogWeight::zero()))
+    }
+
+    fn one() -> Self {
+        Self::new((TropicalWeight::new(1.0), LogWeight::one()))
+    }
+
+    fn new(weight: <Self as Semiring>::Type) -> Self {
+        Self { weight }
+    }
+
+    fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        let rhs = rhs.borrow();
+        let s1 = self.is_positive();
+        let s2 = rhs.is_positive();
+        let f1 = *self.weight.1.value();
+        let f2 = *rhs.weight.1.value();
+        if f1.eq(&f32::INFINITY) {
+            *self = *rhs;
+        } else if f2.eq(&f32::INFINITY) {
+        } else if f1 == f2 {
+            if s1 == s2 {
+                self.weight.1 = LogWeight::new(f1 - f32::consts::LN_2);
+            } else {
+                *self = Self::zero();
+            }
+        } else if f1 < f2 {
+            let v = if s1 == s2 {
+                f1 - ln_pos_exp(f2 - f1)
+            } else {
+                f1 - ln_neg_exp(f2 - f1)
+            };
+            self.weight.1 = LogWeight::new(v);
+        } else {
+            let v = if s1 == s2 {
+                f2 - ln_pos_exp(f1 - f2)
+            } else {
+                f2 - ln_neg_exp(f1 - f2)
+            };
+            *self = Self::new((sign(s2), LogWeight::new(v)));
+        }
+        Ok(())
+    }
+
+    fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        let rhs = rhs.borrow();
+        self.weight.0 = sign(self.is_positive() == rhs.is_positive());
+        self.weight.1.times_assign(rhs.weight.1)?;
+        Ok(())
+    }
+
+    fn approx_equal<P: Borrow<Self>>(&self, rhs: P, delta: f32) -> bool {
+        let rhs = rhs.borrow();
+        if self.is_positive() == rhs.is_positive() {
+            self.weight.1.approx_equal(rhs.weight.1, delta)
+        } else {
+            self.weight.1.approx_equal(LogWeight::zero(), delta)
+                && rhs.weight.1.approx_equal(LogWeight::zero(), delta)
+        }
+    }
+
+    fn value(&self) -> &<Self as Semiring>::Type {
+        &self.weight
+    }
+
+    fn take_value(self) -> <Self as Semiring>::Type {
+        self.weight
+    }
+
+    fn set_value(&mut self, value: <Self as Semiring>::Type) {
+        self.weight = value;
+    }
+
+    fn reverse(&self) -> Result<Self::ReverseWeight> {
+        Ok(*self)
+    }
+
+    fn properties() -> SemiringProperties {
+        SemiringProperties::LEFT_SEMIRING
+            | SemiringProperties::RIGHT_SEMIRING
+            | SemiringProperties::COMMUTATIVE
+    }
+}
+
+impl ReverseBack<SignedLogWeight> for SignedLogWeight {
+    fn reverse_back(&self) -> Result<SignedLogWeight> {
+        Ok(*self)
+    }
+}
+
+impl AsRef<SignedLogWeight> for SignedLogWeight {
+    fn as_ref(&self) -> &SignedLogWeight {
+        self
+    }
+}
+
+impl PartialEq for SignedLogWeight {
+    fn eq(&self, other: &Self) -> bool {
+        self.canonical() == other.canonical()
+    }
+}
+
+impl Eq for SignedLogWeight {}
+
+impl PartialOrd for SignedLogWeight {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.canonical().partial_cmp(&other.canonical())
+    }
+}
+
+impl Hash for SignedLogWeight {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical().hash(state)
+    }
+}
+
+impl WeaklyDivisibleSemiring for SignedLogWeight {
+    fn divide_assign(&mut self, rhs: &Self, divide_type: DivideType) -> Result<()> {
+        if rhs.weight.1.is_zero() {
+            bail!("SignedLogWeight : division by zero");
+        }
+        self.weight.0 = sign(self.is_positive() == rhs.is_positive());
+        self.weight.1.divide_assign(&rhs.weight.1, divide_type)?;
+        Ok(())
+    }
+}
+
+impl WeightQuantize for SignedLogWeight {
+    fn quantize_assign(&mut self, delta: f32) -> Result<()> {
+        self.weight.1.quantize_assign(delta)
+    }
+}
+
+impl From<LogWeight> for SignedLogWeight {
+    fn from(w: LogWeight) -> Self {
+        Self::new((TropicalWeight::new(1.0), w))
+    }
+}
+
+impl fmt::Display for SignedLogWeight {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.value1(), self.value2())?;
+        Ok(())
+    }
+}
+
+impl SerializableSemiring for SignedLogWeight {
+    fn weight_type() -> String {
+        "signed_log".to_string()
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+        let (i, weight_1) = TropicalWeight::parse_binary(i)?;
+        let (i, weight_2) = LogWeight::parse_binary(i)?;
+        Ok((i, Self::new((weight_1, weight_2))))
+    }
+
+    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        self.value1().write_binary(file)?;
+        self.value2().write_binary(file)?;
+        Ok(())
+    }
+
+    fn parse_text(i: &str) -> IResult<&str, Self> {
+        let (i, weight_1) = TropicalWeight::parse_text(i)?;
+        let (i, _) = nom::bytes::complete::tag(",")(i)?;
+        let (i, weight_2) = LogWeight::parse_text(i)?;
+        Ok((i, Self::new((weight_1, weight_2))))
+    }
+}
+
+test_semiring_serializable!(
+    tests_signed_log_weight_serializable,
+    SignedLogWeight,
+    SignedLogWeight::one()
+    SignedLogWeight::new((TropicalWeight::new(-1.0), LogWeight::new(0.3)))
+    SignedLogWeight::new((TropicalWeight::new(1.0), LogWeight::new(-1.2)))
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Signed log weight representing the real number `x`.
+    fn slw(x: f32) -> SignedLogWeight {
+        SignedLogWeight::new((sign(x >= 0.0), LogWeight::new(-x.abs().ln())))
+    }
+
+    #[test]
+    fn test_signed_log_weight_plus() -> Result<()> {
+        assert!(slw(2.0).plus(slw(3.0))?.approx_equal(slw(5.0), 1e-5));
+        assert!(slw(2.0).plus(slw(-3.0))?.approx_equal(slw(-1.0), 1e-5));
+        assert!(slw(-3.0).plus(slw(2.0))?.approx_equal(slw(-1.0), 1e-5));
+        assert!(slw(-2.0).plus(slw(-2.0))?.approx_equal(slw(-4.0), 1e-5));
+        assert_eq!(slw(2.0).plus(slw(-2.0))?, SignedLogWeight::zero());
+        assert_eq!(slw(-2.0).plus(SignedLogWeight::zero())?, slw(-2.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_signed_log_weight_times_divide() -> Result<()> {
+        let w = slw(-2.0).times(slw(3.0))?;
+        assert!(w.approx_equal(slw(-6.0), 1e-5));
+        assert!(w
+            .divide(&slw(-3.0), DivideType::DivideAny)?
+            .approx_equal(slw(2.0), 1e-5));
+        assert!(w
+            .divide(&SignedLogWeight::zero(), DivideType::DivideAny)
+            .is_err());
+        assert!(slw(-2.0).negate().approx_equal(slw(2.0), 1e-5));
+        Ok(())
+    }
+
+    #[test]
+    fn test_signed_log_weight_zeros_are_equal() {
+        let negative_zero = SignedLogWeight::new((TropicalWeight::new(-1.0), LogWeight::zero()));
+        assert_eq!(negative_zero, SignedLogWeight::zero());
+        assert!(negative_zero.is_zero());
+        assert_ne!(slw(-1.0), slw(1.0));
+    }
+}
@@ -0,0 +1,397 @@
+use std::borrow::Borrow;
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::Write;
+
+use anyhow::Result;
+use nom::bytes::complete::tag;
+use nom::multi::{count, many0};
+use nom::sequence::preceded;
+use nom::IResult;
+
+use crate::parsers::nom_utils::{num, NomCustomError};
+use crate::parsers::{parse_bin_i32, parse_bin_i64, write_bin_i32, write_bin_i64};
+use crate::semirings::{
+    DivideType, ReverseBack, Semiring, SemiringProperties, SerializableSemiring,
+    WeaklyDivisibleSemiring, WeightQuantize,
+};
+#[cfg(test)]
+use crate::semirings::{LogWeight, TropicalWeight};
+
+/// Key used by OpenFST to indicate the absence of component.
+const NO_KEY: i32 = -1;
+
+/// Sparse cartesian power semiring: W ^ n for an unbounded n.
+///
+/// Only the components different from a default value are stored, sorted by index. Operations
+/// are computed component-wise, including on the default values. Typically used to store
+/// feature vectors on the trs.
+#[derive(Debug, Eq, PartialOrd, PartialEq, Clone, Default, Hash)]
+pub struct SparsePowerWeight<W: Semiring> {
+    /// Default value and components different from it sorted by index.
+    weight: (W, Vec<(u32, W)>),
+}
+
+impl<W: Semiring> SparsePowerWeight<W> {
+    /// Weight whose components all have the value `default_value`.
+    pub fn with_default(default_value: W) -> Self {
+        Self {
+            weight: (default_value, vec![]),
+        }
+    }
+
+    pub fn default_value(&self) -> &W {
+        &self.weight.0
+    }
+
+    /// Value of the component `idx`.
+    pub fn value_at(&self, idx: u32) -> &W {
+        match self.weight.1.binary_search_by_key(&idx, |(k, _)| *k) {
+            Ok(pos) => &self.weight.1[pos].1,
+            Err(_) => &self.weight.0,
+        }
+    }
+
+    pub fn set_value_at(&mut self, idx: u32, weight: W) {
+        let is_default = weight == self.weight.0;
+        match self.weight.1.binary_search_by_key(&idx, |(k, _)| *k) {
+            Ok(pos) if is_default => {
+                self.weight.1.remove(pos);
+            }
+            Ok(pos) => self.weight.1[pos].1 = weight,
+            Err(_) if is_default => {}
+            Err(pos) => self.weight.1.insert(pos, (idx, weight)),
+        }
+    }
+
+    /// Iterates over the components different from the default value, sorted by index.
+    pub fn iter(&self) -> impl Iterator<Item = &(u32, W)> {
+        self.weight.1.iter()
+    }
+
+    /// Number of components different from the default value.
+    pub fn len(&self) -> usize {
+        self.weight.1.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.weight.1.is_empty()
+    }
+
+    // Applies `f` to each pair of components of `self` and `rhs`, default values included.
+    fn map_with<F>(&self, rhs: &Self, mut f: F) -> Result<Self>
+    where
+        F: FnMut(&W, &W) -> Result<W>,
+    {
+        let mut res = Self::with_default(f(&self.weight.0, &rhs.weight.0)?);
+        let mut lhs_it = self.weight.1.iter().peekable();
+        let mut rhs_it = rhs.weight.1.iter().peekable();
+        loop {
+            let (k, w) = match (lhs_it.peek(), rhs_it.peek()) {
+                (Some((k1, w1)), Some((k2, w2))) if k1 == k2 => {
+                    let r = (*k1, f(w1, w2)?);
+                    lhs_it.next();
+                    rhs_it.next();
+                    r
+                }
+                (Some((k1, w1)), Some((k2, _))) if k1 < k2 => {
+                    let r = (*k1, f(w1, &rhs.weight.0)?);
+                    lhs_it.next();
+                    r
+                }
+                (Some((k1, w1)), None) => {
+                    let r = (*k1, f(w1, &rhs.weight.0)?);
+                    lhs_it.next();
+                    r
+                }
+                (_, Some((k2, w2))) => {
+                    let r = (*k2, f(&self.weight.0, w2)?);
+                    rhs_it.next();
+                    r
+                }
+                (None, None) => break,
+            };
+            if w != res.weight.0 {
+                res.weight.1.push((k, w));
+            }
+        }
+        Ok(res)
+    }
+
+    // Applies `f` to each component, default value included.
+    fn map<W2: Semiring, F>(&self, mut f: F) -> Result<SparsePowerWeight<W2>>
+    where
+        F: FnMut(&W) -> Result<W2>,
+    {
+        let mut res = SparsePowerWeight::with_default(f(&self.weight.0)?);
+        for (k, w) in self.weight.1.iter() {
+            res.set_value_at(*k, f(w)?);
+        }
+        Ok(res)
+    }
+}
+
+impl<W: Semiring> AsRef<Self> for SparsePowerWeight<W> {
+    fn as_ref(&self) -> &SparsePowerWeight<W> {
+        self
+    }
+}
+
+impl<W: Semiring> Semiring for SparsePowerWeight<W> {
+    type Type = (W, Vec<(u32, W)>);
+    type ReverseWeight = SparsePowerWeight<W::ReverseWeight>;
+
+    fn zero() -> Self {
+        Self::with_default(W::zero())
+    }
+
+    fn one() -> Self {
+        Self::with_default(W::one())
+    }
+
+    /// Components equal to the default value are discarded.
+    fn new(value: <Self as Semiring>::Type) -> Self {
+        let (default_value, components) = value;
+        let mut w = Self::with_default(default_value);
+        for (k, v) in components {
+            w.set_value_at(k, v);
+        }
+        w
+    }
+
+    fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        *self = self.map_with(rhs.borrow(), |w1, w2| w1.plus(w2))?;
+        Ok(())
+    }
+
+    fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        *self = self.map_with(rhs.borrow(), |w1, w2| w1.times(w2))?;
+        Ok(())
+    }
+
+    fn approx_equal<P: Borrow<Self>>(&self, rhs: P, delta: f32) -> bool {
+        self.map_with(rhs.borrow(), |w1, w2| {
+            Ok(if w1.approx_equal(w2, delta) {
+                W::one()
+            } else {
+                W::zero()
+            })
+        })
+        .map(|w| w.weight.0.is_one() && w.weight.1.is_empty())
+        .unwrap_or(false)
+    }
+
+    fn value(&self) -> &<Self as Semiring>::Type {
+        &self.weight
+    }
+
+    fn take_value(self) -> <Self as Semiring>::Type {
+        self.weight
+    }
+
+    fn set_value(&mut self, value: <Self as Semiring>::Type) {
+        *self = Self::new(value);
+    }
+
+    fn reverse(&self) -> Result<Self::ReverseWeight> {
+        self.map(|w| w.reverse())
+    }
+
+    fn properties() -> SemiringProperties {
+        W::properties()
+            & (SemiringProperties::LEFT_SEMIRING
+                | SemiringProperties::RIGHT_SEMIRING
+                | SemiringProperties::COMMUTATIVE
+                | SemiringProperties::IDEMPOTENT)
+    }
+}
+
+impl<W: Semiring> ReverseBack<SparsePowerWeight<W>> for SparsePowerWeight<W::ReverseWeight> {
+    fn reverse_back(&self) -> Result<SparsePowerWeight<W>> {
+        self.map(|w| w.reverse_back())
+    }
+}
+
+impl<W: WeaklyDivisibleSemiring> WeaklyDivisibleSemiring for SparsePowerWeight<W> {
+    fn divide_assign(&mut self, rhs: &Self, divide_type: DivideType) -> Result<()> {
+        *self = self.map_with(rhs, |w1, w2| w1.divide(w2, divide_type))?;
+        Ok(())
+    }
+}
+
+impl<W: WeightQuantize> WeightQuantize for SparsePowerWeight<W> {
+    fn quantize_assign(&mut self, delta: f32) -> Result<()> {
+        *self = self.map(|w| w.quantize(delta))?;
+        Ok(())
+    }
+}
+
+impl<W: SerializableSemiring> fmt::Display for SparsePowerWeight<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.default_value())?;
+        for (k, w) in self.iter() {
+            write!(f, ",{},{}", k, w)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_bin_component<W: SerializableSemiring>(
+    i: &[u8],
+) -> IResult<&[u8], (i32, W), NomCustomError<&[u8]>> {
+    let (i, k) = parse_bin_i32(i)?;
+    let (i, w) = W::parse_binary(i)?;
+    Ok((i, (k, w)))
+}
+
+/// Keys are stored on an `i32` as in OpenFST, the ones that don't fit are rejected.
+fn write_bin_key<F: Write>(file: &mut F, k: u32) -> Result<()> {
+    let k = i32::try_from(k).map_err(|_| {
+        format_err!(
+            "SparsePowerWeight : key {} can't be serialized, the maximum is {}",
+            k,
+            i32::MAX
+        )
+    })?;
+    write_bin_i32(file, k)
+}
+
+fn parse_text_component<W: SerializableSemiring>(i: &str) -> IResult<&str, (u32, W)> {
+    let (i, k) = preceded(tag(","), num)(i)?;
+    let (i, w) = preceded(tag(","), W::parse_text)(i)?;
+    Ok((i, (k, w)))
+}
+
+/// The binary format is the one of the OpenFST `SparsePowerWeight` : the default value, the first
+/// component (with key `-1` if there is none) and the number of remaining components followed
+/// by these components.
+impl<W: SerializableSemiring> SerializableSemiring for SparsePowerWeight<W> {
+    fn weight_type() -> String {
+        format!("{}_^n", W::weight_type())
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+        let (i, default_value) = W::parse_binary(i)?;
+        let (i, first) = parse_bin_component::<W>(i)?;
+        let (i, n) = parse_bin_i64(i)?;
+        let (i, rest) = count(parse_bin_component::<W>, n.max(0) as usize)(i)?;
+        let components = std::iter::once(first)
+            .chain(rest)
+            .filter(|(k, _)| *k != NO_KEY)
+            .map(|(k, w)| (k as u32, w))
+            .collect();
+        Ok((i, Self::new((default_value, components))))
+    }
+
+    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        self.default_value().write_binary(file)?;
+        match self.weight.1.split_first() {
+            Some(((k, w), rest)) => {
+                write_bin_key(file, *k)?;
+                w.write_binary(file)?;
+                write_bin_i64(file, rest.len() as i64)?;
+                for (k, w) in rest {
+                    write_bin_key(file, *k)?;
+                    w.write_binary(file)?;
+                }
+            }
+            None => {
+                write_bin_i32(file, NO_KEY)?;
+                self.default_value().write_binary(file)?;
+                write_bin_i64(file, 0)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_text(i: &str) -> IResult<&str, Self> {
+        let (i, default_value) = W::parse_text(i)?;
+        let (i, components) = many0(parse_text_component::<W>)(i)?;
+        Ok((i, Self::new((default_value, components))))
+    }
+}
+
+test_semiring_serializable!(
+    tests_sparse_power_weight_serializable,
+    SparsePowerWeight::<TropicalWeight>,
+    SparsePowerWeight::<TropicalWeight>::one()
+    SparsePowerWeight::new((TropicalWeight::zero(), vec![(3, TropicalWeight::new(1.5))]))
+    SparsePowerWeight::new((
+        TropicalWeight::one(),
+        vec![(0, TropicalWeight::new(0.5)), (7, TropicalWeight::new(-2.0))]
+    ))
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn features(values: &[(u32, f32)]) -> SparsePowerWeight<LogWeight> {
+        SparsePowerWeight::new((
+            LogWeight::one(),
+            values
+                .iter()
+                .map(|(k, v)| (*k, LogWeight::new(*v)))
+                .collect(),
+        ))
+    }
+
+    #[test]
+    fn test_sparse_power_weight_times() -> Result<()> {
+        let w = features(&[(1, 2.0), (4, 1.0)]).times(features(&[(4, -1.0), (6, 3.0)]))?;
+        assert_eq!(w, features(&[(1, 2.0), (6, 3.0)]));
+        assert!(w.value_at(4).is_one());
+        assert_eq!(w.len(), 2);
+        assert!(w.times(SparsePowerWeight::zero())?.is_zero());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sparse_power_weight_plus_divide() -> Result<()> {
+        let w1 =
+            SparsePowerWeight::new((TropicalWeight::zero(), vec![(2, TropicalWeight::new(1.0))]));
+        let w2 = SparsePowerWeight::new((
+            TropicalWeight::zero(),
+            vec![(2, TropicalWeight::new(3.0)), (5, TropicalWeight::new(0.5))],
+        ));
+        let sum = w1.plus(&w2)?;
+        assert_eq!(sum.value_at(2), &TropicalWeight::new(1.0));
+        assert_eq!(sum.value_at(5), &TropicalWeight::new(0.5));
+        assert!(sum.value_at(0).is_zero());
+
+        let w = features(&[(1, 2.0), (4, 1.0)]);
+        let quotient = w.divide(&features(&[(1, 2.0)]), DivideType::DivideAny)?;
+        assert_eq!(quotient, features(&[(4, 1.0)]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sparse_power_weight_serialization_format() -> Result<()> {
+        let w = features(&[(1, 2.0), (4, 0.5)]);
+        assert_eq!(format!("{}", w), "0,1,2,4,0.5");
+        assert_eq!(SparsePowerWeight::<LogWeight>::weight_type(), "log_^n");
+
+        let mut data = vec![];
+        w.write_binary(&mut data)?;
+        let mut expected = vec![];
+        expected.extend_from_slice(&0f32.to_le_bytes());
+        expected.extend_from_slice(&1i32.to_le_bytes());
+        expected.extend_from_slice(&2f32.to_le_bytes());
+        expected.extend_from_slice(&1i64.to_le_bytes());
+        expected.extend_from_slice(&4i32.to_le_bytes());
+        expected.extend_from_slice(&0.5f32.to_le_bytes());
+        assert_eq!(data, expected);
+
+        for key in &[i32::MAX as u32 + 1, u32::MAX] {
+            let w = features(&[(1, 2.0), (*key, 0.5)]);
+            assert!(w.write_binary(&mut vec![]).is_err());
+        }
+        let w = features(&[(i32::MAX as u32, 0.5)]);
+        let mut data = vec![];
+        w.write_binary(&mut data)?;
+        let (_, parsed) = SparsePowerWeight::<LogWeight>::parse_binary(&data)
+            .map_err(|e| format_err!("Can't parse weight : {:?}", e))?;
+        assert_eq!(parsed, w);
+        Ok(())
+    }
+}
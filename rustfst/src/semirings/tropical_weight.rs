@@ -24,6 +24,7 @@ use crate::KDELTA;
 
 /// Tropical semiring: (min, +, inf, 0).
 #[derive(Clone, Debug, PartialOrd, Default, Copy, Eq)]
+#[repr(transparent)]
 pub struct TropicalWeight {
     value: OrderedFloat<f32>,
 }
@@ -0,0 +1,170 @@
+use std::borrow::Borrow;
+use std::f64;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+
+use anyhow::Result;
+use nom::branch::alt;
+use nom::bytes::complete::tag_no_case;
+use nom::combinator::map;
+use nom::number::complete::double;
+use nom::IResult;
+use ordered_float::OrderedFloat;
+
+use crate::parsers::nom_utils::NomCustomError;
+use crate::parsers::parse_bin_f64;
+use crate::parsers::write_bin_f64;
+use crate::semirings::semiring::SerializableSemiring;
+use crate::semirings::utils_float::float64_approx_equal;
+use crate::semirings::{
+    CompleteSemiring, DivideType, ReverseBack, Semiring, SemiringProperties, StarSemiring,
+    WeaklyDivisibleSemiring, WeightQuantize,
+};
+use crate::KDELTA;
+
+/// Tropical semiring with double precision: (min, +, inf, 0).
+#[derive(Clone, Debug, PartialOrd, Default, Copy, Eq)]
+#[repr(transparent)]
+pub struct TropicalWeight64 {
+    value: OrderedFloat<f64>,
+}
+
+impl Semiring for TropicalWeight64 {
+    type Type = f64;
+    type ReverseWeight = TropicalWeight64;
+
+    fn zero() -> Self {
+        Self {
+            value: OrderedFloat(f64::INFINITY),
+        }
+    }
+
+    fn one() -> Self {
+        Self {
+            value: OrderedFloat(0.0),
+        }
+    }
+
+    fn new(value: <Self as Semiring>::Type) -> Self {
+        TropicalWeight64 {
+            value: OrderedFloat(value),
+        }
+    }
+
+    fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        if rhs.borrow().value < self.value {
+            self.value = rhs.borrow().value;
+        }
+        Ok(())
+    }
+
+    fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        let f1 = self.value();
+        let f2 = rhs.borrow().value();
+        if f1.eq(&f64::INFINITY) {
+        } else if f2.eq(&f64::INFINITY) {
+            self.value.0 = *f2;
+        } else {
+            self.value.0 += f2;
+        }
+        Ok(())
+    }
+
+    fn approx_equal<P: Borrow<Self>>(&self, rhs: P, delta: f32) -> bool {
+        float64_approx_equal(self.value.0, rhs.borrow().value.0, delta)
+    }
+
+    fn value(&self) -> &Self::Type {
+        &self.value.0
+    }
+
+    fn take_value(self) -> Self::Type {
+        self.value.0
+    }
+
+    fn set_value(&mut self, value: <Self as Semiring>::Type) {
+        self.value.0 = value
+    }
+
+    fn reverse(&self) -> Result<Self::ReverseWeight> {
+        Ok(*self)
+    }
+
+    fn properties() -> SemiringProperties {
+        SemiringProperties::LEFT_SEMIRING
+            | SemiringProperties::RIGHT_SEMIRING
+            | SemiringProperties::COMMUTATIVE
+            | SemiringProperties::PATH
+            | SemiringProperties::IDEMPOTENT
+    }
+}
+
+impl ReverseBack<TropicalWeight64> for TropicalWeight64 {
+    fn reverse_back(&self) -> Result<TropicalWeight64> {
+        Ok(*self)
+    }
+}
+
+impl AsRef<TropicalWeight64> for TropicalWeight64 {
+    fn as_ref(&self) -> &TropicalWeight64 {
+        self
+    }
+}
+
+display_semiring!(TropicalWeight64);
+
+impl CompleteSemiring for TropicalWeight64 {}
+
+impl StarSemiring for TropicalWeight64 {
+    fn closure(&self) -> Self {
+        if self.value.is_sign_positive() && self.value.is_finite() {
+            Self::new(0.0)
+        } else {
+            Self::new(f64::NEG_INFINITY)
+        }
+    }
+}
+
+impl WeaklyDivisibleSemiring for TropicalWeight64 {
+    fn divide_assign(&mut self, rhs: &Self, _divide_type: DivideType) -> Result<()> {
+        self.value.0 -= rhs.value.0;
+        Ok(())
+    }
+}
+
+impl_quantize_f64!(TropicalWeight64);
+
+partial_eq_and_hash_f64!(TropicalWeight64);
+
+impl SerializableSemiring for TropicalWeight64 {
+    fn weight_type() -> String {
+        "tropical64".to_string()
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+        let (i, weight) = parse_bin_f64(i)?;
+        Ok((i, Self::new(weight)))
+    }
+
+    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        write_bin_f64(file, *self.value())
+    }
+
+    fn parse_text(i: &str) -> IResult<&str, Self> {
+        // Same workaround as for `TropicalWeight` : "infinity" is parsed manually.
+        let (i, f) = alt((map(tag_no_case("infinity"), |_| f64::INFINITY), double))(i)?;
+        Ok((i, Self::new(f)))
+    }
+}
+
+test_semiring_serializable!(
+    tests_tropical_weight64_serializable,
+    TropicalWeight64,
+    TropicalWeight64::one() TropicalWeight64::zero() TropicalWeight64::new(0.3) TropicalWeight64::new(0.5) TropicalWeight64::new(0.0) TropicalWeight64::new(-1.2)
+);
+
+impl From<f64> for TropicalWeight64 {
+    fn from(f: f64) -> Self {
+        Self::new(f)
+    }
+}
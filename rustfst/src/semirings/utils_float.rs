@@ -1,3 +1,7 @@
 pub(crate) fn float_approx_equal(w1: f32, w2: f32, delta: f32) -> bool {
     (w1 - w2).abs() <= delta
 }
+
+pub(crate) fn float64_approx_equal(w1: f64, w2: f64, delta: f32) -> bool {
+    (w1 - w2).abs() <= delta as f64
+}
@@ -2,7 +2,9 @@ use crate::semirings::SerializableSemiring;
 use crate::{Label, StateId};
 
 /// Structure representing a transition from a state to another state in a FST.
+// The layout is fixed so that the trs of an aligned `ConstFst` file can be used in place.
 #[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Hash)]
+#[repr(C)]
 pub struct Tr<W> {
     /// Input label.
     pub ilabel: Label,